@@ -36,12 +36,15 @@ use std::env;
 use std::future;
 use std::time::Duration;
 
+use axum::middleware;
+use axum::routing::get;
 use axum::routing::post;
 use axum::Router;
 use bb8_postgres::bb8::Pool;
 use bb8_postgres::tokio_postgres::NoTls;
 use bb8_postgres::PostgresConnectionManager;
 use dotenvy::Error;
+use hartex_backend_layers::staff::StaffToken;
 use hartex_errors::dotenv;
 use hartex_log::log;
 use miette::IntoDiagnostic;
@@ -77,6 +80,17 @@ pub async fn main() -> miette::Result<()> {
     let manager = PostgresConnectionManager::new_from_stringlike(api_pgsql_url, NoTls).into_diagnostic()?;
     let pool = Pool::builder().build(manager).await.into_diagnostic()?;
 
+    // error reports are stored in the database of the discord frontend
+    let hartex_pgsql_url = env::var("HARTEX_NIGHTLY_PGSQL_URL").into_diagnostic()?;
+
+    log::debug!("building discord frontend database connection pool");
+    let manager = PostgresConnectionManager::new_from_stringlike(hartex_pgsql_url, NoTls).into_diagnostic()?;
+    let frontend_pool = Pool::builder().build(manager).await.into_diagnostic()?;
+
+    // error reports contain details of the guilds and users encountering errors, so they are only
+    // accessible to staff
+    let staff_token = StaffToken::new(env::var("API_STAFF_TOKEN").into_diagnostic()?);
+
     log::debug!("starting axum server");
     let app = Router::new()
         .layer(TraceLayer::new_for_http())
@@ -86,7 +100,19 @@ pub async fn main() -> miette::Result<()> {
             post(hartex_backend_routes::uptime::post_uptime)
                 .patch(hartex_backend_routes::uptime::patch_uptime),
         )
-        .with_state(pool);
+        .with_state(pool)
+        .merge(
+            Router::new()
+                .route(
+                    "/api/:version/errors/:code",
+                    get(hartex_backend_routes::errors::get_error_report),
+                )
+                .route_layer(middleware::from_fn_with_state(
+                    staff_token,
+                    hartex_backend_layers::staff::require_staff_token,
+                ))
+                .with_state(frontend_pool),
+        );
 
     let domain = env::var("API_DOMAIN").into_diagnostic()?;
     let listener = TcpListener::bind(&domain).await.into_diagnostic()?;
@@ -27,3 +27,5 @@
 #![deny(clippy::pedantic)]
 #![deny(unsafe_code)]
 #![deny(warnings)]
+
+pub mod staff;
@@ -0,0 +1,82 @@
+/*
+ * SPDX-License-Identifier: AGPL-3.0-only
+ *
+ * This file is part of HarTex.
+ *
+ * HarTex
+ * Copyright (c) 2021-2024 HarTex Project Developers
+ *
+ * HarTex is free software; you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation; either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * HarTex is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License along
+ * with HarTex. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! # Staff Authorization Layer
+//!
+//! Routes only accessible to the staff of HarTex require requests to carry the staff token as a
+//! bearer token in their `Authorization` header.
+
+use axum::extract::Request;
+use axum::extract::State;
+use axum::http::header::AUTHORIZATION;
+use axum::http::StatusCode;
+use axum::middleware::Next;
+use axum::response::IntoResponse;
+use axum::response::Response as AxumResponse;
+use hartex_backend_models::Response;
+
+/// The token authorizing requests from the staff of HarTex.
+#[derive(Clone)]
+pub struct StaffToken(String);
+
+impl StaffToken {
+    /// Constructs a staff token.
+    #[must_use]
+    pub fn new(token: String) -> Self {
+        Self(token)
+    }
+
+    /// Whether the value of an `Authorization` header carries the staff token.
+    ///
+    /// The tokens are compared in constant time, such that the staff token cannot be guessed from
+    /// the time taken to reject a request.
+    fn authorizes(&self, authorization: &str) -> bool {
+        let Some(token) = authorization.strip_prefix("Bearer ") else {
+            return false;
+        };
+
+        token.len() == self.0.len()
+            && token
+                .bytes()
+                .zip(self.0.bytes())
+                .fold(0, |difference, (left, right)| difference | (left ^ right))
+                == 0
+    }
+}
+
+/// Rejects requests that do not carry the staff token with a status code of 401.
+pub async fn require_staff_token(
+    State(token): State<StaffToken>,
+    request: Request,
+    next: Next,
+) -> AxumResponse {
+    let authorized = request
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| token.authorizes(value));
+    if !authorized {
+        return (StatusCode::UNAUTHORIZED, Response::<()>::unauthorized()).into_response();
+    }
+
+    next.run(request).await
+}
@@ -0,0 +1,135 @@
+/*
+ * SPDX-License-Identifier: AGPL-3.0-only
+ *
+ * This file is part of HarTex.
+ *
+ * HarTex
+ * Copyright (c) 2021-2024 HarTex Project Developers
+ *
+ * HarTex is free software; you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation; either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * HarTex is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License along
+ * with HarTex. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! # Error Report Models
+//!
+//! Models for the error report API specification of the backend.
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// A response to an error report query.
+#[allow(clippy::module_name_repetitions)]
+#[derive(Clone, Deserialize, Serialize)]
+pub struct ErrorReportResponse {
+    code: String,
+    kind: String,
+    report: String,
+    command: Option<String>,
+    options: Option<String>,
+    guild_id: Option<String>,
+    user_id: Option<String>,
+    shard_id: i64,
+    shard_count: i64,
+    timestamp: i64,
+}
+
+impl ErrorReportResponse {
+    /// Constructs a response to an error report query with the information of the error report.
+    #[allow(clippy::too_many_arguments)]
+    #[must_use]
+    pub fn new(
+        code: String,
+        kind: String,
+        report: String,
+        command: Option<String>,
+        options: Option<String>,
+        guild_id: Option<String>,
+        user_id: Option<String>,
+        shard_id: i64,
+        shard_count: i64,
+        timestamp: i64,
+    ) -> Self {
+        Self {
+            code,
+            kind,
+            report,
+            command,
+            options,
+            guild_id,
+            user_id,
+            shard_id,
+            shard_count,
+            timestamp,
+        }
+    }
+
+    /// The error code of the error report.
+    #[must_use]
+    pub fn code(&self) -> &str {
+        self.code.as_str()
+    }
+
+    /// The kind of the error, either `error` or `panic`.
+    #[must_use]
+    pub fn kind(&self) -> &str {
+        self.kind.as_str()
+    }
+
+    /// The full report or panic message of the error.
+    #[must_use]
+    pub fn report(&self) -> &str {
+        self.report.as_str()
+    }
+
+    /// The command, or the custom ID of the component or modal, that encountered the error.
+    #[must_use]
+    pub fn command(&self) -> Option<&str> {
+        self.command.as_deref()
+    }
+
+    /// The options the command was invoked with, serialized as JSON.
+    #[must_use]
+    pub fn options(&self) -> Option<&str> {
+        self.options.as_deref()
+    }
+
+    /// The ID of the guild the error was encountered in.
+    #[must_use]
+    pub fn guild_id(&self) -> Option<&str> {
+        self.guild_id.as_deref()
+    }
+
+    /// The ID of the user encountering the error.
+    #[must_use]
+    pub fn user_id(&self) -> Option<&str> {
+        self.user_id.as_deref()
+    }
+
+    /// The ID of the shard receiving the interaction that encountered the error.
+    #[must_use]
+    pub fn shard_id(&self) -> i64 {
+        self.shard_id
+    }
+
+    /// The total number of shards when the error was encountered.
+    #[must_use]
+    pub fn shard_count(&self) -> i64 {
+        self.shard_count
+    }
+
+    /// The Unix timestamp of when the error was encountered.
+    #[must_use]
+    pub fn timestamp(&self) -> i64 {
+        self.timestamp
+    }
+}
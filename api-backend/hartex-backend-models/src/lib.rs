@@ -43,6 +43,7 @@ use serde::Deserialize;
 use serde::Serialize;
 
 pub use hartex_discord_configuration_models as config;
+pub mod errors;
 pub mod uptime;
 
 /// Specifies the API version to be used for a given API request.
@@ -97,6 +98,24 @@ where
         })
     }
 
+    /// Constructs a response object with a status code of 404 and its corresponding message.
+    pub fn not_found() -> Json<Response<T>> {
+        Json(Self {
+            code: 404,
+            message: String::from("not found"),
+            data: None,
+        })
+    }
+
+    /// Constructs a response object with a status code of 401 and its corresponding message.
+    pub fn unauthorized() -> Json<Response<T>> {
+        Json(Self {
+            code: 401,
+            message: String::from("unauthorized"),
+            data: None,
+        })
+    }
+
     /// Constructs a response object with a status code of 200 and its corresponding message.
     pub fn ok(value: T) -> Json<Response<T>> {
         Json(Self {
@@ -0,0 +1,96 @@
+/*
+ * SPDX-License-Identifier: AGPL-3.0-only
+ *
+ * This file is part of HarTex.
+ *
+ * HarTex
+ * Copyright (c) 2021-2024 HarTex Project Developers
+ *
+ * HarTex is free software; you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation; either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * HarTex is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License along
+ * with HarTex. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+/// # Error Report Routes
+///
+/// Routes interacting with the error report API.
+use axum::extract::Path;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::Json;
+use bb8_postgres::bb8::Pool;
+use bb8_postgres::tokio_postgres::GenericClient;
+use bb8_postgres::tokio_postgres::NoTls;
+use bb8_postgres::PostgresConnectionManager;
+use hartex_backend_models::errors::ErrorReportResponse;
+use hartex_backend_models::APIVersion;
+use hartex_backend_models::Response;
+use hartex_database_queries::discord_frontend::queries::error_report_select_by_code::error_report_select_by_code;
+use hartex_log::log;
+
+/// # `GET /errors/:code`
+///
+/// Obtain the error report of a certain error code.
+#[allow(clippy::missing_panics_doc)] // this function cannot panic
+#[allow(clippy::module_name_repetitions)]
+pub async fn get_error_report(
+    _: APIVersion,
+    State(pool): State<Pool<PostgresConnectionManager<NoTls>>>,
+    Path((_, code)): Path<(String, String)>,
+) -> (StatusCode, Json<Response<ErrorReportResponse>>) {
+    log::trace!("retrieving connection from database pool");
+    let result = pool.get().await;
+    if result.is_err() {
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Response::internal_server_error(),
+        );
+    }
+
+    let connection = result.unwrap();
+    let client = connection.client();
+
+    log::trace!("querying error report");
+    let result = error_report_select_by_code()
+        .bind(client, &code)
+        .opt()
+        .await;
+
+    let data = match result {
+        Ok(Some(data)) => data,
+        Ok(None) => return (StatusCode::NOT_FOUND, Response::not_found()),
+        Err(error) => {
+            log::error!("{error:?}");
+
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Response::internal_server_error(),
+            );
+        }
+    };
+
+    (
+        StatusCode::OK,
+        Response::ok(ErrorReportResponse::new(
+            data.code,
+            data.kind,
+            data.report,
+            data.command,
+            data.options,
+            data.guild_id,
+            data.user_id,
+            data.shard_id,
+            data.shard_count,
+            data.timestamp.unix_timestamp(),
+        )),
+    )
+}
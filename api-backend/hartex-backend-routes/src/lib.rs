@@ -28,4 +28,5 @@
 #![deny(unsafe_code)]
 #![deny(warnings)]
 
+pub mod errors;
 pub mod uptime;
@@ -0,0 +1,8 @@
+CREATE TABLE IF NOT EXISTS "Nightly"."GatewaySessions" (
+    "shard_id" BIGINT NOT NULL,
+    "shard_count" BIGINT NOT NULL,
+    "session_id" TEXT NOT NULL,
+    "sequence" BIGINT NOT NULL,
+    "resume_url" TEXT NOT NULL,
+    PRIMARY KEY("shard_id", "shard_count")
+);
@@ -0,0 +1,5 @@
+CREATE TABLE IF NOT EXISTS "Nightly"."ProcessedEvents" (
+    "key" TEXT NOT NULL,
+    "processed_at" TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+    PRIMARY KEY("key")
+);
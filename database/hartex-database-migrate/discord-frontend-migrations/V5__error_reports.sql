@@ -0,0 +1,13 @@
+CREATE TABLE IF NOT EXISTS "Nightly"."ErrorReports" (
+    "code" TEXT NOT NULL,
+    "kind" TEXT NOT NULL,
+    "report" TEXT NOT NULL,
+    "command" TEXT,
+    "options" TEXT,
+    "guild_id" TEXT,
+    "user_id" TEXT,
+    "shard_id" BIGINT NOT NULL,
+    "shard_count" BIGINT NOT NULL,
+    "timestamp" TIMESTAMP WITH TIME ZONE NOT NULL,
+    PRIMARY KEY("code")
+);
@@ -0,0 +1,7 @@
+CREATE TABLE IF NOT EXISTS "Nightly"."CommandCooldowns" (
+    "command" TEXT NOT NULL,
+    "bucket" TEXT NOT NULL,
+    "uses" BIGINT NOT NULL,
+    "expires_at" TIMESTAMP WITH TIME ZONE NOT NULL,
+    PRIMARY KEY("command", "bucket")
+);
@@ -0,0 +1,8 @@
+CREATE TABLE IF NOT EXISTS "Nightly"."ShardLatencies" (
+    "shard_id" BIGINT NOT NULL,
+    "shard_count" BIGINT NOT NULL,
+    "average_latency" BIGINT,
+    "latest_latency" BIGINT,
+    "updated_at" TIMESTAMP WITH TIME ZONE NOT NULL,
+    PRIMARY KEY("shard_id", "shard_count")
+);
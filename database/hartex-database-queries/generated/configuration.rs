@@ -3,7 +3,84 @@
 #[allow(clippy::all, clippy::pedantic)] #[allow(unused_variables)]
 #[allow(unused_imports)] #[allow(dead_code)] pub mod types { }#[allow(clippy::all, clippy::pedantic)] #[allow(unused_variables)]
 #[allow(unused_imports)] #[allow(dead_code)] pub mod queries
-{ pub mod management_plugin_enabled
+{ pub mod command_cooldown_select
+{ use futures::{{StreamExt, TryStreamExt}};use futures; use cornucopia_async::GenericClient;#[derive( Debug)] pub struct CommandCooldownSelectParams<T1: cornucopia_async::StringSql,T2: cornucopia_async::StringSql,> { pub command: T1,pub guild_id: T2,}#[derive( Debug, Clone, PartialEq,)] pub struct CommandCooldownSelect
+{ pub per : Option<String>,pub duration : Option<i64>,pub burst : Option<i64>,}pub struct CommandCooldownSelectBorrowed<'a> { pub per : Option<&'a str>,pub duration : Option<i64>,pub burst : Option<i64>,}
+impl<'a> From<CommandCooldownSelectBorrowed<'a>> for CommandCooldownSelect
+{
+    fn from(CommandCooldownSelectBorrowed { per,duration,burst,}: CommandCooldownSelectBorrowed<'a>) ->
+    Self { Self { per: per.map(|v| v.into()),duration,burst,} }
+}pub struct CommandCooldownSelectQuery<'a, C: GenericClient, T, const N: usize>
+{
+    client: &'a  C, params:
+    [&'a (dyn postgres_types::ToSql + Sync); N], stmt: &'a mut
+    cornucopia_async::private::Stmt, extractor: fn(&tokio_postgres::Row) -> CommandCooldownSelectBorrowed,
+    mapper: fn(CommandCooldownSelectBorrowed) -> T,
+} impl<'a, C, T:'a, const N: usize> CommandCooldownSelectQuery<'a, C, T, N> where C:
+GenericClient
+{
+    pub fn map<R>(self, mapper: fn(CommandCooldownSelectBorrowed) -> R) ->
+    CommandCooldownSelectQuery<'a,C,R,N>
+    {
+        CommandCooldownSelectQuery
+        {
+            client: self.client, params: self.params, stmt: self.stmt,
+            extractor: self.extractor, mapper,
+        }
+    } pub async fn one(self) -> Result<T, tokio_postgres::Error>
+    {
+        let stmt = self.stmt.prepare(self.client).await?; let row =
+        self.client.query_one(stmt, &self.params).await?;
+        Ok((self.mapper)((self.extractor)(&row)))
+    } pub async fn all(self) -> Result<Vec<T>, tokio_postgres::Error>
+    { self.iter().await?.try_collect().await } pub async fn opt(self) ->
+    Result<Option<T>, tokio_postgres::Error>
+    {
+        let stmt = self.stmt.prepare(self.client).await?;
+        Ok(self.client.query_opt(stmt, &self.params) .await?
+        .map(|row| (self.mapper)((self.extractor)(&row))))
+    } pub async fn iter(self,) -> Result<impl futures::Stream<Item = Result<T,
+    tokio_postgres::Error>> + 'a, tokio_postgres::Error>
+    {
+        let stmt = self.stmt.prepare(self.client).await?; let it =
+        self.client.query_raw(stmt,
+        cornucopia_async::private::slice_iter(&self.params)) .await?
+        .map(move |res|
+        res.map(|row| (self.mapper)((self.extractor)(&row)))) .into_stream();
+        Ok(it)
+    }
+}pub fn command_cooldown_select() -> CommandCooldownSelectStmt
+{ CommandCooldownSelectStmt(cornucopia_async::private::Stmt::new("SELECT
+    configuration -> 'cooldowns' -> $1 ->> 'per' AS \"per\",
+    CAST(configuration -> 'cooldowns' -> $1 ->> 'duration' AS BIGINT) AS \"duration\",
+    CAST(configuration -> 'cooldowns' -> $1 ->> 'burst' AS BIGINT) AS \"burst\"
+FROM
+    \"Nightly\".\"GuildConfigurations\"
+WHERE
+    guild_id = $2")) } pub struct
+CommandCooldownSelectStmt(cornucopia_async::private::Stmt); impl CommandCooldownSelectStmt
+{ pub fn bind<'a, C:
+GenericClient,T1:
+cornucopia_async::StringSql,T2:
+cornucopia_async::StringSql,>(&'a mut self, client: &'a  C,
+command: &'a T1,guild_id: &'a T2,) -> CommandCooldownSelectQuery<'a,C,
+CommandCooldownSelect, 2>
+{
+    CommandCooldownSelectQuery
+    {
+        client, params: [command,guild_id,], stmt: &mut self.0, extractor:
+        |row| { CommandCooldownSelectBorrowed { per: row.get(0),duration: row.get(1),burst: row.get(2),} }, mapper: |it| { <CommandCooldownSelect>::from(it) },
+    }
+} }impl <'a, C: GenericClient,T1: cornucopia_async::StringSql,T2: cornucopia_async::StringSql,> cornucopia_async::Params<'a,
+CommandCooldownSelectParams<T1,T2,>, CommandCooldownSelectQuery<'a, C,
+CommandCooldownSelect, 2>, C> for CommandCooldownSelectStmt
+{
+    fn
+    params(&'a mut self, client: &'a  C, params: &'a
+    CommandCooldownSelectParams<T1,T2,>) -> CommandCooldownSelectQuery<'a, C,
+    CommandCooldownSelect, 2>
+    { self.bind(client, &params.command,&params.guild_id,) }
+}}pub mod management_plugin_enabled
 { use futures::{{StreamExt, TryStreamExt}};use futures; use cornucopia_async::GenericClient;pub struct SerdejsonValueQuery<'a, C: GenericClient, T, const N: usize>
 {
     client: &'a  C, params:
@@ -162,7 +162,63 @@ tokio_postgres::Error>> + Send + 'a>>, C> for CachedEmojiUpsertStmt
     CachedEmojiUpsertParams<T1,T2,T3,>) -> std::pin::Pin<Box<dyn futures::Future<Output = Result<u64,
     tokio_postgres::Error>> + Send + 'a>>
     { Box::pin(self.bind(client, &params.animated,&params.name,&params.id,&params.guild_id,&params.managed,)) }
-}}pub mod cached_guild_select_by_id
+}}pub mod cached_guild_count
+{ use futures::{{StreamExt, TryStreamExt}};use futures; use cornucopia_async::GenericClient;pub struct I64Query<'a, C: GenericClient, T, const N: usize>
+{
+    client: &'a  C, params:
+    [&'a (dyn postgres_types::ToSql + Sync); N], stmt: &'a mut
+    cornucopia_async::private::Stmt, extractor: fn(&tokio_postgres::Row) -> i64,
+    mapper: fn(i64) -> T,
+} impl<'a, C, T:'a, const N: usize> I64Query<'a, C, T, N> where C:
+GenericClient
+{
+    pub fn map<R>(self, mapper: fn(i64) -> R) ->
+    I64Query<'a,C,R,N>
+    {
+        I64Query
+        {
+            client: self.client, params: self.params, stmt: self.stmt,
+            extractor: self.extractor, mapper,
+        }
+    } pub async fn one(self) -> Result<T, tokio_postgres::Error>
+    {
+        let stmt = self.stmt.prepare(self.client).await?; let row =
+        self.client.query_one(stmt, &self.params).await?;
+        Ok((self.mapper)((self.extractor)(&row)))
+    } pub async fn all(self) -> Result<Vec<T>, tokio_postgres::Error>
+    { self.iter().await?.try_collect().await } pub async fn opt(self) ->
+    Result<Option<T>, tokio_postgres::Error>
+    {
+        let stmt = self.stmt.prepare(self.client).await?;
+        Ok(self.client.query_opt(stmt, &self.params) .await?
+        .map(|row| (self.mapper)((self.extractor)(&row))))
+    } pub async fn iter(self,) -> Result<impl futures::Stream<Item = Result<T,
+    tokio_postgres::Error>> + 'a, tokio_postgres::Error>
+    {
+        let stmt = self.stmt.prepare(self.client).await?; let it =
+        self.client.query_raw(stmt,
+        cornucopia_async::private::slice_iter(&self.params)) .await?
+        .map(move |res|
+        res.map(|row| (self.mapper)((self.extractor)(&row)))) .into_stream();
+        Ok(it)
+    }
+}pub fn cached_guild_count() -> CachedGuildCountStmt
+{ CachedGuildCountStmt(cornucopia_async::private::Stmt::new("SELECT
+    COUNT(*) AS \"count\"
+FROM
+    \"DiscordFrontend\".\"Nightly\".\"CachedGuilds\"")) } pub struct
+CachedGuildCountStmt(cornucopia_async::private::Stmt); impl CachedGuildCountStmt
+{ pub fn bind<'a, C:
+GenericClient,>(&'a mut self, client: &'a  C,
+) -> I64Query<'a,C,
+i64, 0>
+{
+    I64Query
+    {
+        client, params: [], stmt: &mut self.0, extractor:
+        |row| { row.get(0) }, mapper: |it| { it },
+    }
+} }}pub mod cached_guild_select_by_id
 { use futures::{{StreamExt, TryStreamExt}};use futures; use cornucopia_async::GenericClient;#[derive( Debug, Clone, PartialEq,)] pub struct CachedGuildSelectById
 { pub default_message_notifications : i16,pub explicit_content_filter : i16,pub features : Vec<String>,pub icon : Option<String>,pub id : String,pub large : bool,pub mfa_level : i16,pub name : String,pub owner_id : String,pub premium_subscription_count : Option<i64>,pub premium_tier : i16,pub verification_level : i16,}pub struct CachedGuildSelectByIdBorrowed<'a> { pub default_message_notifications : i16,pub explicit_content_filter : i16,pub features : cornucopia_async::ArrayIterator<'a, &'a str>,pub icon : Option<&'a str>,pub id : &'a str,pub large : bool,pub mfa_level : i16,pub name : &'a str,pub owner_id : &'a str,pub premium_subscription_count : Option<i64>,pub premium_tier : i16,pub verification_level : i16,}
 impl<'a> From<CachedGuildSelectByIdBorrowed<'a>> for CachedGuildSelectById
@@ -709,4 +765,465 @@ tokio_postgres::Error>> + Send + 'a>>, C> for CachedUserUpsertStmt
     CachedUserUpsertParams<T1,T2,T3,T4,T5,>) -> std::pin::Pin<Box<dyn futures::Future<Output = Result<u64,
     tokio_postgres::Error>> + Send + 'a>>
     { Box::pin(self.bind(client, &params.avatar,&params.id,&params.bot,&params.name,&params.discriminator,&params.global_name,)) }
+}}pub mod command_cooldown_delete_expired
+{ use futures::{{StreamExt, TryStreamExt}};use futures; use cornucopia_async::GenericClient;pub fn command_cooldown_delete_expired() -> CommandCooldownDeleteExpiredStmt
+{ CommandCooldownDeleteExpiredStmt(cornucopia_async::private::Stmt::new("DELETE FROM \"DiscordFrontend\".\"Nightly\".\"CommandCooldowns\"
+WHERE \"expires_at\" <= now()")) } pub struct
+CommandCooldownDeleteExpiredStmt(cornucopia_async::private::Stmt); impl CommandCooldownDeleteExpiredStmt
+{ pub async fn bind<'a, C:
+GenericClient,>(&'a mut self, client: &'a  C,
+) -> Result<u64, tokio_postgres::Error>
+{
+    let stmt = self.0.prepare(client).await?;
+    client.execute(stmt, &[]).await
+} }}pub mod command_cooldown_hit
+{ use futures::{{StreamExt, TryStreamExt}};use futures; use cornucopia_async::GenericClient;#[derive( Debug)] pub struct CommandCooldownHitParams<T1: cornucopia_async::StringSql,T2: cornucopia_async::StringSql,> { pub command: T1,pub bucket: T2,pub duration: i64,}#[derive( Debug, Clone, PartialEq,)] pub struct CommandCooldownHit
+{ pub uses : i64,pub expires_at : time::OffsetDateTime,}pub struct CommandCooldownHitBorrowed<'a> { pub uses : i64,pub expires_at : time::OffsetDateTime,}
+impl<'a> From<CommandCooldownHitBorrowed<'a>> for CommandCooldownHit
+{
+    fn from(CommandCooldownHitBorrowed { uses,expires_at,}: CommandCooldownHitBorrowed<'a>) ->
+    Self { Self { uses,expires_at,} }
+}pub struct CommandCooldownHitQuery<'a, C: GenericClient, T, const N: usize>
+{
+    client: &'a  C, params:
+    [&'a (dyn postgres_types::ToSql + Sync); N], stmt: &'a mut
+    cornucopia_async::private::Stmt, extractor: fn(&tokio_postgres::Row) -> CommandCooldownHitBorrowed,
+    mapper: fn(CommandCooldownHitBorrowed) -> T,
+} impl<'a, C, T:'a, const N: usize> CommandCooldownHitQuery<'a, C, T, N> where C:
+GenericClient
+{
+    pub fn map<R>(self, mapper: fn(CommandCooldownHitBorrowed) -> R) ->
+    CommandCooldownHitQuery<'a,C,R,N>
+    {
+        CommandCooldownHitQuery
+        {
+            client: self.client, params: self.params, stmt: self.stmt,
+            extractor: self.extractor, mapper,
+        }
+    } pub async fn one(self) -> Result<T, tokio_postgres::Error>
+    {
+        let stmt = self.stmt.prepare(self.client).await?; let row =
+        self.client.query_one(stmt, &self.params).await?;
+        Ok((self.mapper)((self.extractor)(&row)))
+    } pub async fn all(self) -> Result<Vec<T>, tokio_postgres::Error>
+    { self.iter().await?.try_collect().await } pub async fn opt(self) ->
+    Result<Option<T>, tokio_postgres::Error>
+    {
+        let stmt = self.stmt.prepare(self.client).await?;
+        Ok(self.client.query_opt(stmt, &self.params) .await?
+        .map(|row| (self.mapper)((self.extractor)(&row))))
+    } pub async fn iter(self,) -> Result<impl futures::Stream<Item = Result<T,
+    tokio_postgres::Error>> + 'a, tokio_postgres::Error>
+    {
+        let stmt = self.stmt.prepare(self.client).await?; let it =
+        self.client.query_raw(stmt,
+        cornucopia_async::private::slice_iter(&self.params)) .await?
+        .map(move |res|
+        res.map(|row| (self.mapper)((self.extractor)(&row)))) .into_stream();
+        Ok(it)
+    }
+}pub fn command_cooldown_hit() -> CommandCooldownHitStmt
+{ CommandCooldownHitStmt(cornucopia_async::private::Stmt::new("INSERT INTO \"DiscordFrontend\".\"Nightly\".\"CommandCooldowns\" (\"command\", \"bucket\", \"uses\", \"expires_at\")
+VALUES ($1, $2, 1, now() + INTERVAL '1 second' * CAST($3 AS BIGINT))
+ON CONFLICT (\"command\", \"bucket\") DO UPDATE
+    SET
+        \"uses\" = CASE
+            WHEN \"CommandCooldowns\".\"expires_at\" <= now() THEN 1
+            ELSE \"CommandCooldowns\".\"uses\" + 1
+        END,
+        \"expires_at\" = CASE
+            WHEN \"CommandCooldowns\".\"expires_at\" <= now() THEN now() + INTERVAL '1 second' * CAST($3 AS BIGINT)
+            ELSE \"CommandCooldowns\".\"expires_at\"
+        END
+RETURNING \"uses\", \"expires_at\"")) } pub struct
+CommandCooldownHitStmt(cornucopia_async::private::Stmt); impl CommandCooldownHitStmt
+{ pub fn bind<'a, C:
+GenericClient,T1:
+cornucopia_async::StringSql,T2:
+cornucopia_async::StringSql,>(&'a mut self, client: &'a  C,
+command: &'a T1,bucket: &'a T2,duration: &'a i64,) -> CommandCooldownHitQuery<'a,C,
+CommandCooldownHit, 3>
+{
+    CommandCooldownHitQuery
+    {
+        client, params: [command,bucket,duration,], stmt: &mut self.0, extractor:
+        |row| { CommandCooldownHitBorrowed { uses: row.get(0),expires_at: row.get(1),} }, mapper: |it| { <CommandCooldownHit>::from(it) },
+    }
+} }impl <'a, C: GenericClient,T1: cornucopia_async::StringSql,T2: cornucopia_async::StringSql,> cornucopia_async::Params<'a,
+CommandCooldownHitParams<T1,T2,>, CommandCooldownHitQuery<'a, C,
+CommandCooldownHit, 3>, C> for CommandCooldownHitStmt
+{
+    fn
+    params(&'a mut self, client: &'a  C, params: &'a
+    CommandCooldownHitParams<T1,T2,>) -> CommandCooldownHitQuery<'a, C,
+    CommandCooldownHit, 3>
+    { self.bind(client, &params.command,&params.bucket,&params.duration,) }
+}}pub mod error_report_insert
+{ use futures::{{StreamExt, TryStreamExt}};use futures; use cornucopia_async::GenericClient;#[derive( Debug)] pub struct ErrorReportInsertParams<T1: cornucopia_async::StringSql,T2: cornucopia_async::StringSql,T3: cornucopia_async::StringSql,T4: cornucopia_async::StringSql,T5: cornucopia_async::StringSql,T6: cornucopia_async::StringSql,T7: cornucopia_async::StringSql,> { pub code: T1,pub kind: T2,pub report: T3,pub command: Option<T4>,pub options: Option<T5>,pub guild_id: Option<T6>,pub user_id: Option<T7>,pub shard_id: i64,pub shard_count: i64,pub timestamp: time::OffsetDateTime,}pub fn error_report_insert() -> ErrorReportInsertStmt
+{ ErrorReportInsertStmt(cornucopia_async::private::Stmt::new("INSERT INTO \"DiscordFrontend\".\"Nightly\".\"ErrorReports\" (\"code\", \"kind\", \"report\", \"command\", \"options\", \"guild_id\", \"user_id\", \"shard_id\", \"shard_count\", \"timestamp\")
+VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)")) } pub struct
+ErrorReportInsertStmt(cornucopia_async::private::Stmt); impl ErrorReportInsertStmt
+{ pub async fn bind<'a, C:
+GenericClient,T1:
+cornucopia_async::StringSql,T2:
+cornucopia_async::StringSql,T3:
+cornucopia_async::StringSql,T4:
+cornucopia_async::StringSql,T5:
+cornucopia_async::StringSql,T6:
+cornucopia_async::StringSql,T7:
+cornucopia_async::StringSql,>(&'a mut self, client: &'a  C,
+code: &'a T1,kind: &'a T2,report: &'a T3,command: &'a Option<T4>,options: &'a Option<T5>,guild_id: &'a Option<T6>,user_id: &'a Option<T7>,shard_id: &'a i64,shard_count: &'a i64,timestamp: &'a time::OffsetDateTime,) -> Result<u64, tokio_postgres::Error>
+{
+    let stmt = self.0.prepare(client).await?;
+    client.execute(stmt, &[code,kind,report,command,options,guild_id,user_id,shard_id,shard_count,timestamp,]).await
+} }impl <'a, C: GenericClient + Send + Sync, T1: cornucopia_async::StringSql,T2: cornucopia_async::StringSql,T3: cornucopia_async::StringSql,T4: cornucopia_async::StringSql,T5: cornucopia_async::StringSql,T6: cornucopia_async::StringSql,T7: cornucopia_async::StringSql,>
+cornucopia_async::Params<'a, ErrorReportInsertParams<T1,T2,T3,T4,T5,T6,T7,>, std::pin::Pin<Box<dyn futures::Future<Output = Result<u64,
+tokio_postgres::Error>> + Send + 'a>>, C> for ErrorReportInsertStmt
+{
+    fn
+    params(&'a mut self, client: &'a  C, params: &'a
+    ErrorReportInsertParams<T1,T2,T3,T4,T5,T6,T7,>) -> std::pin::Pin<Box<dyn futures::Future<Output = Result<u64,
+    tokio_postgres::Error>> + Send + 'a>>
+    { Box::pin(self.bind(client, &params.code,&params.kind,&params.report,&params.command,&params.options,&params.guild_id,&params.user_id,&params.shard_id,&params.shard_count,&params.timestamp,)) }
+}}pub mod error_report_select_by_code
+{ use futures::{{StreamExt, TryStreamExt}};use futures; use cornucopia_async::GenericClient;#[derive( Debug, Clone, PartialEq,)] pub struct ErrorReportSelectByCode
+{ pub code : String,pub kind : String,pub report : String,pub command : Option<String>,pub options : Option<String>,pub guild_id : Option<String>,pub user_id : Option<String>,pub shard_id : i64,pub shard_count : i64,pub timestamp : time::OffsetDateTime,}pub struct ErrorReportSelectByCodeBorrowed<'a> { pub code : &'a str,pub kind : &'a str,pub report : &'a str,pub command : Option<&'a str>,pub options : Option<&'a str>,pub guild_id : Option<&'a str>,pub user_id : Option<&'a str>,pub shard_id : i64,pub shard_count : i64,pub timestamp : time::OffsetDateTime,}
+impl<'a> From<ErrorReportSelectByCodeBorrowed<'a>> for ErrorReportSelectByCode
+{
+    fn from(ErrorReportSelectByCodeBorrowed { code,kind,report,command,options,guild_id,user_id,shard_id,shard_count,timestamp,}: ErrorReportSelectByCodeBorrowed<'a>) ->
+    Self { Self { code: code.into(),kind: kind.into(),report: report.into(),command: command.map(|v| v.into()),options: options.map(|v| v.into()),guild_id: guild_id.map(|v| v.into()),user_id: user_id.map(|v| v.into()),shard_id,shard_count,timestamp,} }
+}pub struct ErrorReportSelectByCodeQuery<'a, C: GenericClient, T, const N: usize>
+{
+    client: &'a  C, params:
+    [&'a (dyn postgres_types::ToSql + Sync); N], stmt: &'a mut
+    cornucopia_async::private::Stmt, extractor: fn(&tokio_postgres::Row) -> ErrorReportSelectByCodeBorrowed,
+    mapper: fn(ErrorReportSelectByCodeBorrowed) -> T,
+} impl<'a, C, T:'a, const N: usize> ErrorReportSelectByCodeQuery<'a, C, T, N> where C:
+GenericClient
+{
+    pub fn map<R>(self, mapper: fn(ErrorReportSelectByCodeBorrowed) -> R) ->
+    ErrorReportSelectByCodeQuery<'a,C,R,N>
+    {
+        ErrorReportSelectByCodeQuery
+        {
+            client: self.client, params: self.params, stmt: self.stmt,
+            extractor: self.extractor, mapper,
+        }
+    } pub async fn one(self) -> Result<T, tokio_postgres::Error>
+    {
+        let stmt = self.stmt.prepare(self.client).await?; let row =
+        self.client.query_one(stmt, &self.params).await?;
+        Ok((self.mapper)((self.extractor)(&row)))
+    } pub async fn all(self) -> Result<Vec<T>, tokio_postgres::Error>
+    { self.iter().await?.try_collect().await } pub async fn opt(self) ->
+    Result<Option<T>, tokio_postgres::Error>
+    {
+        let stmt = self.stmt.prepare(self.client).await?;
+        Ok(self.client.query_opt(stmt, &self.params) .await?
+        .map(|row| (self.mapper)((self.extractor)(&row))))
+    } pub async fn iter(self,) -> Result<impl futures::Stream<Item = Result<T,
+    tokio_postgres::Error>> + 'a, tokio_postgres::Error>
+    {
+        let stmt = self.stmt.prepare(self.client).await?; let it =
+        self.client.query_raw(stmt,
+        cornucopia_async::private::slice_iter(&self.params)) .await?
+        .map(move |res|
+        res.map(|row| (self.mapper)((self.extractor)(&row)))) .into_stream();
+        Ok(it)
+    }
+}pub fn error_report_select_by_code() -> ErrorReportSelectByCodeStmt
+{ ErrorReportSelectByCodeStmt(cornucopia_async::private::Stmt::new("SELECT
+    *
+FROM
+    \"DiscordFrontend\".\"Nightly\".\"ErrorReports\"
+WHERE
+    \"code\" = $1")) } pub struct
+ErrorReportSelectByCodeStmt(cornucopia_async::private::Stmt); impl ErrorReportSelectByCodeStmt
+{ pub fn bind<'a, C:
+GenericClient,T1:
+cornucopia_async::StringSql,>(&'a mut self, client: &'a  C,
+code: &'a T1,) -> ErrorReportSelectByCodeQuery<'a,C,
+ErrorReportSelectByCode, 1>
+{
+    ErrorReportSelectByCodeQuery
+    {
+        client, params: [code,], stmt: &mut self.0, extractor:
+        |row| { ErrorReportSelectByCodeBorrowed { code: row.get(0),kind: row.get(1),report: row.get(2),command: row.get(3),options: row.get(4),guild_id: row.get(5),user_id: row.get(6),shard_id: row.get(7),shard_count: row.get(8),timestamp: row.get(9),} }, mapper: |it| { <ErrorReportSelectByCode>::from(it) },
+    }
+} }}pub mod gateway_session_select_all
+{ use futures::{{StreamExt, TryStreamExt}};use futures; use cornucopia_async::GenericClient;#[derive( Debug, Clone, PartialEq,)] pub struct GatewaySessionSelectAll
+{ pub shard_id : i64,pub shard_count : i64,pub session_id : String,pub sequence : i64,pub resume_url : String,}pub struct GatewaySessionSelectAllBorrowed<'a> { pub shard_id : i64,pub shard_count : i64,pub session_id : &'a str,pub sequence : i64,pub resume_url : &'a str,}
+impl<'a> From<GatewaySessionSelectAllBorrowed<'a>> for GatewaySessionSelectAll
+{
+    fn from(GatewaySessionSelectAllBorrowed { shard_id,shard_count,session_id,sequence,resume_url,}: GatewaySessionSelectAllBorrowed<'a>) ->
+    Self { Self { shard_id,shard_count,session_id: session_id.into(),sequence,resume_url: resume_url.into(),} }
+}pub struct GatewaySessionSelectAllQuery<'a, C: GenericClient, T, const N: usize>
+{
+    client: &'a  C, params:
+    [&'a (dyn postgres_types::ToSql + Sync); N], stmt: &'a mut
+    cornucopia_async::private::Stmt, extractor: fn(&tokio_postgres::Row) -> GatewaySessionSelectAllBorrowed,
+    mapper: fn(GatewaySessionSelectAllBorrowed) -> T,
+} impl<'a, C, T:'a, const N: usize> GatewaySessionSelectAllQuery<'a, C, T, N> where C:
+GenericClient
+{
+    pub fn map<R>(self, mapper: fn(GatewaySessionSelectAllBorrowed) -> R) ->
+    GatewaySessionSelectAllQuery<'a,C,R,N>
+    {
+        GatewaySessionSelectAllQuery
+        {
+            client: self.client, params: self.params, stmt: self.stmt,
+            extractor: self.extractor, mapper,
+        }
+    } pub async fn one(self) -> Result<T, tokio_postgres::Error>
+    {
+        let stmt = self.stmt.prepare(self.client).await?; let row =
+        self.client.query_one(stmt, &self.params).await?;
+        Ok((self.mapper)((self.extractor)(&row)))
+    } pub async fn all(self) -> Result<Vec<T>, tokio_postgres::Error>
+    { self.iter().await?.try_collect().await } pub async fn opt(self) ->
+    Result<Option<T>, tokio_postgres::Error>
+    {
+        let stmt = self.stmt.prepare(self.client).await?;
+        Ok(self.client.query_opt(stmt, &self.params) .await?
+        .map(|row| (self.mapper)((self.extractor)(&row))))
+    } pub async fn iter(self,) -> Result<impl futures::Stream<Item = Result<T,
+    tokio_postgres::Error>> + 'a, tokio_postgres::Error>
+    {
+        let stmt = self.stmt.prepare(self.client).await?; let it =
+        self.client.query_raw(stmt,
+        cornucopia_async::private::slice_iter(&self.params)) .await?
+        .map(move |res|
+        res.map(|row| (self.mapper)((self.extractor)(&row)))) .into_stream();
+        Ok(it)
+    }
+}pub fn gateway_session_select_all() -> GatewaySessionSelectAllStmt
+{ GatewaySessionSelectAllStmt(cornucopia_async::private::Stmt::new("SELECT
+    *
+FROM
+    \"DiscordFrontend\".\"Nightly\".\"GatewaySessions\"")) } pub struct
+GatewaySessionSelectAllStmt(cornucopia_async::private::Stmt); impl GatewaySessionSelectAllStmt
+{ pub fn bind<'a, C:
+GenericClient,>(&'a mut self, client: &'a  C,
+) -> GatewaySessionSelectAllQuery<'a,C,
+GatewaySessionSelectAll, 0>
+{
+    GatewaySessionSelectAllQuery
+    {
+        client, params: [], stmt: &mut self.0, extractor:
+        |row| { GatewaySessionSelectAllBorrowed { shard_id: row.get(0),shard_count: row.get(1),session_id: row.get(2),sequence: row.get(3),resume_url: row.get(4),} }, mapper: |it| { <GatewaySessionSelectAll>::from(it) },
+    }
+} }}pub mod gateway_session_upsert
+{ use futures::{{StreamExt, TryStreamExt}};use futures; use cornucopia_async::GenericClient;#[derive( Debug)] pub struct GatewaySessionUpsertParams<T1: cornucopia_async::StringSql,T2: cornucopia_async::StringSql,> { pub shard_id: i64,pub shard_count: i64,pub session_id: T1,pub sequence: i64,pub resume_url: T2,}pub fn gateway_session_upsert() -> GatewaySessionUpsertStmt
+{ GatewaySessionUpsertStmt(cornucopia_async::private::Stmt::new("INSERT INTO \"DiscordFrontend\".\"Nightly\".\"GatewaySessions\" (\"shard_id\", \"shard_count\", \"session_id\", \"sequence\", \"resume_url\")
+VALUES ($1, $2, $3, $4, $5)
+ON CONFLICT (\"shard_id\", \"shard_count\") DO UPDATE
+    SET
+        \"session_id\" = $3,
+        \"sequence\" = $4,
+        \"resume_url\" = $5")) } pub struct
+GatewaySessionUpsertStmt(cornucopia_async::private::Stmt); impl GatewaySessionUpsertStmt
+{ pub async fn bind<'a, C:
+GenericClient,T1:
+cornucopia_async::StringSql,T2:
+cornucopia_async::StringSql,>(&'a mut self, client: &'a  C,
+shard_id: &'a i64,shard_count: &'a i64,session_id: &'a T1,sequence: &'a i64,resume_url: &'a T2,) -> Result<u64, tokio_postgres::Error>
+{
+    let stmt = self.0.prepare(client).await?;
+    client.execute(stmt, &[shard_id,shard_count,session_id,sequence,resume_url,]).await
+} }impl <'a, C: GenericClient + Send + Sync, T1: cornucopia_async::StringSql,T2: cornucopia_async::StringSql,>
+cornucopia_async::Params<'a, GatewaySessionUpsertParams<T1,T2,>, std::pin::Pin<Box<dyn futures::Future<Output = Result<u64,
+tokio_postgres::Error>> + Send + 'a>>, C> for GatewaySessionUpsertStmt
+{
+    fn
+    params(&'a mut self, client: &'a  C, params: &'a
+    GatewaySessionUpsertParams<T1,T2,>) -> std::pin::Pin<Box<dyn futures::Future<Output = Result<u64,
+    tokio_postgres::Error>> + Send + 'a>>
+    { Box::pin(self.bind(client, &params.shard_id,&params.shard_count,&params.session_id,&params.sequence,&params.resume_url,)) }
+}}pub mod identify_bucket_lock
+{ use futures::{{StreamExt, TryStreamExt}};use futures; use cornucopia_async::GenericClient;pub struct BoolQuery<'a, C: GenericClient, T, const N: usize>
+{
+    client: &'a  C, params:
+    [&'a (dyn postgres_types::ToSql + Sync); N], stmt: &'a mut
+    cornucopia_async::private::Stmt, extractor: fn(&tokio_postgres::Row) -> bool,
+    mapper: fn(bool) -> T,
+} impl<'a, C, T:'a, const N: usize> BoolQuery<'a, C, T, N> where C:
+GenericClient
+{
+    pub fn map<R>(self, mapper: fn(bool) -> R) ->
+    BoolQuery<'a,C,R,N>
+    {
+        BoolQuery
+        {
+            client: self.client, params: self.params, stmt: self.stmt,
+            extractor: self.extractor, mapper,
+        }
+    } pub async fn one(self) -> Result<T, tokio_postgres::Error>
+    {
+        let stmt = self.stmt.prepare(self.client).await?; let row =
+        self.client.query_one(stmt, &self.params).await?;
+        Ok((self.mapper)((self.extractor)(&row)))
+    } pub async fn all(self) -> Result<Vec<T>, tokio_postgres::Error>
+    { self.iter().await?.try_collect().await } pub async fn opt(self) ->
+    Result<Option<T>, tokio_postgres::Error>
+    {
+        let stmt = self.stmt.prepare(self.client).await?;
+        Ok(self.client.query_opt(stmt, &self.params) .await?
+        .map(|row| (self.mapper)((self.extractor)(&row))))
+    } pub async fn iter(self,) -> Result<impl futures::Stream<Item = Result<T,
+    tokio_postgres::Error>> + 'a, tokio_postgres::Error>
+    {
+        let stmt = self.stmt.prepare(self.client).await?; let it =
+        self.client.query_raw(stmt,
+        cornucopia_async::private::slice_iter(&self.params)) .await?
+        .map(move |res|
+        res.map(|row| (self.mapper)((self.extractor)(&row)))) .into_stream();
+        Ok(it)
+    }
+}pub fn identify_bucket_lock() -> IdentifyBucketLockStmt
+{ IdentifyBucketLockStmt(cornucopia_async::private::Stmt::new("SELECT
+    true AS \"locked\"
+FROM
+    pg_advisory_xact_lock(hashtext('IdentifyBucket'), $1)")) } pub struct
+IdentifyBucketLockStmt(cornucopia_async::private::Stmt); impl IdentifyBucketLockStmt
+{ pub fn bind<'a, C:
+GenericClient,>(&'a mut self, client: &'a  C,
+bucket: &'a i32,) -> BoolQuery<'a,C,
+bool, 1>
+{
+    BoolQuery
+    {
+        client, params: [bucket,], stmt: &mut self.0, extractor:
+        |row| { row.get(0) }, mapper: |it| { it },
+    }
+} }}pub mod processed_event_delete
+{ use futures::{{StreamExt, TryStreamExt}};use futures; use cornucopia_async::GenericClient;pub fn processed_event_delete() -> ProcessedEventDeleteStmt
+{ ProcessedEventDeleteStmt(cornucopia_async::private::Stmt::new("DELETE FROM
+    \"DiscordFrontend\".\"Nightly\".\"ProcessedEvents\"
+WHERE
+    \"key\" = $1")) } pub struct
+ProcessedEventDeleteStmt(cornucopia_async::private::Stmt); impl ProcessedEventDeleteStmt
+{ pub async fn bind<'a, C:
+GenericClient,T1:
+cornucopia_async::StringSql,>(&'a mut self, client: &'a  C,
+key: &'a T1,) -> Result<u64, tokio_postgres::Error>
+{
+    let stmt = self.0.prepare(client).await?;
+    client.execute(stmt, &[key,]).await
+} }}pub mod processed_event_delete_before
+{ use futures::{{StreamExt, TryStreamExt}};use futures; use cornucopia_async::GenericClient;pub fn processed_event_delete_before() -> ProcessedEventDeleteBeforeStmt
+{ ProcessedEventDeleteBeforeStmt(cornucopia_async::private::Stmt::new("DELETE FROM
+    \"DiscordFrontend\".\"Nightly\".\"ProcessedEvents\"
+WHERE
+    \"processed_at\" < $1")) } pub struct
+ProcessedEventDeleteBeforeStmt(cornucopia_async::private::Stmt); impl ProcessedEventDeleteBeforeStmt
+{ pub async fn bind<'a, C:
+GenericClient,>(&'a mut self, client: &'a  C,
+before: &'a time::OffsetDateTime,) -> Result<u64, tokio_postgres::Error>
+{
+    let stmt = self.0.prepare(client).await?;
+    client.execute(stmt, &[before,]).await
+} }}pub mod processed_event_insert
+{ use futures::{{StreamExt, TryStreamExt}};use futures; use cornucopia_async::GenericClient;pub fn processed_event_insert() -> ProcessedEventInsertStmt
+{ ProcessedEventInsertStmt(cornucopia_async::private::Stmt::new("INSERT INTO \"DiscordFrontend\".\"Nightly\".\"ProcessedEvents\" (\"key\")
+VALUES ($1)
+ON CONFLICT (\"key\") DO NOTHING")) } pub struct
+ProcessedEventInsertStmt(cornucopia_async::private::Stmt); impl ProcessedEventInsertStmt
+{ pub async fn bind<'a, C:
+GenericClient,T1:
+cornucopia_async::StringSql,>(&'a mut self, client: &'a  C,
+key: &'a T1,) -> Result<u64, tokio_postgres::Error>
+{
+    let stmt = self.0.prepare(client).await?;
+    client.execute(stmt, &[key,]).await
+} }}pub mod shard_latency_select_since
+{ use futures::{{StreamExt, TryStreamExt}};use futures; use cornucopia_async::GenericClient;#[derive( Debug, Clone, PartialEq,)] pub struct ShardLatencySelectSince
+{ pub shard_id : i64,pub shard_count : i64,pub average_latency : Option<i64>,pub latest_latency : Option<i64>,pub updated_at : time::OffsetDateTime,}pub struct ShardLatencySelectSinceBorrowed<'a> { pub shard_id : i64,pub shard_count : i64,pub average_latency : Option<i64>,pub latest_latency : Option<i64>,pub updated_at : time::OffsetDateTime,}
+impl<'a> From<ShardLatencySelectSinceBorrowed<'a>> for ShardLatencySelectSince
+{
+    fn from(ShardLatencySelectSinceBorrowed { shard_id,shard_count,average_latency,latest_latency,updated_at,}: ShardLatencySelectSinceBorrowed<'a>) ->
+    Self { Self { shard_id,shard_count,average_latency,latest_latency,updated_at,} }
+}pub struct ShardLatencySelectSinceQuery<'a, C: GenericClient, T, const N: usize>
+{
+    client: &'a  C, params:
+    [&'a (dyn postgres_types::ToSql + Sync); N], stmt: &'a mut
+    cornucopia_async::private::Stmt, extractor: fn(&tokio_postgres::Row) -> ShardLatencySelectSinceBorrowed,
+    mapper: fn(ShardLatencySelectSinceBorrowed) -> T,
+} impl<'a, C, T:'a, const N: usize> ShardLatencySelectSinceQuery<'a, C, T, N> where C:
+GenericClient
+{
+    pub fn map<R>(self, mapper: fn(ShardLatencySelectSinceBorrowed) -> R) ->
+    ShardLatencySelectSinceQuery<'a,C,R,N>
+    {
+        ShardLatencySelectSinceQuery
+        {
+            client: self.client, params: self.params, stmt: self.stmt,
+            extractor: self.extractor, mapper,
+        }
+    } pub async fn one(self) -> Result<T, tokio_postgres::Error>
+    {
+        let stmt = self.stmt.prepare(self.client).await?; let row =
+        self.client.query_one(stmt, &self.params).await?;
+        Ok((self.mapper)((self.extractor)(&row)))
+    } pub async fn all(self) -> Result<Vec<T>, tokio_postgres::Error>
+    { self.iter().await?.try_collect().await } pub async fn opt(self) ->
+    Result<Option<T>, tokio_postgres::Error>
+    {
+        let stmt = self.stmt.prepare(self.client).await?;
+        Ok(self.client.query_opt(stmt, &self.params) .await?
+        .map(|row| (self.mapper)((self.extractor)(&row))))
+    } pub async fn iter(self,) -> Result<impl futures::Stream<Item = Result<T,
+    tokio_postgres::Error>> + 'a, tokio_postgres::Error>
+    {
+        let stmt = self.stmt.prepare(self.client).await?; let it =
+        self.client.query_raw(stmt,
+        cornucopia_async::private::slice_iter(&self.params)) .await?
+        .map(move |res|
+        res.map(|row| (self.mapper)((self.extractor)(&row)))) .into_stream();
+        Ok(it)
+    }
+}pub fn shard_latency_select_since() -> ShardLatencySelectSinceStmt
+{ ShardLatencySelectSinceStmt(cornucopia_async::private::Stmt::new("SELECT
+    *
+FROM
+    \"DiscordFrontend\".\"Nightly\".\"ShardLatencies\"
+WHERE
+    \"updated_at\" >= $1
+ORDER BY
+    \"shard_count\", \"shard_id\"")) } pub struct
+ShardLatencySelectSinceStmt(cornucopia_async::private::Stmt); impl ShardLatencySelectSinceStmt
+{ pub fn bind<'a, C:
+GenericClient,>(&'a mut self, client: &'a  C,
+since: &'a time::OffsetDateTime,) -> ShardLatencySelectSinceQuery<'a,C,
+ShardLatencySelectSince, 1>
+{
+    ShardLatencySelectSinceQuery
+    {
+        client, params: [since,], stmt: &mut self.0, extractor:
+        |row| { ShardLatencySelectSinceBorrowed { shard_id: row.get(0),shard_count: row.get(1),average_latency: row.get(2),latest_latency: row.get(3),updated_at: row.get(4),} }, mapper: |it| { <ShardLatencySelectSince>::from(it) },
+    }
+} }}pub mod shard_latency_upsert
+{ use futures::{{StreamExt, TryStreamExt}};use futures; use cornucopia_async::GenericClient;#[derive(Clone,Copy, Debug)] pub struct ShardLatencyUpsertParams { pub shard_id: i64,pub shard_count: i64,pub average_latency: Option<i64>,pub latest_latency: Option<i64>,}pub fn shard_latency_upsert() -> ShardLatencyUpsertStmt
+{ ShardLatencyUpsertStmt(cornucopia_async::private::Stmt::new("INSERT INTO \"DiscordFrontend\".\"Nightly\".\"ShardLatencies\" (\"shard_id\", \"shard_count\", \"average_latency\", \"latest_latency\", \"updated_at\")
+VALUES ($1, $2, $3, $4, now())
+ON CONFLICT (\"shard_id\", \"shard_count\") DO UPDATE
+    SET
+        \"average_latency\" = $3,
+        \"latest_latency\" = $4,
+        \"updated_at\" = now()")) } pub struct
+ShardLatencyUpsertStmt(cornucopia_async::private::Stmt); impl ShardLatencyUpsertStmt
+{ pub async fn bind<'a, C:
+GenericClient,>(&'a mut self, client: &'a  C,
+shard_id: &'a i64,shard_count: &'a i64,average_latency: &'a Option<i64>,latest_latency: &'a Option<i64>,) -> Result<u64, tokio_postgres::Error>
+{
+    let stmt = self.0.prepare(client).await?;
+    client.execute(stmt, &[shard_id,shard_count,average_latency,latest_latency,]).await
+} }impl <'a, C: GenericClient + Send + Sync, >
+cornucopia_async::Params<'a, ShardLatencyUpsertParams, std::pin::Pin<Box<dyn futures::Future<Output = Result<u64,
+tokio_postgres::Error>> + Send + 'a>>, C> for ShardLatencyUpsertStmt
+{
+    fn
+    params(&'a mut self, client: &'a  C, params: &'a
+    ShardLatencyUpsertParams) -> std::pin::Pin<Box<dyn futures::Future<Output = Result<u64,
+    tokio_postgres::Error>> + Send + 'a>>
+    { Box::pin(self.bind(client, &params.shard_id,&params.shard_count,&params.average_latency,&params.latest_latency,)) }
 }}}
\ No newline at end of file
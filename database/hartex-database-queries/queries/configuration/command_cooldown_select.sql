@@ -0,0 +1,9 @@
+--! command_cooldown_select (guild_id, command) : (per?, duration?, burst?)
+SELECT
+    configuration -> 'cooldowns' -> :command ->> 'per' AS "per",
+    CAST(configuration -> 'cooldowns' -> :command ->> 'duration' AS BIGINT) AS "duration",
+    CAST(configuration -> 'cooldowns' -> :command ->> 'burst' AS BIGINT) AS "burst"
+FROM
+    "Nightly"."GuildConfigurations"
+WHERE
+    guild_id = :guild_id;
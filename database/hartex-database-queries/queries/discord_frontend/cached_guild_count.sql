@@ -0,0 +1,5 @@
+--! cached_guild_count : (count)
+SELECT
+    COUNT(*) AS "count"
+FROM
+    "DiscordFrontend"."Nightly"."CachedGuilds";
@@ -0,0 +1,3 @@
+--! command_cooldown_delete_expired
+DELETE FROM "DiscordFrontend"."Nightly"."CommandCooldowns"
+WHERE "expires_at" <= now();
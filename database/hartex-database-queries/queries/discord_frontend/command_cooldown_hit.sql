@@ -0,0 +1,14 @@
+--! command_cooldown_hit (command, bucket, duration) : (uses, expires_at)
+INSERT INTO "DiscordFrontend"."Nightly"."CommandCooldowns" ("command", "bucket", "uses", "expires_at")
+VALUES (:command, :bucket, 1, now() + INTERVAL '1 second' * CAST(:duration AS BIGINT))
+ON CONFLICT ("command", "bucket") DO UPDATE
+    SET
+        "uses" = CASE
+            WHEN "CommandCooldowns"."expires_at" <= now() THEN 1
+            ELSE "CommandCooldowns"."uses" + 1
+        END,
+        "expires_at" = CASE
+            WHEN "CommandCooldowns"."expires_at" <= now() THEN now() + INTERVAL '1 second' * CAST(:duration AS BIGINT)
+            ELSE "CommandCooldowns"."expires_at"
+        END
+RETURNING "uses", "expires_at";
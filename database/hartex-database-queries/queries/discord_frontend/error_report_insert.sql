@@ -0,0 +1,3 @@
+--! error_report_insert (code, kind, report, command?, options?, guild_id?, user_id?, shard_id, shard_count, timestamp)
+INSERT INTO "DiscordFrontend"."Nightly"."ErrorReports" ("code", "kind", "report", "command", "options", "guild_id", "user_id", "shard_id", "shard_count", "timestamp")
+VALUES (:code, :kind, :report, :command, :options, :guild_id, :user_id, :shard_id, :shard_count, :timestamp);
@@ -0,0 +1,7 @@
+--! error_report_select_by_code (code) : (code, kind, report, command?, options?, guild_id?, user_id?, shard_id, shard_count, timestamp)
+SELECT
+    *
+FROM
+    "DiscordFrontend"."Nightly"."ErrorReports"
+WHERE
+    "code" = :code;
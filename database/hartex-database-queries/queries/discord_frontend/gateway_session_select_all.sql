@@ -0,0 +1,5 @@
+--! gateway_session_select_all : (shard_id, shard_count, session_id, sequence, resume_url)
+SELECT
+    *
+FROM
+    "DiscordFrontend"."Nightly"."GatewaySessions";
@@ -0,0 +1,8 @@
+--! gateway_session_upsert (shard_id, shard_count, session_id, sequence, resume_url)
+INSERT INTO "DiscordFrontend"."Nightly"."GatewaySessions" ("shard_id", "shard_count", "session_id", "sequence", "resume_url")
+VALUES (:shard_id, :shard_count, :session_id, :sequence, :resume_url)
+ON CONFLICT ("shard_id", "shard_count") DO UPDATE
+    SET
+        "session_id" = :session_id,
+        "sequence" = :sequence,
+        "resume_url" = :resume_url;
@@ -0,0 +1,5 @@
+--! identify_bucket_lock (bucket) : (locked)
+SELECT
+    true AS "locked"
+FROM
+    pg_advisory_xact_lock(hashtext('IdentifyBucket'), :bucket);
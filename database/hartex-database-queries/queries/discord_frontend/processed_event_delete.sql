@@ -0,0 +1,5 @@
+--! processed_event_delete (key)
+DELETE FROM
+    "DiscordFrontend"."Nightly"."ProcessedEvents"
+WHERE
+    "key" = :key;
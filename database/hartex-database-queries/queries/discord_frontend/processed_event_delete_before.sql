@@ -0,0 +1,5 @@
+--! processed_event_delete_before (before)
+DELETE FROM
+    "DiscordFrontend"."Nightly"."ProcessedEvents"
+WHERE
+    "processed_at" < :before;
@@ -0,0 +1,4 @@
+--! processed_event_insert (key)
+INSERT INTO "DiscordFrontend"."Nightly"."ProcessedEvents" ("key")
+VALUES (:key)
+ON CONFLICT ("key") DO NOTHING;
@@ -0,0 +1,9 @@
+--! shard_latency_select_since (since) : (shard_id, shard_count, average_latency?, latest_latency?, updated_at)
+SELECT
+    *
+FROM
+    "DiscordFrontend"."Nightly"."ShardLatencies"
+WHERE
+    "updated_at" >= :since
+ORDER BY
+    "shard_count", "shard_id";
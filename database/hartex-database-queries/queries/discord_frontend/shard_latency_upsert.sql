@@ -0,0 +1,8 @@
+--! shard_latency_upsert (shard_id, shard_count, average_latency?, latest_latency?)
+INSERT INTO "DiscordFrontend"."Nightly"."ShardLatencies" ("shard_id", "shard_count", "average_latency", "latest_latency", "updated_at")
+VALUES (:shard_id, :shard_count, :average_latency, :latest_latency, now())
+ON CONFLICT ("shard_id", "shard_count") DO UPDATE
+    SET
+        "average_latency" = :average_latency,
+        "latest_latency" = :latest_latency,
+        "updated_at" = now();
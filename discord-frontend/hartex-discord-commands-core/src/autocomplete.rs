@@ -0,0 +1,70 @@
+/*
+ * SPDX-License-Identifier: AGPL-3.0-only
+ *
+ * This file is part of HarTex.
+ *
+ * HarTex
+ * Copyright (c) 2021-2024 HarTex Project Developers
+ *
+ * HarTex is free software; you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation; either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * HarTex is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License along
+ * with HarTex. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! # Autocomplete
+//!
+//! Utilities for suggesting choices for the option of a command the user is typing.
+
+use hartex_discord_core::discord::model::application::command::CommandOptionType;
+use hartex_discord_core::discord::model::application::interaction::application_command::CommandDataOption;
+use hartex_discord_core::discord::model::application::interaction::application_command::CommandOptionValue;
+
+/// The maximum number of choices allowed by Discord in an autocomplete response.
+pub const MAX_CHOICES: usize = 25;
+
+/// The option of a command the user is typing.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FocusedOption {
+    /// The names of the subcommand group and subcommand the option belongs to, if any, from the
+    /// outermost to the innermost.
+    pub path: Vec<String>,
+    /// The name of the option.
+    pub name: String,
+    /// The partial value of the option typed so far.
+    pub value: String,
+    /// The type of the option.
+    pub kind: CommandOptionType,
+}
+
+impl FocusedOption {
+    /// Find the focused option among the options of a command, descending into subcommand
+    /// groups and subcommands.
+    #[must_use]
+    pub fn find(options: &[CommandDataOption]) -> Option<Self> {
+        options.iter().find_map(|option| match &option.value {
+            CommandOptionValue::Focused(value, kind) => Some(Self {
+                path: Vec::new(),
+                name: option.name.clone(),
+                value: value.clone(),
+                kind: *kind,
+            }),
+            CommandOptionValue::SubCommand(options)
+            | CommandOptionValue::SubCommandGroup(options) => {
+                let mut focused = Self::find(options)?;
+                focused.path.insert(0, option.name.clone());
+
+                Some(focused)
+            }
+            _ => None,
+        })
+    }
+}
@@ -0,0 +1,162 @@
+/*
+ * SPDX-License-Identifier: AGPL-3.0-only
+ *
+ * This file is part of HarTex.
+ *
+ * HarTex
+ * Copyright (c) 2021-2024 HarTex Project Developers
+ *
+ * HarTex is free software; you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation; either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * HarTex is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License along
+ * with HarTex. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! # Command Cooldowns
+//!
+//! Cooldowns limit how often a command can be run. A cooldown applies to a bucket, which is
+//! either a user, a channel or a guild, and allows the command to be run a number of times (the
+//! burst) in each window of a given duration within the bucket.
+//!
+//! The cooldown declared by a command can be overridden by the configuration of a guild.
+
+use std::time::Duration;
+
+/// The scope of the buckets of a cooldown.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CooldownScope {
+    /// Each user has its own bucket.
+    User,
+    /// Each channel has its own bucket.
+    Channel,
+    /// Each guild has its own bucket.
+    Guild,
+}
+
+impl CooldownScope {
+    /// The name of the scope, as used in the configuration of a guild.
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::User => "user",
+            Self::Channel => "channel",
+            Self::Guild => "guild",
+        }
+    }
+
+    /// Obtain the scope from its name, as used in the configuration of a guild.
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "user" => Some(Self::User),
+            "channel" => Some(Self::Channel),
+            "guild" => Some(Self::Guild),
+            _ => None,
+        }
+    }
+}
+
+/// A cooldown of a command.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Cooldown {
+    scope: CooldownScope,
+    duration: Duration,
+    burst: u32,
+}
+
+impl Cooldown {
+    /// Construct a cooldown of the given number of seconds for each user.
+    #[must_use]
+    pub const fn per_user(seconds: u64) -> Self {
+        Self::new(CooldownScope::User, seconds)
+    }
+
+    /// Construct a cooldown of the given number of seconds for each channel.
+    #[must_use]
+    pub const fn per_channel(seconds: u64) -> Self {
+        Self::new(CooldownScope::Channel, seconds)
+    }
+
+    /// Construct a cooldown of the given number of seconds for each guild.
+    #[must_use]
+    pub const fn per_guild(seconds: u64) -> Self {
+        Self::new(CooldownScope::Guild, seconds)
+    }
+
+    /// Construct a cooldown of the given number of seconds with the given scope, allowing the
+    /// command to be run once in each window.
+    #[must_use]
+    pub const fn new(scope: CooldownScope, seconds: u64) -> Self {
+        Self {
+            scope,
+            duration: Duration::from_secs(seconds),
+            burst: 1,
+        }
+    }
+
+    /// Set the number of times the command can be run in each window.
+    #[must_use]
+    pub const fn burst(self, burst: u32) -> Self {
+        Self { burst, ..self }
+    }
+
+    /// Apply the overrides configured by a guild to a declared cooldown, if any.
+    ///
+    /// Overrides that are not configured keep the declared values. A command without a declared
+    /// cooldown is given one if the guild configures a duration, applying to each user and
+    /// allowing the command to be run once unless otherwise configured. A duration of zero
+    /// disables the cooldown.
+    #[must_use]
+    pub fn overridden(
+        declared: Option<Self>,
+        scope: Option<CooldownScope>,
+        seconds: Option<u64>,
+        burst: Option<u32>,
+    ) -> Option<Self> {
+        let mut cooldown = match (declared, seconds) {
+            (Some(cooldown), _) => cooldown,
+            (None, Some(seconds)) => Self::per_user(seconds),
+            (None, None) => return None,
+        };
+
+        if let Some(scope) = scope {
+            cooldown.scope = scope;
+        }
+
+        if let Some(seconds) = seconds {
+            cooldown.duration = Duration::from_secs(seconds);
+        }
+
+        if let Some(burst) = burst {
+            cooldown.burst = burst;
+        }
+
+        (!cooldown.duration.is_zero() && cooldown.burst > 0).then_some(cooldown)
+    }
+
+    /// The scope of the buckets of the cooldown.
+    #[must_use]
+    pub fn scope(&self) -> CooldownScope {
+        self.scope
+    }
+
+    /// The duration of each window of the cooldown.
+    #[must_use]
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// The number of times the command can be run in each window.
+    #[must_use]
+    pub fn burst_size(&self) -> u32 {
+        self.burst
+    }
+}
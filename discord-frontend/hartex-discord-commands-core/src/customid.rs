@@ -0,0 +1,233 @@
+/*
+ * SPDX-License-Identifier: AGPL-3.0-only
+ *
+ * This file is part of HarTex.
+ *
+ * HarTex
+ * Copyright (c) 2021-2024 HarTex Project Developers
+ *
+ * HarTex is free software; you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation; either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * HarTex is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License along
+ * with HarTex. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! # Custom IDs
+//!
+//! The custom IDs of message components and modals are used to route interactions with them to
+//! their handlers, and to carry state between the interactions, as no state is kept by the bot
+//! itself.
+//!
+//! A custom ID is of the form `handler:component:state...`, where `handler` is the name of the
+//! component or modal handler, `component` identifies the component or modal within the handler
+//! and each piece of state is separated by a colon.
+
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::str::FromStr;
+
+use miette::Report;
+
+/// The maximum length of a custom ID allowed by Discord.
+pub const MAX_LENGTH: usize = 100;
+
+/// The separator between the parts of a custom ID.
+pub const SEPARATOR: char = ':';
+
+/// A custom ID of a message component or a modal.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CustomId {
+    handler: String,
+    component: String,
+    state: Vec<String>,
+}
+
+impl CustomId {
+    /// Construct a custom ID for a component of a handler, without any state.
+    #[must_use]
+    pub fn new(handler: impl Into<String>, component: impl Into<String>) -> Self {
+        Self {
+            handler: handler.into(),
+            component: component.into(),
+            state: Vec::new(),
+        }
+    }
+
+    /// Append a piece of state to the custom ID.
+    #[must_use]
+    pub fn with_state(mut self, state: impl ToString) -> Self {
+        self.state.push(state.to_string());
+        self
+    }
+
+    /// The name of the component handler.
+    #[must_use]
+    pub fn handler(&self) -> &str {
+        &self.handler
+    }
+
+    /// The component within the handler.
+    #[must_use]
+    pub fn component(&self) -> &str {
+        &self.component
+    }
+
+    /// The pieces of state of the custom ID.
+    #[must_use]
+    pub fn state(&self) -> &[String] {
+        &self.state
+    }
+
+    /// Parse the piece of state at a given index.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is no piece of state at the index, or if it cannot be parsed.
+    pub fn parse_state<T>(&self, index: usize) -> miette::Result<T>
+    where
+        T: FromStr,
+    {
+        self.state
+            .get(index)
+            .and_then(|state| state.parse().ok())
+            .ok_or_else(|| {
+                Report::msg(format!(
+                    "custom id {self} has no valid piece of state at index {index}"
+                ))
+            })
+    }
+
+    /// Build the custom ID into a string to set on a component.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any part of the custom ID contains the separator, or if the custom ID
+    /// is longer than Discord allows.
+    pub fn build(&self) -> miette::Result<String> {
+        let parts = [&self.handler, &self.component]
+            .into_iter()
+            .chain(self.state.iter());
+        if let Some(part) = parts.clone().find(|part| part.contains(SEPARATOR)) {
+            return Err(Report::msg(format!(
+                "part {part} of custom id contains separator {SEPARATOR}"
+            )));
+        }
+
+        let custom_id = self.to_string();
+        if custom_id.len() > MAX_LENGTH {
+            return Err(Report::msg(format!(
+                "custom id {custom_id} is longer than {MAX_LENGTH} characters"
+            )));
+        }
+
+        Ok(custom_id)
+    }
+}
+
+impl Display for CustomId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{SEPARATOR}{}", self.handler, self.component)?;
+
+        for state in &self.state {
+            write!(f, "{SEPARATOR}{state}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for CustomId {
+    type Err = Report;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        let mut parts = string.split(SEPARATOR);
+        let (Some(handler), Some(component)) = (parts.next(), parts.next()) else {
+            return Err(Report::msg(format!("malformed custom id {string}")));
+        };
+        if handler.is_empty() || component.is_empty() {
+            return Err(Report::msg(format!("malformed custom id {string}")));
+        }
+
+        Ok(Self {
+            handler: handler.to_string(),
+            component: component.to_string(),
+            state: parts.map(String::from).collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CustomId;
+    use super::MAX_LENGTH;
+
+    #[test]
+    fn custom_id_round_trips() {
+        let custom_id = CustomId::new("info", "server-features")
+            .with_state(true)
+            .with_state(42);
+
+        let built = custom_id.build().unwrap();
+        let parsed = built.parse::<CustomId>().unwrap();
+
+        assert_eq!(built, "info:server-features:true:42");
+        assert_eq!(parsed, custom_id);
+        assert!(parsed.parse_state::<bool>(0).unwrap());
+        assert_eq!(parsed.parse_state::<u64>(1).unwrap(), 42);
+    }
+
+    #[test]
+    fn custom_id_without_state_round_trips() {
+        let custom_id = CustomId::new("feedback", "form");
+
+        let parsed = custom_id.build().unwrap().parse::<CustomId>().unwrap();
+
+        assert_eq!(parsed, custom_id);
+        assert!(parsed.state().is_empty());
+    }
+
+    #[test]
+    fn custom_id_without_handler_or_component_is_rejected() {
+        for malformed in ["", "info", ":server-features", "info:", ":"] {
+            assert!(
+                malformed.parse::<CustomId>().is_err(),
+                "{malformed} is accepted"
+            );
+        }
+    }
+
+    #[test]
+    fn missing_or_malformed_state_is_rejected() {
+        let custom_id = "info:server-features:verbose".parse::<CustomId>().unwrap();
+
+        assert!(custom_id.parse_state::<bool>(0).is_err());
+        assert!(custom_id.parse_state::<String>(1).is_err());
+    }
+
+    #[test]
+    fn part_containing_separator_is_rejected() {
+        let custom_id = CustomId::new("info", "server-features").with_state("a:b");
+
+        assert!(custom_id.build().is_err());
+    }
+
+    #[test]
+    fn oversize_custom_id_is_rejected() {
+        let state = "a".repeat(MAX_LENGTH);
+        let custom_id = CustomId::new("info", "server-features").with_state(state);
+
+        assert!(custom_id.build().is_err());
+        assert!(CustomId::new("a", "b".repeat(MAX_LENGTH - 2))
+            .build()
+            .is_ok());
+    }
+}
@@ -35,5 +35,13 @@
 extern crate hartex_discord_commands_macros;
 #[cfg(feature = "derive")]
 pub use hartex_discord_commands_macros::*;
+#[doc(hidden)]
+pub use linkme;
 
+pub mod autocomplete;
+pub mod cooldown;
+pub mod customid;
+pub mod modalfields;
+pub mod registry;
+pub mod response;
 pub mod traits;
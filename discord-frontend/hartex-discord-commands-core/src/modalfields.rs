@@ -0,0 +1,90 @@
+/*
+ * SPDX-License-Identifier: AGPL-3.0-only
+ *
+ * This file is part of HarTex.
+ *
+ * HarTex
+ * Copyright (c) 2021-2024 HarTex Project Developers
+ *
+ * HarTex is free software; you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation; either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * HarTex is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License along
+ * with HarTex. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! # Modal Fields
+//!
+//! The values submitted through the text inputs of a modal, keyed by the custom IDs of the text
+//! inputs.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use hartex_discord_core::discord::model::application::interaction::modal::ModalInteractionData;
+use miette::Report;
+
+/// The fields submitted through a modal.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ModalFields(HashMap<String, String>);
+
+impl ModalFields {
+    /// The value of the text input with a given custom ID.
+    ///
+    /// Text inputs that are not required and left empty by the user have no value.
+    #[must_use]
+    pub fn get(&self, custom_id: &str) -> Option<&str> {
+        self.0
+            .get(custom_id)
+            .map(String::as_str)
+            .filter(|value| !value.is_empty())
+    }
+
+    /// The value of a required text input with a given custom ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the text input has no value.
+    pub fn required(&self, custom_id: &str) -> miette::Result<&str> {
+        self.get(custom_id).ok_or_else(|| {
+            Report::msg(format!(
+                "modal has no value for text input with custom id {custom_id}"
+            ))
+        })
+    }
+
+    /// Parse the value of a required text input with a given custom ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the text input has no value, or if it cannot be parsed.
+    pub fn parse<T>(&self, custom_id: &str) -> miette::Result<T>
+    where
+        T: FromStr,
+    {
+        self.required(custom_id)?.parse().map_err(|_| {
+            Report::msg(format!(
+                "value of text input with custom id {custom_id} cannot be parsed"
+            ))
+        })
+    }
+}
+
+impl From<ModalInteractionData> for ModalFields {
+    fn from(data: ModalInteractionData) -> Self {
+        Self(
+            data.components
+                .into_iter()
+                .flat_map(|row| row.components)
+                .filter_map(|component| Some((component.custom_id, component.value?)))
+                .collect(),
+        )
+    }
+}
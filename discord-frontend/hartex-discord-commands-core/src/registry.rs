@@ -0,0 +1,66 @@
+/*
+ * SPDX-License-Identifier: AGPL-3.0-only
+ *
+ * This file is part of HarTex.
+ *
+ * HarTex
+ * Copyright (c) 2021-2024 HarTex Project Developers
+ *
+ * HarTex is free software; you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation; either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * HarTex is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License along
+ * with HarTex. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! # Command Registry
+//!
+//! Commands declared with the `command` macro are registered into a distributed slice, which is
+//! assembled by the linker from the commands of every crate linked into the final binary. New
+//! commands are hence available without being listed anywhere else.
+//!
+//! Component handlers and modal handlers are registered the same way by the `component` and
+//! `modal` macros.
+
+use linkme::distributed_slice;
+
+use crate::traits::Command;
+use crate::traits::ComponentHandler;
+use crate::traits::ModalHandler;
+
+/// The constructors of the commands registered by the `command` macro.
+#[allow(unsafe_code)]
+#[distributed_slice]
+pub static COMMANDS: [fn() -> Box<dyn Command + Send + Sync>];
+
+/// Returns every registered command.
+pub fn commands() -> impl Iterator<Item = Box<dyn Command + Send + Sync>> {
+    COMMANDS.iter().map(|constructor| constructor())
+}
+
+/// The constructors of the component handlers registered by the `component` macro.
+#[allow(unsafe_code)]
+#[distributed_slice]
+pub static COMPONENT_HANDLERS: [fn() -> Box<dyn ComponentHandler + Send + Sync>];
+
+/// Returns every registered component handler.
+pub fn component_handlers() -> impl Iterator<Item = Box<dyn ComponentHandler + Send + Sync>> {
+    COMPONENT_HANDLERS.iter().map(|constructor| constructor())
+}
+
+/// The constructors of the modal handlers registered by the `modal` macro.
+#[allow(unsafe_code)]
+#[distributed_slice]
+pub static MODAL_HANDLERS: [fn() -> Box<dyn ModalHandler + Send + Sync>];
+
+/// Returns every registered modal handler.
+pub fn modal_handlers() -> impl Iterator<Item = Box<dyn ModalHandler + Send + Sync>> {
+    MODAL_HANDLERS.iter().map(|constructor| constructor())
+}
@@ -0,0 +1,566 @@
+/*
+ * SPDX-License-Identifier: AGPL-3.0-only
+ *
+ * This file is part of HarTex.
+ *
+ * HarTex
+ * Copyright (c) 2021-2024 HarTex Project Developers
+ *
+ * HarTex is free software; you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation; either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * HarTex is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License along
+ * with HarTex. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! # Interaction Responses
+//!
+//! Discord requires interactions to be acknowledged within three seconds of their creation, after
+//! which the interaction token can no longer be used for an initial response. The response
+//! context tracks whether an interaction has been acknowledged, defers the response when the
+//! deadline approaches, and sends every further response as an edit of the original response or
+//! as a followup message accordingly.
+
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use hartex_discord_core::discord::http::client::InteractionClient;
+use hartex_discord_core::discord::http::request::application::interaction::UpdateResponse;
+use hartex_discord_core::discord::http::Client;
+use hartex_discord_core::discord::model::application::interaction::Interaction;
+use hartex_discord_core::discord::model::application::interaction::InteractionType;
+use hartex_discord_core::discord::model::channel::message::MessageFlags;
+use hartex_discord_core::discord::model::http::interaction::InteractionResponse;
+use hartex_discord_core::discord::model::http::interaction::InteractionResponseData;
+use hartex_discord_core::discord::model::http::interaction::InteractionResponseType;
+use hartex_discord_core::discord::model::id::marker::ApplicationMarker;
+use hartex_discord_core::discord::model::id::marker::InteractionMarker;
+use hartex_discord_core::discord::model::id::Id;
+use hartex_discord_core::tokio;
+use hartex_discord_core::tokio::sync::Mutex;
+use hartex_discord_core::tokio::task::JoinHandle;
+use hartex_discord_core::tokio::time::sleep;
+use miette::IntoDiagnostic;
+use miette::Report;
+
+/// The time after the creation of an interaction within which it has to be acknowledged.
+pub const DEADLINE: Duration = Duration::from_secs(3);
+
+/// The margin before the deadline at which the response is deferred automatically, leaving time
+/// for the deferral to reach Discord.
+pub const DEFER_MARGIN: Duration = Duration::from_millis(1000);
+
+/// The first second of 2015, the epoch of Discord snowflakes, in milliseconds since the Unix epoch.
+const DISCORD_EPOCH: u64 = 1_420_070_400_000;
+
+/// The state of the response to an interaction.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ResponseState {
+    /// The interaction has not been acknowledged.
+    Pending,
+    /// The response to the interaction has been deferred.
+    Deferred {
+        /// Whether the deferred response is only visible to the invoking user.
+        ephemeral: bool,
+    },
+    /// The update of the message of the component interacted with has been deferred.
+    DeferredUpdate,
+    /// The interaction has been responded to.
+    Responded,
+}
+
+impl ResponseState {
+    /// Decide how a response to the interaction is sent in this state, and the state after it is
+    /// sent.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the response can only be sent as the initial response and the
+    /// interaction has already been acknowledged.
+    fn transition(self, response: &InteractionResponse) -> miette::Result<(Delivery, Self)> {
+        if self == Self::Pending {
+            let state = match response.kind {
+                InteractionResponseType::DeferredChannelMessageWithSource => Self::Deferred {
+                    ephemeral: is_ephemeral(response.data.as_ref()),
+                },
+                InteractionResponseType::DeferredUpdateMessage => Self::DeferredUpdate,
+                _ => Self::Responded,
+            };
+
+            return Ok((Delivery::Initial, state));
+        }
+
+        if !matches!(
+            response.kind,
+            InteractionResponseType::ChannelMessageWithSource
+                | InteractionResponseType::UpdateMessage
+        ) {
+            return Err(Report::msg(format!(
+                "cannot respond with {:?} to an interaction that has already been acknowledged",
+                response.kind
+            )));
+        }
+
+        let delivery = match self {
+            Self::Deferred { ephemeral } if ephemeral || !is_ephemeral(response.data.as_ref()) => {
+                Delivery::Edit
+            }
+            Self::Deferred { .. } => Delivery::Replace,
+            Self::DeferredUpdate if response.kind == InteractionResponseType::UpdateMessage => {
+                Delivery::Edit
+            }
+            _ => Delivery::Followup,
+        };
+
+        Ok((delivery, Self::Responded))
+    }
+}
+
+/// How a response to an interaction is sent.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Delivery {
+    /// As the initial response.
+    Initial,
+    /// As an edit of the original response, or of the message of the component interacted with.
+    Edit,
+    /// As a followup message replacing the original response, which is deleted.
+    Replace,
+    /// As a followup message.
+    Followup,
+}
+
+/// The context for responding to an interaction.
+///
+/// The context is cheap to clone; every clone shares the state of the response.
+#[derive(Clone, Debug)]
+pub struct ResponseContext {
+    inner: Arc<ResponseContextInner>,
+}
+
+#[derive(Debug)]
+struct ResponseContextInner {
+    client: &'static Client,
+    application_id: Id<ApplicationMarker>,
+    interaction_id: Id<InteractionMarker>,
+    kind: InteractionType,
+    token: String,
+    state: Mutex<ResponseState>,
+}
+
+impl ResponseContext {
+    /// Construct the response context for an interaction that has not been acknowledged.
+    #[must_use]
+    pub fn new(client: &'static Client, interaction: &Interaction) -> Self {
+        Self {
+            inner: Arc::new(ResponseContextInner {
+                client,
+                application_id: interaction.application_id,
+                interaction_id: interaction.id,
+                kind: interaction.kind,
+                token: interaction.token.clone(),
+                state: Mutex::new(ResponseState::Pending),
+            }),
+        }
+    }
+
+    /// The interaction client, for requests the response context does not cover.
+    #[must_use]
+    pub fn interaction_client(&self) -> InteractionClient<'static> {
+        self.inner.client.interaction(self.inner.application_id)
+    }
+
+    /// The current state of the response.
+    pub async fn state(&self) -> ResponseState {
+        *self.inner.state.lock().await
+    }
+
+    /// Defer the response to the interaction, showing a loading state to the user.
+    ///
+    /// This does nothing if the interaction has already been acknowledged.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the deferral cannot be sent.
+    pub async fn defer(&self, ephemeral: bool) -> miette::Result<()> {
+        let flags = if ephemeral {
+            Some(MessageFlags::EPHEMERAL)
+        } else {
+            None
+        };
+
+        self.acknowledge(InteractionResponse {
+            kind: InteractionResponseType::DeferredChannelMessageWithSource,
+            data: Some(InteractionResponseData {
+                flags,
+                ..Default::default()
+            }),
+        })
+        .await
+    }
+
+    /// Defer the update of the message of the component interacted with, without showing a
+    /// loading state to the user.
+    ///
+    /// This does nothing if the interaction has already been acknowledged.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the deferral cannot be sent.
+    pub async fn defer_update(&self) -> miette::Result<()> {
+        self.acknowledge(InteractionResponse {
+            kind: InteractionResponseType::DeferredUpdateMessage,
+            data: None,
+        })
+        .await
+    }
+
+    /// Defer the response to the interaction when the deadline for acknowledging it approaches,
+    /// unless it has been acknowledged by then.
+    ///
+    /// The automatic deferral of a command is visible to everyone in the channel, while that of a
+    /// message component defers the update of its message. The returned guard cancels the
+    /// deferral when dropped, and should be held until the interaction is handled.
+    #[must_use = "the deferral is cancelled when the guard is dropped"]
+    pub fn defer_before_deadline(&self) -> DeferralGuard {
+        let created = (self.inner.interaction_id.get() >> 22) + DISCORD_EPOCH;
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| u64::try_from(duration.as_millis()).unwrap_or(u64::MAX))
+            .unwrap_or_default();
+        let deadline = DEADLINE.saturating_sub(DEFER_MARGIN);
+        let remaining = deadline.saturating_sub(Duration::from_millis(now.saturating_sub(created)));
+
+        let context = self.clone();
+        DeferralGuard(tokio::spawn(async move {
+            sleep(remaining).await;
+
+            let result = if context.inner.kind == InteractionType::MessageComponent {
+                context.defer_update().await
+            } else {
+                context.defer(false).await
+            };
+            if let Err(error) = result {
+                println!("{error:?}");
+            }
+        }))
+    }
+
+    /// Respond to the interaction.
+    ///
+    /// The response is sent as the initial response if the interaction has not been acknowledged,
+    /// as an edit of the original response if the response has been deferred, or as a followup
+    /// message otherwise. An ephemeral response to an interaction whose response was deferred
+    /// visibly replaces the original response with an ephemeral followup message. An update of
+    /// the message of a component whose update was deferred is sent as an edit of the message.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the response cannot be sent, or if it can only be sent as the initial
+    /// response (such as a modal) and the interaction has already been acknowledged.
+    pub async fn respond(&self, response: InteractionResponse) -> miette::Result<()> {
+        let mut state = self.inner.state.lock().await;
+        let client = self.interaction_client();
+
+        let (delivery, next) = state.transition(&response)?;
+        if delivery == Delivery::Initial {
+            client
+                .create_response(self.inner.interaction_id, &self.inner.token, &response)
+                .await
+                .into_diagnostic()?;
+
+            *state = next;
+
+            return Ok(());
+        }
+
+        let data = response.data.unwrap_or_default();
+        match delivery {
+            Delivery::Edit => {
+                edit(client.update_response(&self.inner.token), &data)
+                    .await
+                    .into_diagnostic()?;
+            }
+            Delivery::Replace => {
+                client
+                    .delete_response(&self.inner.token)
+                    .await
+                    .into_diagnostic()?;
+                self.followup(&client, &data).await?;
+            }
+            _ => self.followup(&client, &data).await?,
+        }
+
+        *state = next;
+
+        Ok(())
+    }
+
+    /// Acknowledge the interaction with a deferral, unless it has already been acknowledged.
+    async fn acknowledge(&self, response: InteractionResponse) -> miette::Result<()> {
+        let mut state = self.inner.state.lock().await;
+        if *state != ResponseState::Pending {
+            return Ok(());
+        }
+
+        let (_, next) = state.transition(&response)?;
+        self.interaction_client()
+            .create_response(self.inner.interaction_id, &self.inner.token, &response)
+            .await
+            .into_diagnostic()?;
+
+        *state = next;
+
+        Ok(())
+    }
+
+    async fn followup(
+        &self,
+        client: &InteractionClient<'_>,
+        data: &InteractionResponseData,
+    ) -> miette::Result<()> {
+        let mut followup = client
+            .create_followup(&self.inner.token)
+            .allowed_mentions(data.allowed_mentions.as_ref())
+            .components(data.components.as_deref().unwrap_or_default())
+            .content(data.content.as_deref().unwrap_or_default())
+            .embeds(data.embeds.as_deref().unwrap_or_default());
+        if let Some(flags) = data.flags {
+            followup = followup.flags(flags);
+        }
+
+        followup.await.into_diagnostic()?;
+
+        Ok(())
+    }
+}
+
+/// Whether a response is only visible to the invoking user.
+fn is_ephemeral(data: Option<&InteractionResponseData>) -> bool {
+    data.and_then(|data| data.flags)
+        .is_some_and(|flags| flags.contains(MessageFlags::EPHEMERAL))
+}
+
+/// A guard cancelling the automatic deferral of a response when dropped.
+#[derive(Debug)]
+pub struct DeferralGuard(JoinHandle<()>);
+
+impl Drop for DeferralGuard {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+/// Set the fields of an edit of a response to those of the data of a response.
+///
+/// Fields omitted from the data are left unchanged rather than cleared, as they are by a response
+/// updating the message of a component without a deferral.
+fn edit<'a>(
+    mut request: UpdateResponse<'a>,
+    data: &'a InteractionResponseData,
+) -> UpdateResponse<'a> {
+    if let Some(allowed_mentions) = &data.allowed_mentions {
+        request = request.allowed_mentions(Some(allowed_mentions));
+    }
+    if let Some(components) = &data.components {
+        request = request.components(Some(components));
+    }
+    if let Some(content) = &data.content {
+        request = request.content(Some(content));
+    }
+    if let Some(embeds) = &data.embeds {
+        request = request.embeds(Some(embeds));
+    }
+
+    request
+}
+
+#[cfg(test)]
+mod tests {
+    use hartex_discord_core::discord::http::request::TryIntoRequest;
+    use hartex_discord_core::discord::http::Client;
+    use hartex_discord_core::discord::model::channel::message::MessageFlags;
+    use hartex_discord_core::discord::model::http::interaction::InteractionResponse;
+    use hartex_discord_core::discord::model::http::interaction::InteractionResponseData;
+    use hartex_discord_core::discord::model::http::interaction::InteractionResponseType;
+    use hartex_discord_core::discord::model::id::Id;
+
+    use super::Delivery;
+    use super::ResponseState;
+
+    fn response(kind: InteractionResponseType, ephemeral: bool) -> InteractionResponse {
+        InteractionResponse {
+            kind,
+            data: Some(InteractionResponseData {
+                flags: ephemeral.then_some(MessageFlags::EPHEMERAL),
+                ..Default::default()
+            }),
+        }
+    }
+
+    #[test]
+    fn pending_response_is_sent_as_initial_response() {
+        let (delivery, state) = ResponseState::Pending
+            .transition(&response(
+                InteractionResponseType::ChannelMessageWithSource,
+                false,
+            ))
+            .unwrap();
+
+        assert_eq!(delivery, Delivery::Initial);
+        assert_eq!(state, ResponseState::Responded);
+    }
+
+    #[test]
+    fn pending_deferral_keeps_whether_it_is_ephemeral() {
+        let (delivery, state) = ResponseState::Pending
+            .transition(&response(
+                InteractionResponseType::DeferredChannelMessageWithSource,
+                true,
+            ))
+            .unwrap();
+
+        assert_eq!(delivery, Delivery::Initial);
+        assert_eq!(state, ResponseState::Deferred { ephemeral: true });
+    }
+
+    #[test]
+    fn pending_deferred_update_defers_the_update() {
+        let (delivery, state) = ResponseState::Pending
+            .transition(&response(
+                InteractionResponseType::DeferredUpdateMessage,
+                false,
+            ))
+            .unwrap();
+
+        assert_eq!(delivery, Delivery::Initial);
+        assert_eq!(state, ResponseState::DeferredUpdate);
+    }
+
+    #[test]
+    fn deferred_response_is_edited() {
+        for (deferred_ephemeral, ephemeral) in [(false, false), (true, false), (true, true)] {
+            let (delivery, state) = ResponseState::Deferred {
+                ephemeral: deferred_ephemeral,
+            }
+            .transition(&response(
+                InteractionResponseType::ChannelMessageWithSource,
+                ephemeral,
+            ))
+            .unwrap();
+
+            assert_eq!(delivery, Delivery::Edit);
+            assert_eq!(state, ResponseState::Responded);
+        }
+    }
+
+    #[test]
+    fn visible_deferral_is_replaced_by_ephemeral_response() {
+        let (delivery, state) = ResponseState::Deferred { ephemeral: false }
+            .transition(&response(
+                InteractionResponseType::ChannelMessageWithSource,
+                true,
+            ))
+            .unwrap();
+
+        assert_eq!(delivery, Delivery::Replace);
+        assert_eq!(state, ResponseState::Responded);
+    }
+
+    #[test]
+    fn deferred_update_is_edited_by_update() {
+        let (delivery, state) = ResponseState::DeferredUpdate
+            .transition(&response(InteractionResponseType::UpdateMessage, false))
+            .unwrap();
+
+        assert_eq!(delivery, Delivery::Edit);
+        assert_eq!(state, ResponseState::Responded);
+    }
+
+    #[test]
+    fn deferred_update_is_edited_by_partial_update() {
+        let update = InteractionResponse {
+            kind: InteractionResponseType::UpdateMessage,
+            data: Some(InteractionResponseData {
+                content: Some(String::from("content")),
+                ..Default::default()
+            }),
+        };
+        let (delivery, state) = ResponseState::DeferredUpdate.transition(&update).unwrap();
+
+        assert_eq!(delivery, Delivery::Edit);
+        assert_eq!(state, ResponseState::Responded);
+
+        let client = Client::new(String::from("token"));
+        let data = update.data.unwrap();
+        let request = super::edit(
+            client.interaction(Id::new(1)).update_response("token"),
+            &data,
+        )
+        .try_into_request()
+        .unwrap();
+        let body = serde_json::from_slice::<serde_json::Value>(request.body().unwrap()).unwrap();
+
+        assert_eq!(body["content"], "content");
+        for omitted in ["allowed_mentions", "components", "embeds"] {
+            assert!(
+                body.get(omitted).is_none(),
+                "{omitted} is not left unchanged"
+            );
+        }
+    }
+
+    #[test]
+    fn deferred_update_is_followed_by_message() {
+        let (delivery, state) = ResponseState::DeferredUpdate
+            .transition(&response(
+                InteractionResponseType::ChannelMessageWithSource,
+                true,
+            ))
+            .unwrap();
+
+        assert_eq!(delivery, Delivery::Followup);
+        assert_eq!(state, ResponseState::Responded);
+    }
+
+    #[test]
+    fn responded_interaction_is_followed_up() {
+        let (delivery, state) = ResponseState::Responded
+            .transition(&response(
+                InteractionResponseType::ChannelMessageWithSource,
+                false,
+            ))
+            .unwrap();
+
+        assert_eq!(delivery, Delivery::Followup);
+        assert_eq!(state, ResponseState::Responded);
+    }
+
+    #[test]
+    fn acknowledged_interaction_cannot_be_deferred_or_open_modal() {
+        let acknowledged = [
+            ResponseState::Deferred { ephemeral: false },
+            ResponseState::DeferredUpdate,
+            ResponseState::Responded,
+        ];
+        let initial_only = [
+            InteractionResponseType::DeferredChannelMessageWithSource,
+            InteractionResponseType::DeferredUpdateMessage,
+            InteractionResponseType::Modal,
+        ];
+
+        for state in acknowledged {
+            for kind in initial_only {
+                assert!(state.transition(&response(kind, false)).is_err());
+            }
+        }
+    }
+}
@@ -22,15 +22,41 @@
 
 use async_trait::async_trait;
 use hartex_discord_configuration_provider::ConfigurationProvider;
-use hartex_discord_core::discord::http::client::InteractionClient;
+use hartex_discord_core::discord::model::application::command::CommandOptionChoice;
 use hartex_discord_core::discord::model::application::interaction::Interaction;
+use hartex_discord_core::discord::model::application::interaction::InteractionContextType;
+use hartex_discord_core::discord::model::gateway::event::DispatchEvent;
+use hartex_discord_core::discord::model::gateway::event::EventType;
+use hartex_discord_core::discord::model::gateway::Intents;
+use hartex_discord_core::discord::model::gateway::ShardId;
 use hartex_discord_core::discord::model::guild::Permissions;
 use hartex_discord_core::discord::model::id::marker::GuildMarker;
 use hartex_discord_core::discord::model::id::Id;
 use hartex_localization_core::Localizer;
 
+use crate::autocomplete::FocusedOption;
+use crate::cooldown::Cooldown;
+use crate::customid::CustomId;
+use crate::modalfields::ModalFields;
+use crate::response::ResponseContext;
+
 /// The command metadata trait, specifying the various information about a command.
 pub trait CommandMetadata {
+    /// The contexts in which this command can be run.
+    ///
+    /// Commands can only be run in guilds by default. Commands that can also be run in direct
+    /// messages with the bot or in other private channels must not depend on a guild.
+    fn contexts(&self) -> Vec<InteractionContextType> {
+        vec![InteractionContextType::Guild]
+    }
+
+    /// The cooldown of this command, if any.
+    ///
+    /// The cooldown can be overridden by the configuration of a guild.
+    fn cooldown(&self) -> Option<Cooldown> {
+        None
+    }
+
     /// The minimum permission level required for this command to be run.
     fn required_permissions(&self) -> Permissions {
         Permissions::empty()
@@ -48,14 +74,88 @@ pub trait CommandMetadata {
 #[async_trait]
 pub trait Command: CommandMetadata {
     /// Executes the command.
+    ///
+    /// The response to the command is sent through the response context, which takes care of
+    /// acknowledging the interaction in time.
     async fn execute(
         &self,
         interaction: Interaction,
-        interaction_client: &InteractionClient<'_>,
+        response: &ResponseContext,
+        localizer: Localizer<'_>,
+    ) -> miette::Result<()>;
+
+    /// Suggests choices for the option of the command the user is typing.
+    ///
+    /// This is only invoked for options with autocomplete enabled in the specification of the
+    /// command. At most 25 choices are shown to the user.
+    async fn autocomplete(
+        &self,
+        _: Interaction,
+        _: FocusedOption,
+        _: Localizer<'_>,
+    ) -> miette::Result<Vec<CommandOptionChoice>> {
+        Ok(Vec::new())
+    }
+}
+
+/// The component handler trait, contains callbacks that are to be run when a message component
+/// whose custom ID names the handler is interacted with.
+///
+/// A component handler is named after the command creating its components, and is subject to the
+/// same checks as the command.
+#[async_trait]
+pub trait ComponentHandler: CommandMetadata {
+    /// Handles an interaction with a message component.
+    async fn handle(
+        &self,
+        interaction: Interaction,
+        custom_id: CustomId,
+        response: &ResponseContext,
+        localizer: Localizer<'_>,
+    ) -> miette::Result<()>;
+}
+
+/// The modal handler trait, contains callbacks that are to be run when a modal whose custom ID
+/// names the handler is submitted.
+///
+/// A modal handler is named after the command opening its modals, and is subject to the same
+/// checks as the command.
+#[async_trait]
+pub trait ModalHandler: CommandMetadata {
+    /// Handles the submission of a modal.
+    async fn handle(
+        &self,
+        interaction: Interaction,
+        custom_id: CustomId,
+        fields: ModalFields,
+        response: &ResponseContext,
         localizer: Localizer<'_>,
     ) -> miette::Result<()>;
 }
 
+/// The event handler trait, contains callbacks that are to be run when a gateway event is received.
+#[async_trait]
+pub trait EventHandler {
+    /// The name of the event handler, used for reporting errors.
+    fn name(&self) -> String;
+
+    /// The types of the dispatch events the event handler subscribes to.
+    fn event_types(&self) -> Vec<EventType>;
+
+    /// The key identifying the side effects of handling a dispatch event, if the event handler
+    /// has side effects that must not be repeated when the event is consumed again.
+    ///
+    /// An event handler is not invoked for an event whose key was already claimed, such that it
+    /// runs at most once for each key. The key is released if the event handler fails to handle
+    /// the event, such that the event is handled again when it is consumed again.
+    fn idempotency_key(&self, _: &DispatchEvent) -> Option<String> {
+        None
+    }
+
+    /// Handles a dispatch event of one of the types the event handler subscribes to.
+    async fn handle(&self, event: DispatchEvent, shard: ShardId) -> miette::Result<()>;
+}
+
 /// The plugin metadata data specifying information about a plugin.
 pub trait PluginMetadata {
     /// The name of the plugin.
@@ -66,6 +166,16 @@ pub trait PluginMetadata {
 /// handled.
 #[async_trait]
 pub trait Plugin: PluginMetadata {
+    /// The gateway intents required by the commands of a given plugin.
+    fn intents(&self) -> Intents {
+        Intents::empty()
+    }
+
+    /// The event handlers provided by a given plugin.
+    fn event_handlers(&self) -> Vec<Box<dyn EventHandler + Send + Sync>> {
+        Vec::new()
+    }
+
     /// Whether a given plugin is enabled.
     async fn enabled(&self, guild_id: Id<GuildMarker>) -> miette::Result<bool> {
         ConfigurationProvider::plugin_enabled(guild_id, self.name()).await
@@ -30,6 +30,9 @@ use syn::ItemStruct;
 use syn::Lit;
 use syn::Token;
 
+/// The contexts a command can be declared to be run in.
+const CONTEXTS: [&str; 3] = ["Guild", "BotDm", "PrivateChannel"];
+
 /// Represents input to the `metadata` derive macro.
 #[allow(dead_code)]
 pub struct CommandMetadataMacroInput {
@@ -44,6 +47,12 @@ pub struct CommandMetadataMacroInput {
     pub(self) minimum_permission_level_ident: Option<Ident>,
     pub(self) equal_2: Option<Token![=]>,
     pub(self) minimum_permission_level: Option<Expr>,
+    pub(self) contexts_ident: Option<Ident>,
+    pub(self) equal_5: Option<Token![=]>,
+    pub(self) contexts: Option<Expr>,
+    pub(self) cooldown_ident: Option<Ident>,
+    pub(self) equal_6: Option<Token![=]>,
+    pub(self) cooldown: Option<Expr>,
     pub(self) comma4: Option<Token![,]>,
 }
 
@@ -61,23 +70,56 @@ impl Parse for CommandMetadataMacroInput {
             minimum_permission_level_ident: None,
             equal_2: None,
             minimum_permission_level: None,
+            contexts_ident: None,
+            equal_5: None,
+            contexts: None,
+            cooldown_ident: None,
+            equal_6: None,
+            cooldown: None,
             comma4: None,
         };
 
-        let Some(comma_3) = input.parse().ok() else {
-            return Ok(result);
-        };
+        // the remaining parameters are optional and may be specified in any order
+        while let Some(comma) = input.parse::<Option<Token![,]>>()? {
+            if input.is_empty() {
+                result.comma4.replace(comma);
+                break;
+            }
+
+            result.comma3.replace(comma);
+
+            let ident = input.parse::<Ident>()?;
+            let equal = input.parse::<Token![=]>()?;
+            let expr = input.parse::<Expr>()?;
 
-        result.comma3.replace(comma_3);
-        result.minimum_permission_level_ident = input.parse().ok();
-        result.equal_2 = input.parse().ok();
-        result.comma4 = input.parse().ok();
+            if ident == "contexts" {
+                result.contexts_ident.replace(ident);
+                result.equal_5.replace(equal);
+                result.contexts.replace(expr);
+            } else if ident == "cooldown" {
+                result.cooldown_ident.replace(ident);
+                result.equal_6.replace(equal);
+                result.cooldown.replace(expr);
+            } else if ident == "required_permissions" {
+                result.minimum_permission_level_ident.replace(ident);
+                result.equal_2.replace(equal);
+                result.minimum_permission_level.replace(expr);
+            } else {
+                return Err(syn::Error::new(
+                    ident.span(),
+                    format!(
+                        "unknown parameter `{ident}`; expected `required_permissions`, `contexts` or `cooldown`"
+                    ),
+                ));
+            }
+        }
 
         Ok(result)
     }
 }
 
-/// Returns the token stream for generating the `CommandMetadata` trait implementation
+/// Returns the token stream for generating the `CommandMetadata` trait implementation and
+/// registering the command
 #[allow(clippy::too_many_lines)]
 pub fn implement_metadata(
     parameters: &CommandMetadataMacroInput,
@@ -149,7 +191,9 @@ pub fn implement_metadata(
             return None;
         };
 
-        let Some(Expr::Binary(expr)) = parameters.minimum_permission_level.clone() else {
+        let Some(expr @ (Expr::Binary(_) | Expr::Path(_))) =
+            parameters.minimum_permission_level.clone()
+        else {
             parameters
                 .minimum_permission_level
                 .span()
@@ -169,6 +213,65 @@ pub fn implement_metadata(
         functions.extend(expanded);
     }
 
+    // contexts = [?]
+    if let Some(contexts) = parameters.contexts.clone() {
+        let Expr::Array(array) = contexts else {
+            contexts.span().unwrap().error("expected array").emit();
+
+            return None;
+        };
+
+        let mut variants = Vec::new();
+        for element in array.elems {
+            let variant = match &element {
+                Expr::Path(path) => path.path.get_ident().cloned(),
+                _ => None,
+            };
+            let Some(variant) = variant
+                .filter(|variant| CONTEXTS.contains(&variant.to_string().as_str()))
+            else {
+                element
+                    .span()
+                    .unwrap()
+                    .error("expected one of `Guild`, `BotDm` or `PrivateChannel`")
+                    .emit();
+
+                return None;
+            };
+
+            variants.push(variant);
+        }
+
+        let expanded = quote::quote! {
+            fn contexts(&self) -> Vec<hartex_discord_core::discord::model::application::interaction::InteractionContextType> {
+                vec![#(hartex_discord_core::discord::model::application::interaction::InteractionContextType::#variants),*]
+            }
+        };
+        functions.extend(expanded);
+    }
+
+    // cooldown = Cooldown::?
+    if let Some(cooldown) = parameters.cooldown.clone() {
+        if !matches!(cooldown, Expr::Call(_) | Expr::MethodCall(_)) {
+            cooldown
+                .span()
+                .unwrap()
+                .error("expected cooldown, such as `Cooldown::per_user(5)`")
+                .emit();
+
+            return None;
+        }
+
+        let expanded = quote::quote! {
+            fn cooldown(&self) -> Option<_commands_core::cooldown::Cooldown> {
+                use _commands_core::cooldown::Cooldown;
+
+                Some(#cooldown)
+            }
+        };
+        functions.extend(expanded);
+    }
+
     let core_use = quote::quote! {
         extern crate hartex_discord_commands_core as _commands_core;
 
@@ -184,6 +287,14 @@ pub fn implement_metadata(
         impl _commands_core::traits::CommandMetadata for #ident {
             #functions
         }
+
+        const _: () = {
+            #[allow(unsafe_code)]
+            #[_commands_core::linkme::distributed_slice(_commands_core::registry::COMMANDS)]
+            #[linkme(crate = _commands_core::linkme)]
+            static COMMAND: fn() -> Box<dyn _commands_core::traits::Command + Send + Sync> =
+                || Box::new(#ident);
+        };
     };
 
     Some(quote::quote! {
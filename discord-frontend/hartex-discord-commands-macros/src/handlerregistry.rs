@@ -0,0 +1,47 @@
+/*
+ * SPDX-License-Identifier: AGPL-3.0-only
+ *
+ * This file is part of HarTex.
+ *
+ * HarTex
+ * Copyright (c) 2021-2024 HarTex Project Developers
+ *
+ * HarTex is free software; you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation; either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * HarTex is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License along
+ * with HarTex. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::format_ident;
+use syn::ItemStruct;
+
+/// Returns the token stream for registering a handler into a registry of the command system core,
+/// given the name of the distributed slice of the registry and of the trait of its handlers.
+pub fn register_handler(struct_item: &ItemStruct, registry: &str, handler: &str) -> TokenStream2 {
+    let ident = struct_item.ident.clone();
+    let registry = format_ident!("{registry}");
+    let handler = format_ident!("{handler}");
+
+    quote::quote! {
+        #struct_item
+
+        const _: () = {
+            extern crate hartex_discord_commands_core as _commands_core;
+
+            #[allow(unsafe_code)]
+            #[_commands_core::linkme::distributed_slice(_commands_core::registry::#registry)]
+            #[linkme(crate = _commands_core::linkme)]
+            static HANDLER: fn() -> Box<dyn _commands_core::traits::#handler + Send + Sync> =
+                || Box::new(#ident);
+        };
+    }
+}
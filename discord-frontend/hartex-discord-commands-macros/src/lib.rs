@@ -34,13 +34,18 @@
 extern crate proc_macro;
 
 use proc_macro::TokenStream;
+use syn::parse::Nothing;
 use syn::parse_macro_input;
 use syn::ItemStruct;
 
 mod commandmetadata;
+mod handlerregistry;
 mod pluginmetadata;
 
-/// Macro to implement the `CommandMetadata` trait.
+/// Macro to implement the `CommandMetadata` trait and register the command.
+///
+/// The struct must implement the `Command` trait, and is registered into the command registry
+/// such that it is looked up by its name when invoked.
 #[proc_macro_attribute]
 pub fn command(tokens: TokenStream, item: TokenStream) -> TokenStream {
     let input = parse_macro_input!(tokens as commandmetadata::CommandMetadataMacroInput);
@@ -50,6 +55,30 @@ pub fn command(tokens: TokenStream, item: TokenStream) -> TokenStream {
         .into()
 }
 
+/// Macro to register a component handler.
+///
+/// The struct must implement the `ComponentHandler` trait, and is registered into the component
+/// handler registry such that it is looked up by its name when one of its components is
+/// interacted with.
+#[proc_macro_attribute]
+pub fn component(tokens: TokenStream, item: TokenStream) -> TokenStream {
+    parse_macro_input!(tokens as Nothing);
+    let struct_decl = parse_macro_input!(item as ItemStruct);
+    handlerregistry::register_handler(&struct_decl, "COMPONENT_HANDLERS", "ComponentHandler")
+        .into()
+}
+
+/// Macro to register a modal handler.
+///
+/// The struct must implement the `ModalHandler` trait, and is registered into the modal handler
+/// registry such that it is looked up by its name when one of its modals is submitted.
+#[proc_macro_attribute]
+pub fn modal(tokens: TokenStream, item: TokenStream) -> TokenStream {
+    parse_macro_input!(tokens as Nothing);
+    let struct_decl = parse_macro_input!(item as ItemStruct);
+    handlerregistry::register_handler(&struct_decl, "MODAL_HANDLERS", "ModalHandler").into()
+}
+
 /// Macro to implement the `PluginMetadata` trait.
 #[proc_macro_attribute]
 pub fn plugin(tokens: TokenStream, item: TokenStream) -> TokenStream {
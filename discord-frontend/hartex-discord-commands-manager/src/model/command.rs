@@ -26,7 +26,9 @@ use std::fmt::Display;
 use std::fmt::Formatter;
 
 use hartex_discord_core::discord::model::application::command::CommandType;
+use hartex_discord_core::discord::model::application::interaction::InteractionContextType;
 use hartex_discord_core::discord::model::guild::Permissions;
+use hartex_discord_core::discord::model::oauth::ApplicationIntegrationType;
 use owo_colors::OwoColorize;
 use serde::Deserialize;
 use serde::Serialize;
@@ -40,6 +42,8 @@ use super::TypeEnumExt;
 #[allow(clippy::module_name_repetitions)]
 #[derive(Debug, Deserialize, Serialize)]
 pub struct CommandManagerCommand {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contexts: Option<Vec<InteractionContextType>>,
     pub default_member_permissions: Option<Permissions>,
     #[deprecated(note = "use default_member_permissions instead")]
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -54,6 +58,8 @@ pub struct CommandManagerCommand {
     pub kind: CommandType,
     pub name: String,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub integration_types: Option<Vec<ApplicationIntegrationType>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub name_localizations: Option<HashMap<String, String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub nsfw: Option<bool>,
@@ -107,6 +113,28 @@ impl Display for CommandManagerCommand {
                 .bright_cyan()
         )?;
 
+        write!(f, "{}", "Command Contexts: ".bold())?;
+        if let Some(contexts) = &self.contexts {
+            writeln!(f)?;
+
+            for context in contexts {
+                writeln!(f, "    - {}", context.kind().bright_cyan())?;
+            }
+        } else {
+            writeln!(f, "{}", "Unspecified".truecolor(107, 107, 107))?;
+        }
+
+        write!(f, "{}", "Command Installation Contexts: ".bold())?;
+        if let Some(integration_types) = &self.integration_types {
+            writeln!(f)?;
+
+            for integration_type in integration_types {
+                writeln!(f, "    - {}", integration_type.kind().bright_cyan())?;
+            }
+        } else {
+            writeln!(f, "{}", "Unspecified".truecolor(107, 107, 107))?;
+        }
+
         #[allow(deprecated)]
         writeln!(
             f,
@@ -26,8 +26,8 @@
 
 use async_trait::async_trait;
 use hartex_discord_commands_core::command;
+use hartex_discord_commands_core::response::ResponseContext;
 use hartex_discord_commands_core::traits::Command;
-use hartex_discord_core::discord::http::client::InteractionClient;
 use hartex_discord_core::discord::model::application::interaction::Interaction;
 use hartex_discord_core::discord::util::builder::embed::EmbedAuthorBuilder;
 use hartex_discord_core::discord::util::builder::embed::EmbedBuilder;
@@ -40,15 +40,15 @@ use miette::IntoDiagnostic;
 use crate::general::General;
 
 /// The `contributors` command declaration.
-#[command(name = "contributors", plugin = General)]
+#[command(name = "contributors", plugin = General, contexts = [Guild, BotDm, PrivateChannel])]
 pub struct Contributors;
 
 #[async_trait]
 impl Command for Contributors {
     async fn execute(
         &self,
-        interaction: Interaction,
-        interaction_client: &InteractionClient<'_>,
+        _: Interaction,
+        response: &ResponseContext,
         localizer: Localizer<'_>,
     ) -> miette::Result<()> {
         let contributors_embed_title = localizer.general_plugin_contributors_embed_title()?;
@@ -89,14 +89,7 @@ impl Command for Contributors {
             .into_diagnostic()?
             .build();
 
-        interaction_client
-            .create_response(
-                interaction.id,
-                &interaction.token,
-                &embed_response(vec![embed]),
-            )
-            .await
-            .into_diagnostic()?;
+        response.respond(embed_response(vec![embed])).await?;
 
         Ok(())
     }
@@ -0,0 +1,161 @@
+/*
+ * SPDX-License-Identifier: AGPL-3.0-only
+ *
+ * This file is part of HarTex.
+ *
+ * HarTex
+ * Copyright (c) 2021-2024 HarTex Project Developers
+ *
+ * HarTex is free software; you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation; either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * HarTex is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License along
+ * with HarTex. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! # The Feedback Command
+//!
+//! This command opens a form for the user to send feedback about the bot, which is forwarded to a
+//! designated channel for feedback in the support server.
+
+use std::env;
+use std::str::FromStr;
+
+use async_trait::async_trait;
+use hartex_discord_commands_core::command;
+use hartex_discord_commands_core::customid::CustomId;
+use hartex_discord_commands_core::modal;
+use hartex_discord_commands_core::modalfields::ModalFields;
+use hartex_discord_commands_core::response::ResponseContext;
+use hartex_discord_commands_core::traits::Command;
+use hartex_discord_commands_core::traits::ModalHandler;
+use hartex_discord_core::discord::model::application::interaction::Interaction;
+use hartex_discord_core::discord::model::channel::message::MessageFlags;
+use hartex_discord_core::discord::model::http::interaction::InteractionResponse;
+use hartex_discord_core::discord::model::http::interaction::InteractionResponseType;
+use hartex_discord_core::discord::model::id::marker::ChannelMarker;
+use hartex_discord_core::discord::model::id::Id;
+use hartex_discord_core::discord::util::builder::embed::EmbedBuilder;
+use hartex_discord_core::discord::util::builder::embed::EmbedFieldBuilder;
+use hartex_discord_core::discord::util::builder::InteractionResponseDataBuilder;
+use hartex_discord_utils::interaction::Modal;
+use hartex_discord_utils::markdown::MarkdownStyle;
+use hartex_discord_utils::CLIENT;
+use hartex_localization_core::Localizer;
+use miette::IntoDiagnostic;
+use miette::Report;
+
+use crate::general::General;
+
+/// The component of the form for sending feedback.
+const FORM_COMPONENT: &str = "form";
+
+/// The custom ID of the text input for the feedback in the form.
+const FEEDBACK_TEXT_INPUT: &str = "feedback";
+
+/// The `feedback` command declaration.
+#[command(
+    name = "feedback",
+    plugin = General,
+    contexts = [Guild, BotDm, PrivateChannel],
+    cooldown = Cooldown::per_user(300)
+)]
+#[modal]
+pub struct Feedback;
+
+#[async_trait]
+impl Command for Feedback {
+    async fn execute(
+        &self,
+        _: Interaction,
+        response: &ResponseContext,
+        localizer: Localizer<'_>,
+    ) -> miette::Result<()> {
+        let feedback_modal_title = localizer.general_plugin_feedback_modal_title()?;
+        let feedback_modal_feedback_label =
+            localizer.general_plugin_feedback_modal_feedback_label()?;
+
+        let modal = Modal::new(
+            CustomId::new("feedback", FORM_COMPONENT).build()?,
+            feedback_modal_title,
+        )
+        .paragraph(FEEDBACK_TEXT_INPUT, feedback_modal_feedback_label, true)
+        .build()?;
+
+        response.respond(modal).await
+    }
+}
+
+#[async_trait]
+impl ModalHandler for Feedback {
+    async fn handle(
+        &self,
+        interaction: Interaction,
+        custom_id: CustomId,
+        fields: ModalFields,
+        response: &ResponseContext,
+        localizer: Localizer<'_>,
+    ) -> miette::Result<()> {
+        if custom_id.component() != FORM_COMPONENT {
+            return Err(Report::msg(format!(
+                "unknown modal {} of the feedback command",
+                custom_id.component()
+            )));
+        }
+
+        let feedback = fields.required(FEEDBACK_TEXT_INPUT)?;
+
+        let channel_id = env::var("FEEDBACK_CHANNEL_ID").into_diagnostic()?;
+        let channel_id = Id::<ChannelMarker>::from_str(&channel_id).into_diagnostic()?;
+
+        let or_none = |value: Option<String>| {
+            value.map_or_else(|| String::from("None"), MarkdownStyle::discord_inline_code)
+        };
+        let embed = EmbedBuilder::new()
+            .color(0x41_A0_DE)
+            .title("Feedback")
+            .description(feedback)
+            .field(
+                EmbedFieldBuilder::new(
+                    "User",
+                    or_none(interaction.author_id().map(|id| id.to_string())),
+                )
+                .inline(),
+            )
+            .field(
+                EmbedFieldBuilder::new(
+                    "Server",
+                    or_none(interaction.guild_id.map(|id| id.to_string())),
+                )
+                .inline(),
+            )
+            .validate()
+            .into_diagnostic()?
+            .build();
+
+        CLIENT
+            .create_message(channel_id)
+            .embeds(&[embed])
+            .await
+            .into_diagnostic()?;
+
+        response
+            .respond(InteractionResponse {
+                kind: InteractionResponseType::ChannelMessageWithSource,
+                data: Some(
+                    InteractionResponseDataBuilder::new()
+                        .content(localizer.general_plugin_feedback_submitted()?)
+                        .flags(MessageFlags::EPHEMERAL)
+                        .build(),
+                ),
+            })
+            .await
+    }
+}
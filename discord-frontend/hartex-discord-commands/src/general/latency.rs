@@ -0,0 +1,156 @@
+/*
+ * SPDX-License-Identifier: AGPL-3.0-only
+ *
+ * This file is part of HarTex.
+ *
+ * HarTex
+ * Copyright (c) 2021-2024 HarTex Project Developers
+ *
+ * HarTex is free software; you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation; either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * HarTex is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License along
+ * with HarTex. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! # The Latency Command
+//!
+//! This command returns the gateway heartbeat latency of each shard, the round-trip time of
+//! requests to the Discord REST API and the ping of the database.
+
+use std::pin::Pin;
+use std::time::Instant;
+
+use async_trait::async_trait;
+use hartex_database_queries::discord_frontend::queries::shard_latency_select_since::shard_latency_select_since;
+use hartex_discord_commands_core::command;
+use hartex_discord_commands_core::response::ResponseContext;
+use hartex_discord_commands_core::traits::Command;
+use hartex_discord_core::discord::model::application::interaction::Interaction;
+use hartex_discord_core::discord::util::builder::embed::EmbedBuilder;
+use hartex_discord_core::discord::util::builder::embed::EmbedFieldBuilder;
+use hartex_discord_utils::interaction::embed_response;
+use hartex_discord_utils::markdown::MarkdownStyle;
+use hartex_discord_utils::CLIENT;
+use hartex_discord_utils::DATABASE_POOL;
+use hartex_localization_core::Localizer;
+use miette::IntoDiagnostic;
+use time::Duration;
+use time::OffsetDateTime;
+use tokio_postgres::GenericClient;
+
+use crate::general::General;
+
+/// How recently a shard must have reported its latency to be listed.
+///
+/// Shards report their latency on every heartbeat, which is sent roughly every 41 seconds.
+const REPORTED_WITHIN: Duration = Duration::minutes(2);
+
+/// The maximum number of shards listed, such that the embed field does not exceed its length
+/// limit.
+const MAX_SHARDS_LISTED: usize = 20;
+
+/// The `latency` command declaration.
+#[command(name = "latency", plugin = General)]
+pub struct Latency;
+
+#[async_trait]
+impl Command for Latency {
+    async fn execute(
+        &self,
+        _: Interaction,
+        response: &ResponseContext,
+        localizer: Localizer<'_>,
+    ) -> miette::Result<()> {
+        let started = Instant::now();
+        CLIENT.current_user().await.into_diagnostic()?;
+        let rest_latency = started.elapsed().as_millis();
+
+        let pinned = Pin::static_ref(&DATABASE_POOL).await;
+        let pooled = pinned.get().await.into_diagnostic()?;
+        let client = pooled.client();
+
+        let started = Instant::now();
+        client.batch_execute("SELECT 1").await.into_diagnostic()?;
+        let database_latency = started.elapsed().as_millis();
+
+        let shards = shard_latency_select_since()
+            .bind(client, &(OffsetDateTime::now_utc() - REPORTED_WITHIN))
+            .all()
+            .await
+            .into_diagnostic()?;
+
+        let unmeasured = localizer.general_plugin_latency_embed_unmeasured()?;
+        let format_latency = |latency: Option<i64>| {
+            latency.map_or(unmeasured.clone(), |latency| {
+                latency.to_string().discord_inline_code()
+            })
+        };
+
+        let mut lines = shards
+            .iter()
+            .take(MAX_SHARDS_LISTED)
+            .map(|shard| {
+                localizer.general_plugin_latency_embed_shard(
+                    format_latency(shard.average_latency),
+                    format_latency(shard.latest_latency),
+                    shard.shard_count,
+                    shard.shard_id,
+                )
+            })
+            .collect::<miette::Result<Vec<_>>>()?;
+        if shards.len() > MAX_SHARDS_LISTED {
+            lines.push(
+                localizer
+                    .general_plugin_latency_embed_more_shards(shards.len() - MAX_SHARDS_LISTED)?,
+            );
+        }
+        if lines.is_empty() {
+            lines.push(localizer.general_plugin_latency_embed_no_shards()?);
+        }
+
+        let latency_embed_title = localizer.general_plugin_latency_embed_title()?;
+        let latency_embed_shards_field_name =
+            localizer.general_plugin_latency_embed_shards_field_name()?;
+        let latency_embed_rest_field_name =
+            localizer.general_plugin_latency_embed_rest_field_name()?;
+        let latency_embed_database_field_name =
+            localizer.general_plugin_latency_embed_database_field_name()?;
+
+        let embed = EmbedBuilder::new()
+            .color(0x41_A0_DE)
+            .field(EmbedFieldBuilder::new(
+                latency_embed_shards_field_name,
+                lines.join("\n"),
+            ))
+            .field(
+                EmbedFieldBuilder::new(
+                    latency_embed_rest_field_name,
+                    rest_latency.to_string().discord_inline_code(),
+                )
+                .inline(),
+            )
+            .field(
+                EmbedFieldBuilder::new(
+                    latency_embed_database_field_name,
+                    database_latency.to_string().discord_inline_code(),
+                )
+                .inline(),
+            )
+            .title(latency_embed_title)
+            .validate()
+            .into_diagnostic()?
+            .build();
+
+        response.respond(embed_response(vec![embed])).await?;
+
+        Ok(())
+    }
+}
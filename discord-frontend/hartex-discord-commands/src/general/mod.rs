@@ -25,6 +25,8 @@
 //! Command list:
 //! - about
 //! - contributors
+//! - feedback
+//! - latency
 
 use async_trait::async_trait;
 use hartex_discord_commands_core::plugin;
@@ -34,6 +36,8 @@ use hartex_discord_core::discord::model::id::Id;
 
 pub mod about;
 pub mod contributors;
+pub mod feedback;
+pub mod latency;
 
 /// The general plugin.
 #[plugin(name = "general")]
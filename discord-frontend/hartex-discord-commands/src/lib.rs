@@ -33,5 +33,18 @@
 #![feature(if_let_guard)]
 #![feature(let_chains)]
 
+use hartex_discord_commands_core::traits::Plugin;
+
+use crate::general::General;
+use crate::staff::Staff;
+use crate::utilities::Utilities;
+
 pub mod general;
+pub mod staff;
 pub mod utilities;
+
+/// Returns every plugin provided by the bot.
+#[must_use]
+pub fn plugins() -> Vec<Box<dyn Plugin + Send + Sync>> {
+    vec![Box::new(General), Box::new(Staff), Box::new(Utilities)]
+}
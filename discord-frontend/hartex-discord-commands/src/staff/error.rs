@@ -0,0 +1,195 @@
+/*
+ * SPDX-License-Identifier: AGPL-3.0-only
+ *
+ * This file is part of HarTex.
+ *
+ * HarTex
+ * Copyright (c) 2021-2024 HarTex Project Developers
+ *
+ * HarTex is free software; you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation; either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * HarTex is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License along
+ * with HarTex. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! # The Error Command
+//!
+//! This command looks up the report of an error by the error code given to the user that
+//! encountered it.
+
+use std::pin::Pin;
+
+use async_trait::async_trait;
+use hartex_database_queries::discord_frontend::queries::error_report_select_by_code::error_report_select_by_code;
+use hartex_discord_commands_core::command;
+use hartex_discord_commands_core::response::ResponseContext;
+use hartex_discord_commands_core::traits::Command;
+use hartex_discord_core::discord::model::application::interaction::Interaction;
+use hartex_discord_core::discord::model::application::interaction::InteractionData;
+use hartex_discord_core::discord::util::builder::embed::EmbedBuilder;
+use hartex_discord_core::discord::util::builder::embed::EmbedFieldBuilder;
+use hartex_discord_utils::commands::CommandDataOptionsExt;
+use hartex_discord_utils::interaction::ephemeral_embed_response;
+use hartex_discord_utils::interaction::ephemeral_error_response;
+use hartex_discord_utils::markdown::MarkdownStyle;
+use hartex_discord_utils::DATABASE_POOL;
+use hartex_localization_core::Localizer;
+use miette::IntoDiagnostic;
+use tokio_postgres::GenericClient;
+
+use crate::staff::Staff;
+
+/// The maximum number of characters of a report that is displayed, such that the report fits in
+/// the description of an embed.
+const REPORT_MAX_LENGTH: usize = 4000;
+
+/// The maximum number of characters of the options of a command that is displayed, such that the
+/// options fit in a field of an embed.
+const OPTIONS_MAX_LENGTH: usize = 1000;
+
+/// The `error` command declaration.
+///
+/// Reports contain the details of the guilds and users encountering errors, so the command
+/// requires the same permissions as its specification, and reports are only shown to the staff
+/// member looking them up.
+#[command(
+    name = "error",
+    plugin = Staff,
+    required_permissions = Permissions::MANAGE_MESSAGES
+)]
+pub struct Error;
+
+#[async_trait]
+impl Command for Error {
+    async fn execute(
+        &self,
+        interaction: Interaction,
+        response: &ResponseContext,
+        localizer: Localizer<'_>,
+    ) -> miette::Result<()> {
+        let Some(InteractionData::ApplicationCommand(command)) = interaction.data else {
+            unreachable!()
+        };
+
+        let code = command.options.string_value_of("code");
+
+        let pinned = Pin::static_ref(&DATABASE_POOL).await;
+        let pooled = pinned.get().await.into_diagnostic()?;
+        let client = pooled.client();
+
+        let Some(report) = error_report_select_by_code()
+            .bind(client, &code.trim())
+            .opt()
+            .await
+            .into_diagnostic()?
+        else {
+            response
+                .respond(ephemeral_error_response(
+                    localizer.staff_plugin_error_error_unknown_code(code)?,
+                ))
+                .await?;
+
+            return Ok(());
+        };
+
+        let error_embed_title = localizer.staff_plugin_error_embed_title()?;
+        let error_embed_code_field_name = localizer.staff_plugin_error_embed_code_field_name()?;
+        let error_embed_kind_field_name = localizer.staff_plugin_error_embed_kind_field_name()?;
+        let error_embed_command_field_name =
+            localizer.staff_plugin_error_embed_command_field_name()?;
+        let error_embed_options_field_name =
+            localizer.staff_plugin_error_embed_options_field_name()?;
+        let error_embed_guild_field_name = localizer.staff_plugin_error_embed_guild_field_name()?;
+        let error_embed_user_field_name = localizer.staff_plugin_error_embed_user_field_name()?;
+        let error_embed_shard_field_name = localizer.staff_plugin_error_embed_shard_field_name()?;
+        let error_embed_timestamp_field_name =
+            localizer.staff_plugin_error_embed_timestamp_field_name()?;
+        let error_embed_none = localizer.staff_plugin_error_embed_none()?;
+
+        let or_none = |value: Option<String>| {
+            value.map_or_else(
+                || error_embed_none.clone(),
+                MarkdownStyle::discord_inline_code,
+            )
+        };
+        let report_text = report
+            .report
+            .chars()
+            .take(REPORT_MAX_LENGTH)
+            .collect::<String>();
+        let options = report
+            .options
+            .map(|options| options.chars().take(OPTIONS_MAX_LENGTH).collect::<String>());
+
+        let embed = EmbedBuilder::new()
+            .color(if report.kind == "panic" {
+                0xFF_33_33
+            } else {
+                0xFF_99_33
+            })
+            .title(error_embed_title)
+            .description(report_text.discord_codeblock())
+            .field(EmbedFieldBuilder::new(
+                error_embed_code_field_name,
+                report.code.discord_inline_code(),
+            ))
+            .field(
+                EmbedFieldBuilder::new(
+                    error_embed_kind_field_name,
+                    report.kind.discord_inline_code(),
+                )
+                .inline(),
+            )
+            .field(
+                EmbedFieldBuilder::new(error_embed_command_field_name, or_none(report.command))
+                    .inline(),
+            )
+            .field(
+                EmbedFieldBuilder::new(
+                    error_embed_shard_field_name,
+                    format!("{}/{}", report.shard_id, report.shard_count).discord_inline_code(),
+                )
+                .inline(),
+            )
+            .field(
+                EmbedFieldBuilder::new(error_embed_guild_field_name, or_none(report.guild_id))
+                    .inline(),
+            )
+            .field(
+                EmbedFieldBuilder::new(error_embed_user_field_name, or_none(report.user_id))
+                    .inline(),
+            )
+            .field(
+                EmbedFieldBuilder::new(
+                    error_embed_timestamp_field_name,
+                    report
+                        .timestamp
+                        .unix_timestamp()
+                        .to_string()
+                        .discord_relative_timestamp(),
+                )
+                .inline(),
+            )
+            .field(EmbedFieldBuilder::new(
+                error_embed_options_field_name,
+                or_none(options),
+            ))
+            .validate()
+            .into_diagnostic()?
+            .build();
+
+        response
+            .respond(ephemeral_embed_response(vec![embed]))
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,51 @@
+/*
+ * SPDX-License-Identifier: AGPL-3.0-only
+ *
+ * This file is part of HarTex.
+ *
+ * HarTex
+ * Copyright (c) 2021-2024 HarTex Project Developers
+ *
+ * HarTex is free software; you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation; either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * HarTex is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License along
+ * with HarTex. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! # The Staff Plugin
+//!
+//! Command list:
+//! - error
+
+use std::env;
+
+use async_trait::async_trait;
+use hartex_discord_commands_core::plugin;
+use hartex_discord_commands_core::traits::Plugin;
+use hartex_discord_core::discord::model::id::marker::GuildMarker;
+use hartex_discord_core::discord::model::id::Id;
+use miette::IntoDiagnostic;
+
+pub mod error;
+
+/// The staff plugin.
+#[plugin(name = "staff")]
+pub struct Staff;
+
+#[async_trait]
+impl Plugin for Staff {
+    // staff commands are only available in the support server
+    async fn enabled(&self, guild_id: Id<GuildMarker>) -> miette::Result<bool> {
+        let support_guild_id = env::var("SUPPORT_GUILD_ID").into_diagnostic()?;
+
+        Ok(guild_id.to_string() == support_guild_id)
+    }
+}
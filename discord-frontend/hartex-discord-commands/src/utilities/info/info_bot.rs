@@ -30,7 +30,7 @@ use std::time::SystemTime;
 use hartex_backend_models::uptime::UptimeQuery;
 use hartex_backend_models::uptime::UptimeResponse;
 use hartex_backend_models::Response;
-use hartex_discord_core::discord::http::client::InteractionClient;
+use hartex_discord_commands_core::response::ResponseContext;
 use hartex_discord_core::discord::model::application::interaction::application_command::CommandDataOption;
 use hartex_discord_core::discord::model::application::interaction::Interaction;
 use hartex_discord_core::discord::util::builder::embed::EmbedBuilder;
@@ -54,8 +54,8 @@ use miette::Report;
 
 /// Executes the `info bot` command
 pub async fn execute(
-    interaction: Interaction,
-    interaction_client: &InteractionClient<'_>,
+    _: Interaction,
+    response: &ResponseContext,
     _: CommandDataOption,
     localizer: Localizer<'_>,
 ) -> miette::Result<()> {
@@ -86,12 +86,12 @@ pub async fn execute(
     let result = sender.send_request(request).await.into_diagnostic()?;
     log::debug!("deserializing result");
     let body = result.collect().await.into_diagnostic()?.aggregate();
-    let response: Response<UptimeResponse> =
+    let uptime_response: Response<UptimeResponse> =
         serde_json::from_reader(body.reader()).into_diagnostic()?;
 
     let latency = now.elapsed().into_diagnostic()?.as_millis();
 
-    let data = response.data();
+    let data = uptime_response.data();
     let timestamp = data
         .ok_or(Report::msg("failed to obtain uptime data"))?
         .start_timestamp();
@@ -117,14 +117,7 @@ pub async fn execute(
         .into_diagnostic()?
         .build();
 
-    interaction_client
-        .create_response(
-            interaction.id,
-            &interaction.token,
-            &embed_response(vec![embed]),
-        )
-        .await
-        .into_diagnostic()?;
+    response.respond(embed_response(vec![embed])).await?;
 
     Ok(())
 }
@@ -26,7 +26,11 @@
 
 use std::str::FromStr;
 
-use hartex_discord_core::discord::http::client::InteractionClient;
+use hartex_discord_commands_core::autocomplete::FocusedOption;
+use hartex_discord_commands_core::autocomplete::MAX_CHOICES;
+use hartex_discord_commands_core::response::ResponseContext;
+use hartex_discord_core::discord::model::application::command::CommandOptionChoice;
+use hartex_discord_core::discord::model::application::command::CommandOptionChoiceValue;
 use hartex_discord_core::discord::model::application::interaction::application_command::CommandDataOption;
 use hartex_discord_core::discord::model::application::interaction::Interaction;
 use hartex_discord_core::discord::model::id::marker::EmojiMarker;
@@ -53,11 +57,44 @@ lazy_static::lazy_static! {
     static ref EMOJI_REGEX: Regex = Regex::new("<a?:[a-zA-Z0-9_]+:([0-9]{17,19})>").unwrap();
 }
 
+/// Suggests the custom emojis of the current server whose names contain the input.
+pub async fn autocomplete(
+    interaction: Interaction,
+    focused: FocusedOption,
+) -> miette::Result<Vec<CommandOptionChoice>> {
+    let Some(guild_id) = interaction.guild_id else {
+        return Ok(Vec::new());
+    };
+
+    let input = focused.value.to_lowercase();
+    let emojis = CachedEmojiRepository
+        .emojis_in_guild(guild_id)
+        .await
+        .into_diagnostic()?;
+
+    Ok(emojis
+        .into_iter()
+        .filter(|emoji| emoji.name.to_lowercase().contains(&input))
+        .take(MAX_CHOICES)
+        .map(|emoji| CommandOptionChoice {
+            // the value is the emoji itself, such that it is handled like a typed emoji
+            value: CommandOptionChoiceValue::String(format!(
+                "<{}:{}:{}>",
+                if emoji.animated { "a" } else { "" },
+                emoji.name,
+                emoji.id
+            )),
+            name: emoji.name,
+            name_localizations: None,
+        })
+        .collect())
+}
+
 /// Executes the `info emoji` command.
 #[allow(clippy::too_many_lines)]
 pub async fn execute(
     interaction: Interaction,
-    interaction_client: &InteractionClient<'_>,
+    response: &ResponseContext,
     option: CommandDataOption,
     localizer: Localizer<'_>,
 ) -> miette::Result<()> {
@@ -78,27 +115,17 @@ pub async fn execute(
         localizer.utilities_plugin_emojiinfo_error_unknown_emoji()?;
 
     let Some(captures) = EMOJI_REGEX.captures(&emoji) else {
-        interaction_client
-            .create_response(
-                interaction.id,
-                &interaction.token,
-                &ephemeral_error_response(emojiinfo_error_only_custom_emojis),
-            )
-            .await
-            .into_diagnostic()?;
+        response
+            .respond(ephemeral_error_response(emojiinfo_error_only_custom_emojis))
+            .await?;
 
         return Ok(());
     };
 
     if captures.len() > 2 {
-        interaction_client
-            .create_response(
-                interaction.id,
-                &interaction.token,
-                &ephemeral_error_response(emojiinfo_error_only_one_emoji),
-            )
-            .await
-            .into_diagnostic()?;
+        response
+            .respond(ephemeral_error_response(emojiinfo_error_only_one_emoji))
+            .await?;
 
         return Ok(());
     }
@@ -110,14 +137,9 @@ pub async fn execute(
     let emoji = match result {
         Ok(emoji) => emoji,
         Err(CacheError::Postgres(postgres_error)) if postgres_error.is(SqlState::NO_DATA) => {
-            interaction_client
-                .create_response(
-                    interaction.id,
-                    &interaction.token,
-                    &ephemeral_error_response(emojiinfo_error_unknown_emoji),
-                )
-                .await
-                .into_diagnostic()?;
+            response
+                .respond(ephemeral_error_response(emojiinfo_error_unknown_emoji))
+                .await?;
 
             return Ok(());
         }
@@ -159,14 +181,7 @@ pub async fn execute(
         .into_diagnostic()?
         .build();
 
-    interaction_client
-        .create_response(
-            interaction.id,
-            &interaction.token,
-            &embed_response(vec![embed]),
-        )
-        .await
-        .into_diagnostic()?;
+    response.respond(embed_response(vec![embed])).await?;
 
     Ok(())
 }
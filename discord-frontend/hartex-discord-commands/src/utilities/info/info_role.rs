@@ -25,7 +25,7 @@
 //! This command returns informatiomn about a role.
 
 use hartex_discord_cdn::Cdn;
-use hartex_discord_core::discord::http::client::InteractionClient;
+use hartex_discord_commands_core::response::ResponseContext;
 use hartex_discord_core::discord::mention::Mention;
 use hartex_discord_core::discord::model::application::interaction::application_command::CommandDataOption;
 use hartex_discord_core::discord::model::application::interaction::Interaction;
@@ -33,20 +33,24 @@ use hartex_discord_core::discord::util::builder::embed::EmbedBuilder;
 use hartex_discord_core::discord::util::builder::embed::EmbedFieldBuilder;
 use hartex_discord_core::discord::util::builder::embed::ImageSource;
 use hartex_discord_core::discord::util::snowflake::Snowflake;
+use hartex_discord_entitycache_core::error::CacheError;
 use hartex_discord_entitycache_core::traits::Repository;
 use hartex_discord_entitycache_repositories::role::CachedRoleRepository;
 use hartex_discord_utils::commands::CommandDataOptionExt;
 use hartex_discord_utils::commands::CommandDataOptionsExt;
 use hartex_discord_utils::interaction::embed_response;
+use hartex_discord_utils::interaction::ephemeral_error_response;
 use hartex_discord_utils::localizable::Localizable;
 use hartex_discord_utils::markdown::MarkdownStyle;
+use hartex_discord_utils::postgres::PostgresErrorExt;
 use hartex_localization_core::Localizer;
 use miette::IntoDiagnostic;
+use tokio_postgres::error::SqlState;
 
-/// Executes the `info emoji` command.
+/// Executes the `info role` command.
 pub async fn execute(
     interaction: Interaction,
-    interaction_client: &InteractionClient<'_>,
+    response: &ResponseContext,
     option: CommandDataOption,
     localizer: Localizer<'_>,
 ) -> miette::Result<()> {
@@ -79,11 +83,22 @@ pub async fn execute(
         localizer.utilities_plugin_roleinfo_embed_attributes_mentionable_subfield_name()?;
     let roleinfo_embed_attributes_position_subfield_name =
         localizer.utilities_plugin_roleinfo_embed_attributes_position_subfield_name()?;
+    let roleinfo_error_unknown_role = localizer.utilities_plugin_roleinfo_error_unknown_role()?;
 
-    let role = CachedRoleRepository
+    let result = CachedRoleRepository
         .get((interaction.guild_id.unwrap(), role_id))
-        .await
-        .into_diagnostic()?;
+        .await;
+    let role = match result {
+        Ok(role) => role,
+        Err(CacheError::Postgres(postgres_error)) if postgres_error.is(SqlState::NO_DATA) => {
+            response
+                .respond(ephemeral_error_response(roleinfo_error_unknown_role))
+                .await?;
+
+            return Ok(());
+        }
+        error => error.into_diagnostic()?,
+    };
 
     let mut builder = EmbedBuilder::new()
         .color(0x41_A0_DE)
@@ -124,14 +139,7 @@ pub async fn execute(
 
     let embed = builder.validate().into_diagnostic()?.build();
 
-    interaction_client
-        .create_response(
-            interaction.id,
-            &interaction.token,
-            &embed_response(vec![embed]),
-        )
-        .await
-        .into_diagnostic()?;
+    response.respond(embed_response(vec![embed])).await?;
 
     Ok(())
 }
@@ -29,43 +29,86 @@ use std::fmt::Write;
 
 use futures::future;
 use hartex_discord_cdn::Cdn;
-use hartex_discord_core::discord::http::client::InteractionClient;
+use hartex_discord_commands_core::customid::CustomId;
+use hartex_discord_commands_core::response::ResponseContext;
 use hartex_discord_core::discord::mention::Mention;
 use hartex_discord_core::discord::model::application::interaction::application_command::CommandDataOption;
 use hartex_discord_core::discord::model::application::interaction::Interaction;
+use hartex_discord_core::discord::model::channel::message::component::ActionRow;
+use hartex_discord_core::discord::model::channel::message::component::Button;
+use hartex_discord_core::discord::model::channel::message::component::ButtonStyle;
+use hartex_discord_core::discord::model::channel::message::Component;
 use hartex_discord_core::discord::model::channel::ChannelType;
+use hartex_discord_core::discord::model::http::interaction::InteractionResponse;
+use hartex_discord_core::discord::model::http::interaction::InteractionResponseData;
+use hartex_discord_core::discord::model::http::interaction::InteractionResponseType;
 use hartex_discord_core::discord::util::builder::embed::EmbedBuilder;
 use hartex_discord_core::discord::util::builder::embed::EmbedFieldBuilder;
 use hartex_discord_core::discord::util::builder::embed::ImageSource;
+use hartex_discord_core::discord::util::builder::InteractionResponseDataBuilder;
 use hartex_discord_core::discord::util::snowflake::Snowflake;
 use hartex_discord_entitycache_core::traits::Repository;
 use hartex_discord_entitycache_repositories::guild::CachedGuildRepository;
 use hartex_discord_utils::commands::CommandDataOptionExt;
 use hartex_discord_utils::commands::CommandDataOptionsExt;
-use hartex_discord_utils::interaction::embed_response;
 use hartex_discord_utils::localizable::Localizable;
 use hartex_discord_utils::markdown::MarkdownStyle;
 use hartex_discord_utils::CLIENT;
 use hartex_localization_core::Localizer;
 use miette::IntoDiagnostic;
 
+/// The component of the button toggling whether the features of the server are shown.
+pub const FEATURES_COMPONENT: &str = "server-features";
+
 /// Executes the `info server` command.
-#[allow(clippy::too_many_lines)]
 pub async fn execute(
     interaction: Interaction,
-    interaction_client: &InteractionClient<'_>,
+    response: &ResponseContext,
     option: CommandDataOption,
     localizer: Localizer<'_>,
 ) -> miette::Result<()> {
     let options = option.assume_subcommand();
+    let verbose = options.boolean_value_of("verbose");
+
+    response
+        .respond(InteractionResponse {
+            kind: InteractionResponseType::ChannelMessageWithSource,
+            data: Some(server_information(&interaction, verbose, &localizer).await?),
+        })
+        .await
+}
+
+/// Handles the button toggling whether the features of the server are shown, updating the
+/// message with the information about the server accordingly.
+pub async fn toggle_features(
+    interaction: Interaction,
+    custom_id: CustomId,
+    response: &ResponseContext,
+    localizer: Localizer<'_>,
+) -> miette::Result<()> {
+    let verbose = custom_id.parse_state(0)?;
 
+    response
+        .respond(InteractionResponse {
+            kind: InteractionResponseType::UpdateMessage,
+            data: Some(server_information(&interaction, verbose, &localizer).await?),
+        })
+        .await
+}
+
+/// Builds the message with the information about the server, with a button toggling whether the
+/// features of the server are shown.
+#[allow(clippy::too_many_lines)]
+async fn server_information(
+    interaction: &Interaction,
+    verbose: bool,
+    localizer: &Localizer<'_>,
+) -> miette::Result<InteractionResponseData> {
     let langid_locale = interaction
         .locale
         .clone()
         .and_then(|locale| locale.parse().ok());
 
-    let verbose = options.boolean_value_of("verbose");
-
     let guild = CachedGuildRepository
         .get(interaction.guild_id.unwrap())
         .await
@@ -293,14 +336,29 @@ pub async fn execute(
 
     let embed = builder.validate().into_diagnostic()?.build();
 
-    interaction_client
-        .create_response(
-            interaction.id,
-            &interaction.token,
-            &embed_response(vec![embed]),
-        )
-        .await
-        .into_diagnostic()?;
+    let label = if verbose {
+        localizer.utilities_plugin_serverinfo_button_hide_features()?
+    } else {
+        localizer.utilities_plugin_serverinfo_button_show_features()?
+    };
+    let button = Component::Button(Button {
+        custom_id: Some(
+            CustomId::new("info", FEATURES_COMPONENT)
+                .with_state(!verbose)
+                .build()?,
+        ),
+        disabled: false,
+        emoji: None,
+        label: Some(label),
+        style: ButtonStyle::Secondary,
+        url: None,
+        sku_id: None,
+    });
 
-    Ok(())
+    Ok(InteractionResponseDataBuilder::new()
+        .embeds(vec![embed])
+        .components(vec![Component::ActionRow(ActionRow {
+            components: vec![button],
+        })])
+        .build())
 }
@@ -25,7 +25,7 @@
 //! This command returns informatiomn about a user.
 
 use hartex_discord_cdn::Cdn;
-use hartex_discord_core::discord::http::client::InteractionClient;
+use hartex_discord_commands_core::response::ResponseContext;
 use hartex_discord_core::discord::mention::Mention;
 use hartex_discord_core::discord::model::application::interaction::application_command::CommandDataOption;
 use hartex_discord_core::discord::model::application::interaction::Interaction;
@@ -49,7 +49,7 @@ use rand::thread_rng;
 #[allow(clippy::too_many_lines)]
 pub async fn execute(
     interaction: Interaction,
-    interaction_client: &InteractionClient<'_>,
+    response: &ResponseContext,
     option: CommandDataOption,
     localizer: Localizer<'_>,
 ) -> miette::Result<()> {
@@ -157,14 +157,7 @@ pub async fn execute(
 
     let embed = builder.validate().into_diagnostic()?.build();
 
-    interaction_client
-        .create_response(
-            interaction.id,
-            &interaction.token,
-            &embed_response(vec![embed]),
-        )
-        .await
-        .into_diagnostic()?;
+    response.respond(embed_response(vec![embed])).await?;
 
     Ok(())
 }
@@ -23,13 +23,19 @@
 //! # The Info Command
 
 use async_trait::async_trait;
+use hartex_discord_commands_core::autocomplete::FocusedOption;
 use hartex_discord_commands_core::command;
+use hartex_discord_commands_core::component;
+use hartex_discord_commands_core::customid::CustomId;
+use hartex_discord_commands_core::response::ResponseContext;
 use hartex_discord_commands_core::traits::Command;
-use hartex_discord_core::discord::http::client::InteractionClient;
+use hartex_discord_commands_core::traits::ComponentHandler;
+use hartex_discord_core::discord::model::application::command::CommandOptionChoice;
 use hartex_discord_core::discord::model::application::interaction::application_command::CommandOptionValue;
 use hartex_discord_core::discord::model::application::interaction::Interaction;
 use hartex_discord_core::discord::model::application::interaction::InteractionData;
 use hartex_localization_core::Localizer;
+use miette::Report;
 
 use crate::utilities::Utilities;
 
@@ -40,7 +46,8 @@ mod info_server;
 mod info_user;
 
 /// The `info` command declaration.
-#[command(name = "info", plugin = Utilities)]
+#[command(name = "info", plugin = Utilities, cooldown = Cooldown::per_user(5).burst(3))]
+#[component]
 pub struct Info;
 
 #[async_trait]
@@ -48,7 +55,7 @@ impl Command for Info {
     async fn execute(
         &self,
         interaction: Interaction,
-        interaction_client: &InteractionClient<'_>,
+        response: &ResponseContext,
         localizer: Localizer<'_>,
     ) -> miette::Result<()> {
         let Some(InteractionData::ApplicationCommand(command)) = interaction.clone().data else {
@@ -64,52 +71,52 @@ impl Command for Info {
         };
 
         match subcommand.name.as_str() {
-            "bot" => {
-                info_bot::execute(
-                    interaction,
-                    interaction_client,
-                    subcommand.clone(),
-                    localizer,
-                )
-                .await
-            }
+            "bot" => info_bot::execute(interaction, response, subcommand.clone(), localizer).await,
             "emoji" => {
-                info_emoji::execute(
-                    interaction,
-                    interaction_client,
-                    subcommand.clone(),
-                    localizer,
-                )
-                .await
+                info_emoji::execute(interaction, response, subcommand.clone(), localizer).await
             }
             "role" => {
-                info_role::execute(
-                    interaction,
-                    interaction_client,
-                    subcommand.clone(),
-                    localizer,
-                )
-                .await
+                info_role::execute(interaction, response, subcommand.clone(), localizer).await
             }
             "server" => {
-                info_server::execute(
-                    interaction,
-                    interaction_client,
-                    subcommand.clone(),
-                    localizer,
-                )
-                .await
+                info_server::execute(interaction, response, subcommand.clone(), localizer).await
             }
             "user" => {
-                info_user::execute(
-                    interaction,
-                    interaction_client,
-                    subcommand.clone(),
-                    localizer,
-                )
-                .await
+                info_user::execute(interaction, response, subcommand.clone(), localizer).await
             }
             _ => unreachable!(),
         }
     }
+
+    async fn autocomplete(
+        &self,
+        interaction: Interaction,
+        focused: FocusedOption,
+        _: Localizer<'_>,
+    ) -> miette::Result<Vec<CommandOptionChoice>> {
+        match focused.path.first().map(String::as_str) {
+            Some("emoji") => info_emoji::autocomplete(interaction, focused).await,
+            _ => Ok(Vec::new()),
+        }
+    }
+}
+
+#[async_trait]
+impl ComponentHandler for Info {
+    async fn handle(
+        &self,
+        interaction: Interaction,
+        custom_id: CustomId,
+        response: &ResponseContext,
+        localizer: Localizer<'_>,
+    ) -> miette::Result<()> {
+        match custom_id.component() {
+            info_server::FEATURES_COMPONENT => {
+                info_server::toggle_features(interaction, custom_id, response, localizer).await
+            }
+            component => Err(Report::msg(format!(
+                "unknown component {component} of the info command"
+            ))),
+        }
+    }
 }
@@ -28,6 +28,7 @@
 use async_trait::async_trait;
 use hartex_discord_commands_core::plugin;
 use hartex_discord_commands_core::traits::Plugin;
+use hartex_discord_core::discord::model::gateway::Intents;
 
 pub mod info;
 
@@ -36,4 +37,9 @@ pub mod info;
 pub struct Utilities;
 
 #[async_trait]
-impl Plugin for Utilities {}
+impl Plugin for Utilities {
+    fn intents(&self) -> Intents {
+        // guilds and members are read from the cache
+        Intents::GUILDS | Intents::GUILD_MEMBERS
+    }
+}
@@ -35,6 +35,14 @@ const SAMPLE_CONFIG: &'static str = r#"return {
         colour = hartexconf.colour.rgb(0x768EE5)
     },
 
+    cooldowns = {
+        info = {
+            per = "channel",
+            duration = 10,
+            burst = 3
+        }
+    },
+
     permissions = {
         roles = {
             ["1234567890987654"] = 100
@@ -69,6 +77,21 @@ Configuration {
             ),
         },
     ),
+    cooldowns: Some(
+        {
+            "info": Cooldown {
+                per: Some(
+                    "channel",
+                ),
+                duration: Some(
+                    10,
+                ),
+                burst: Some(
+                    3,
+                ),
+            },
+        },
+    ),
     dashboard: Dashboard {
         admins: [
             "1000000000000000",
@@ -89,6 +112,7 @@ Configuration {
     },
     plugins: Some(
         Plugins {
+            management: None,
             utilities: Some(
                 UtilitiesPlugin {
                     enabled: true,
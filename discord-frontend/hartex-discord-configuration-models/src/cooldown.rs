@@ -0,0 +1,72 @@
+/*
+ * SPDX-License-Identifier: AGPL-3.0-only
+ *
+ * This file is part of HarTex.
+ *
+ * HarTex
+ * Copyright (c) 2021-2024 HarTex Project Developers
+ *
+ * HarTex is free software; you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation; either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * HarTex is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License along
+ * with HarTex. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! # Cooldown Configuration Object
+
+use mlua::Error;
+use mlua::FromLua;
+use mlua::Lua;
+use mlua::Value;
+use serde::Serialize;
+
+/// The cooldown configuration object, overriding the cooldown of a command.
+///
+/// Any field that is not specified is taken from the cooldown declared by the command.
+#[derive(Debug, Serialize)]
+pub struct Cooldown {
+    /// What the cooldown applies to, one of `user`, `channel` or `guild`.
+    pub per: Option<String>,
+    /// The duration of the cooldown in seconds. A duration of zero disables the cooldown.
+    pub duration: Option<u64>,
+    /// The number of times the command can be run within the duration.
+    pub burst: Option<u32>,
+}
+
+impl<'lua> FromLua<'lua> for Cooldown {
+    fn from_lua(lua_value: Value<'lua>, _: &'lua Lua) -> mlua::Result<Self> {
+        let Value::Table(table) = lua_value.clone() else {
+            return Err(Error::RuntimeError(format!(
+                "Cooldown: mismatched value type, exoected table, found: {}",
+                lua_value.type_name()
+            )));
+        };
+
+        let per: Option<String> = table.get("per")?;
+        if let Some(per) = per
+            .as_deref()
+            .filter(|per| !["user", "channel", "guild"].contains(per))
+        {
+            return Err(Error::RuntimeError(format!(
+                "Cooldown: invalid value for per, expected user, channel or guild, found: {per}"
+            )));
+        }
+
+        let duration = table.get("duration")?;
+        let burst = table.get("burst")?;
+
+        Ok(Self {
+            per,
+            duration,
+            burst,
+        })
+    }
+}
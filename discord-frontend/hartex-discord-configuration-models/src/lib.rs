@@ -30,6 +30,8 @@
 #![deny(warnings)]
 #![feature(result_flattening)]
 
+use std::collections::HashMap;
+
 use mlua::Error;
 use mlua::FromLuaMulti;
 use mlua::Lua;
@@ -38,6 +40,7 @@ use mlua::Value;
 use serde::Serialize;
 
 pub mod appearance;
+pub mod cooldown;
 pub mod dashboard;
 pub mod plugins;
 
@@ -45,6 +48,8 @@ pub mod plugins;
 pub struct Configuration {
     /// An optional appearance configuration object.
     pub appearance: Option<appearance::Appearance>,
+    /// Optional overrides of the cooldowns of commands, by the names of the commands.
+    pub cooldowns: Option<HashMap<String, cooldown::Cooldown>>,
     /// A dashboard configuration object.
     pub dashboard: dashboard::Dashboard,
     /// An optional plugins configuration object.
@@ -68,11 +73,13 @@ impl<'lua> FromLuaMulti<'lua> for Configuration {
         };
 
         let appearance = value.get("appearance")?;
+        let cooldowns = value.get("cooldowns")?;
         let dashboard = value.get("dashboard")?;
         let plugins = value.get("plugins")?;
 
         Ok(Self {
             appearance,
+            cooldowns,
             dashboard,
             plugins,
         })
@@ -30,6 +30,8 @@
 
 use std::pin::Pin;
 
+use hartex_database_queries::configuration::queries::command_cooldown_select::command_cooldown_select;
+use hartex_database_queries::configuration::queries::command_cooldown_select::CommandCooldownSelect;
 use hartex_database_queries::configuration::queries::plugin_enabled::plugin_enabled;
 use hartex_discord_core::discord::model::id::marker::GuildMarker;
 use hartex_discord_core::discord::model::id::Id;
@@ -41,6 +43,26 @@ use tokio_postgres::GenericClient;
 pub struct ConfigurationProvider;
 
 impl ConfigurationProvider {
+    /// Queries the cooldown overrides of a specific command for a certain guild.
+    ///
+    /// Returns `None` if the guild has no configuration. Each of the overrides is `None` if it is
+    /// not configured by the guild.
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn command_cooldown(
+        guild_id: Id<GuildMarker>,
+        command: impl Into<String>,
+    ) -> miette::Result<Option<CommandCooldownSelect>> {
+        let pinned = Pin::static_ref(&DATABASE_POOL).await;
+        let pooled = pinned.get().await.into_diagnostic()?;
+        let client = pooled.client();
+
+        command_cooldown_select()
+            .bind(client, &command.into(), &guild_id.to_string())
+            .opt()
+            .await
+            .into_diagnostic()
+    }
+
     /// Queries whether a specific plugin is enabled for a certain guild.
     #[allow(clippy::missing_errors_doc)]
     pub async fn plugin_enabled(
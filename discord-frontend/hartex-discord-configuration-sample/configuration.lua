@@ -42,6 +42,18 @@ return {
         colour = hartexconf.colour.rgb(0x768EE5)
     },
 
+    -- Overrides of the cooldowns of commands, by the names of the commands.
+    cooldowns = {
+        info = {
+            -- What the cooldown applies to, one of "user", "channel" or "guild".
+            per = "channel",
+            -- The duration of the cooldown in seconds. A duration of zero disables the cooldown.
+            duration = 10,
+            -- The number of times the command can be run within the duration.
+            burst = 3
+        }
+    },
+
     -- Configuration for various plugins.
     plugins = {
         -- Configuration for the Management plugin.
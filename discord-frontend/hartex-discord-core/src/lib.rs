@@ -34,3 +34,5 @@ pub use dotenvy;
 pub use tokio;
 
 pub mod discord;
+#[cfg(feature = "async-shutdown")]
+pub mod shutdown;
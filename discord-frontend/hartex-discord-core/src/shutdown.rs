@@ -0,0 +1,176 @@
+/*
+ * SPDX-License-Identifier: AGPL-3.0-only
+ *
+ * This file is part of HarTex.
+ *
+ * HarTex
+ * Copyright (c) 2021-2024 HarTex Project Developers
+ *
+ * HarTex is free software; you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation; either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * HarTex is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License along
+ * with HarTex. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! # Graceful Shutdown
+//!
+//! Long-running processes shut down in stages once they receive SIGINT or SIGTERM: they stop
+//! accepting new work, wait a limited time for the work in flight to finish, and then release the
+//! resources they hold, such as uncommitted Kafka offsets and database connections.
+
+use std::future;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::signal;
+use tokio::sync::watch;
+use tokio::sync::Notify;
+use tokio::time;
+
+/// The default duration to wait for the tasks in flight to finish.
+pub const DEFAULT_DRAIN_TIMEOUT: Duration = Duration::from_secs(20);
+
+/// The state shared by the handles of a controller.
+struct Inner {
+    requested: watch::Sender<bool>,
+    in_flight: AtomicUsize,
+    drained: Notify,
+}
+
+/// Coordinates the shutdown of a process.
+///
+/// Handles are cheap to clone and share the same state.
+#[allow(clippy::module_name_repetitions)]
+#[derive(Clone)]
+pub struct ShutdownController {
+    inner: Arc<Inner>,
+}
+
+impl ShutdownController {
+    /// Construct a controller.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                requested: watch::Sender::new(false),
+                in_flight: AtomicUsize::new(0),
+                drained: Notify::new(),
+            }),
+        }
+    }
+
+    /// Request shutdown once SIGINT, or SIGTERM on Unix, is received.
+    pub fn listen_for_signals(&self) {
+        let controller = self.clone();
+        tokio::spawn(async move {
+            signal_received().await;
+            controller.request();
+        });
+    }
+
+    /// Request shutdown.
+    pub fn request(&self) {
+        self.inner.requested.send_replace(true);
+    }
+
+    /// Whether shutdown has been requested.
+    #[must_use]
+    pub fn is_requested(&self) -> bool {
+        *self.inner.requested.borrow()
+    }
+
+    /// Wait until shutdown is requested.
+    pub async fn requested(&self) {
+        let mut receiver = self.inner.requested.subscribe();
+
+        // the sender lives as long as the controller, so this only returns once requested
+        let _ = receiver.wait_for(|requested| *requested).await;
+    }
+
+    /// Track a task in flight, until the returned guard is dropped.
+    #[must_use]
+    pub fn track(&self) -> InFlightGuard {
+        self.inner.in_flight.fetch_add(1, Ordering::SeqCst);
+
+        InFlightGuard {
+            inner: self.inner.clone(),
+        }
+    }
+
+    /// Wait for the tasks in flight to finish, up to a timeout, returning the number of tasks
+    /// still in flight.
+    pub async fn drain(&self, timeout: Duration) -> usize {
+        let drained = async {
+            loop {
+                // the notification is registered before checking, such that it cannot be missed
+                let notified = self.inner.drained.notified();
+                if self.inner.in_flight.load(Ordering::SeqCst) == 0 {
+                    break;
+                }
+
+                notified.await;
+            }
+        };
+        let _ = time::timeout(timeout, drained).await;
+
+        self.inner.in_flight.load(Ordering::SeqCst)
+    }
+}
+
+impl Default for ShutdownController {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A guard tracking a task in flight.
+pub struct InFlightGuard {
+    inner: Arc<Inner>,
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        if self.inner.in_flight.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.inner.drained.notify_waiters();
+        }
+    }
+}
+
+/// Wait until SIGINT, or SIGTERM on Unix, is received.
+///
+/// A signal whose handler cannot be installed is never received.
+async fn signal_received() {
+    let interrupt = async {
+        if signal::ctrl_c().await.is_err() {
+            future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match signal::unix::signal(signal::unix::SignalKind::terminate()) {
+            Ok(mut terminate) => {
+                terminate.recv().await;
+            }
+            Err(_) => future::pending::<()>().await,
+        }
+    };
+
+    #[cfg(not(unix))]
+    let terminate = future::pending::<()>();
+
+    tokio::select! {
+        () = interrupt => {},
+        () = terminate => {},
+    }
+}
@@ -0,0 +1,69 @@
+/*
+ * SPDX-License-Identifier: AGPL-3.0-only
+ *
+ * This file is part of HarTex.
+ *
+ * HarTex
+ * Copyright (c) 2021-2024 HarTex Project Developers
+ *
+ * HarTex is free software; you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation; either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * HarTex is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License along
+ * with HarTex. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! # Inspect Command
+
+use clap::ArgMatches;
+use hartex_discord_core::dotenvy;
+use hartex_kafka_utils::deadletter::DeadLetterStage;
+use hartex_log::log;
+use miette::IntoDiagnostic;
+use miette::Report;
+use rdkafka::Message;
+
+use crate::deadletters;
+
+/// Inspect the records in the dead-letter topic.
+#[allow(clippy::module_name_repetitions)]
+pub fn inspect_command(matches: ArgMatches) -> miette::Result<()> {
+    log::trace!("loading environment variables");
+    dotenvy::dotenv().into_diagnostic()?;
+
+    let service = matches.get_one::<String>("service");
+    let stage = matches
+        .get_one::<String>("stage")
+        .map(|stage| {
+            stage
+                .parse::<DeadLetterStage>()
+                .map_err(|()| Report::msg(format!("unknown stage: {stage}")))
+        })
+        .transpose()?;
+
+    log::trace!("reading dead-letter topic");
+    let records = deadletters::read_all()?
+        .into_iter()
+        .filter(|record| service.map_or(true, |service| &record.dead_letter.service == service))
+        .filter(|record| stage.map_or(true, |stage| record.dead_letter.stage == stage))
+        .collect::<Vec<_>>();
+    log::info!("found {} dead letters", records.len());
+
+    for record in records {
+        print!("{record}");
+
+        if matches.get_flag("with-payloads") {
+            let payload = record.message.payload().unwrap_or_default();
+            println!("{}", String::from_utf8_lossy(payload));
+        }
+    }
+
+    Ok(())
+}
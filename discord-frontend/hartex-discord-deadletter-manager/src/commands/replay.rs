@@ -0,0 +1,136 @@
+/*
+ * SPDX-License-Identifier: AGPL-3.0-only
+ *
+ * This file is part of HarTex.
+ *
+ * HarTex
+ * Copyright (c) 2021-2024 HarTex Project Developers
+ *
+ * HarTex is free software; you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation; either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * HarTex is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License along
+ * with HarTex. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! # Replay Command
+
+use std::env;
+
+use clap::ArgMatches;
+use hartex_discord_core::dotenvy;
+use hartex_kafka_utils::deadletter::original_headers;
+use hartex_kafka_utils::traits::ClientConfigUtils;
+use hartex_kafka_utils::types::CompressionType;
+use hartex_log::log;
+use miette::IntoDiagnostic;
+use miette::Report;
+use rdkafka::message::OwnedHeaders;
+use rdkafka::producer::FutureProducer;
+use rdkafka::producer::FutureRecord;
+use rdkafka::util::Timeout;
+use rdkafka::ClientConfig;
+use rdkafka::Message;
+
+use crate::deadletters;
+
+/// Replay records in the dead-letter topic.
+#[allow(clippy::module_name_repetitions)]
+pub async fn replay_command(matches: ArgMatches) -> miette::Result<()> {
+    log::trace!("loading environment variables");
+    dotenvy::dotenv().into_diagnostic()?;
+
+    let all = matches.get_flag("all");
+    let selected = matches
+        .get_many::<String>("records")
+        .unwrap_or_default()
+        .map(String::as_str)
+        .map(parse_record)
+        .collect::<miette::Result<Vec<_>>>()?;
+
+    let bootstrap_servers = env::var("KAFKA_BOOTSTRAP_SERVERS")
+        .into_diagnostic()?
+        .split(';')
+        .map(String::from)
+        .collect::<Vec<_>>();
+
+    let producer = ClientConfig::new()
+        .bootstrap_servers(bootstrap_servers.into_iter())
+        .compression_type(CompressionType::Lz4)
+        .delivery_timeout_ms(30000)
+        .create::<FutureProducer>()
+        .into_diagnostic()?;
+
+    log::trace!("reading dead-letter topic");
+    let records = deadletters::read_all()?;
+
+    for (partition, offset) in &selected {
+        if !records
+            .iter()
+            .any(|record| record.is_at(*partition, *offset))
+        {
+            log::warn!("record {partition}:{offset} is not in the dead-letter topic");
+        }
+    }
+
+    let mut replayed = 0;
+    for record in records.iter().filter(|record| {
+        all || selected
+            .iter()
+            .any(|(partition, offset)| record.is_at(*partition, *offset))
+    }) {
+        let headers = record
+            .message
+            .headers()
+            .map_or_else(OwnedHeaders::new, original_headers);
+
+        let mut kafka_record = FutureRecord::<[u8], [u8]>::to(&record.dead_letter.source_topic)
+            .payload(record.message.payload().unwrap_or_default())
+            .headers(headers);
+        if let Some(key) = record.message.key() {
+            kafka_record = kafka_record.key(key);
+        }
+
+        producer
+            .send(kafka_record, Timeout::Never)
+            .await
+            .map_err(|(error, _)| error)
+            .into_diagnostic()?;
+
+        log::info!(
+            "replayed record {}:{} to {}",
+            record.message.partition(),
+            record.message.offset(),
+            record.dead_letter.source_topic
+        );
+        replayed += 1;
+    }
+
+    // replayed records are kept in the dead-letter topic, as Kafka does not support deleting
+    // individual records
+    log::info!("replayed {replayed} dead letters; they remain in the dead-letter topic");
+
+    Ok(())
+}
+
+/// Parse a record in the form `partition:offset`.
+fn parse_record(record: &str) -> miette::Result<(i32, i64)> {
+    let invalid = || {
+        Report::msg(format!(
+            "invalid record: {record}; expected partition:offset"
+        ))
+    };
+
+    let (partition, offset) = record.split_once(':').ok_or_else(invalid)?;
+    let partition = partition.parse().map_err(|_| invalid())?;
+    let offset = offset.parse().map_err(|_| invalid())?;
+
+    Ok((partition, offset))
+}
@@ -0,0 +1,230 @@
+/*
+ * SPDX-License-Identifier: AGPL-3.0-only
+ *
+ * This file is part of HarTex.
+ *
+ * HarTex
+ * Copyright (c) 2021-2024 HarTex Project Developers
+ *
+ * HarTex is free software; you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation; either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * HarTex is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License along
+ * with HarTex. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! # Dead Letter Records
+//!
+//! Reading the records in the dead-letter topic.
+
+use std::collections::HashMap;
+use std::env;
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::time::Duration;
+
+use chrono::DateTime;
+use hartex_kafka_utils::deadletter::DeadLetter;
+use hartex_kafka_utils::envelope::InboundGatewayEnvelope;
+use hartex_kafka_utils::traits::ClientConfigUtils;
+use hartex_log::log;
+use miette::IntoDiagnostic;
+use miette::Report;
+use owo_colors::OwoColorize;
+use rdkafka::consumer::BaseConsumer;
+use rdkafka::consumer::Consumer;
+use rdkafka::message::OwnedMessage;
+use rdkafka::metadata::MetadataTopic;
+use rdkafka::ClientConfig;
+use rdkafka::Message;
+use rdkafka::Offset;
+use rdkafka::TopicPartitionList;
+
+/// The timeout of requests to the Kafka cluster.
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A record in the dead-letter topic.
+#[allow(clippy::module_name_repetitions)]
+pub struct DeadLetterRecord {
+    /// The record itself.
+    pub message: OwnedMessage,
+    /// The metadata of the dead letter.
+    pub dead_letter: DeadLetter,
+}
+
+impl DeadLetterRecord {
+    /// Whether this record is at a given partition and offset of the dead-letter topic.
+    #[must_use]
+    pub fn is_at(&self, partition: i32, offset: i64) -> bool {
+        self.message.partition() == partition && self.message.offset() == offset
+    }
+}
+
+impl Display for DeadLetterRecord {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f)?;
+        writeln!(
+            f,
+            "{}{}",
+            "Record: ".bold(),
+            format!("{}:{}", self.message.partition(), self.message.offset()).bright_cyan()
+        )?;
+        writeln!(
+            f,
+            "{}{} (partition {}, offset {})",
+            "Source: ".bold(),
+            self.dead_letter.source_topic.bright_cyan(),
+            self.dead_letter.source_partition,
+            self.dead_letter.source_offset
+        )?;
+        writeln!(
+            f,
+            "{}{}",
+            "Service: ".bold(),
+            self.dead_letter.service.bright_cyan()
+        )?;
+        writeln!(
+            f,
+            "{}{}",
+            "Stage: ".bold(),
+            self.dead_letter.stage.bright_cyan()
+        )?;
+
+        write!(f, "{}", "Failed At: ".bold())?;
+        match i64::try_from(self.dead_letter.failed_at)
+            .ok()
+            .and_then(DateTime::from_timestamp_millis)
+        {
+            Some(failed_at) => writeln!(f, "{}", failed_at.bright_cyan())?,
+            None => writeln!(f, "{}", "Unknown".truecolor(107, 107, 107))?,
+        }
+
+        // the envelope is only present on records that were published by the gateway
+        let envelope = self
+            .message
+            .headers()
+            .and_then(|headers| InboundGatewayEnvelope::from_headers(headers).ok());
+        write!(f, "{}", "Shard: ".bold())?;
+        match &envelope {
+            Some(envelope) => writeln!(
+                f,
+                "{}",
+                format!("{}/{}", envelope.shard_id, envelope.shard_count).bright_cyan()
+            )?,
+            None => writeln!(f, "{}", "None".truecolor(107, 107, 107))?,
+        }
+
+        write!(f, "{}", "Event Type: ".bold())?;
+        match envelope.and_then(|envelope| envelope.event_type) {
+            Some(event_type) => writeln!(f, "{}", event_type.bright_cyan())?,
+            None => writeln!(f, "{}", "None".truecolor(107, 107, 107))?,
+        }
+
+        writeln!(
+            f,
+            "{}{}",
+            "Error: ".bold(),
+            self.dead_letter.error.bright_red()
+        )
+    }
+}
+
+/// Read all the records currently in the dead-letter topic, in order of partition and offset.
+///
+/// Records whose dead letter metadata is missing or malformed are skipped.
+pub fn read_all() -> miette::Result<Vec<DeadLetterRecord>> {
+    let bootstrap_servers = env::var("KAFKA_BOOTSTRAP_SERVERS")
+        .into_diagnostic()?
+        .split(';')
+        .map(String::from)
+        .collect::<Vec<_>>();
+    let topic = env::var("KAFKA_TOPIC_DEAD_LETTER").into_diagnostic()?;
+
+    log::trace!("creating consumer");
+    let consumer = ClientConfig::new()
+        .bootstrap_servers(bootstrap_servers.into_iter())
+        .group_id("com.github.teamhartex.hartex.deadletter.manager")
+        .set("enable.auto.commit", "false")
+        .create::<BaseConsumer>()
+        .into_diagnostic()?;
+
+    log::trace!("fetching watermarks of dead-letter topic partitions");
+    let metadata = consumer
+        .fetch_metadata(Some(&topic), TIMEOUT)
+        .into_diagnostic()?;
+
+    let mut assignment = TopicPartitionList::new();
+    let mut high_watermarks = HashMap::new();
+    for partition in metadata.topics().iter().flat_map(MetadataTopic::partitions) {
+        let (low, high) = consumer
+            .fetch_watermarks(&topic, partition.id(), TIMEOUT)
+            .into_diagnostic()?;
+        if low >= high {
+            continue;
+        }
+
+        assignment
+            .add_partition_offset(&topic, partition.id(), Offset::Offset(low))
+            .into_diagnostic()?;
+        high_watermarks.insert(partition.id(), high);
+    }
+
+    if high_watermarks.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    consumer.assign(&assignment).into_diagnostic()?;
+
+    let mut records = Vec::new();
+    while !high_watermarks.is_empty() {
+        let Some(result) = consumer.poll(TIMEOUT) else {
+            return Err(Report::msg("timed out reading the dead-letter topic"));
+        };
+        let message = result.into_diagnostic()?.detach();
+
+        // stop reading a partition once its end at the time of assignment is reached
+        if high_watermarks
+            .get(&message.partition())
+            .is_some_and(|high| message.offset() + 1 >= *high)
+        {
+            high_watermarks.remove(&message.partition());
+        }
+
+        let Some(headers) = message.headers() else {
+            log::warn!(
+                "skipping record {}:{}: missing headers",
+                message.partition(),
+                message.offset()
+            );
+            continue;
+        };
+        let dead_letter = match DeadLetter::from_headers(headers) {
+            Ok(dead_letter) => dead_letter,
+            Err(error) => {
+                log::warn!(
+                    "skipping record {}:{}: {error}",
+                    message.partition(),
+                    message.offset()
+                );
+                continue;
+            }
+        };
+
+        records.push(DeadLetterRecord {
+            message,
+            dead_letter,
+        });
+    }
+
+    records.sort_unstable_by_key(|record| (record.message.partition(), record.message.offset()));
+
+    Ok(records)
+}
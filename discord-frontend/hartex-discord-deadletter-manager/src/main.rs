@@ -0,0 +1,96 @@
+/*
+ * SPDX-License-Identifier: AGPL-3.0-only
+ *
+ * This file is part of HarTex.
+ *
+ * HarTex
+ * Copyright (c) 2021-2024 HarTex Project Developers
+ *
+ * HarTex is free software; you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation; either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * HarTex is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License along
+ * with HarTex. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! # Dead Letter Manager Executable
+//!
+//! This is the executable for the dead letter manager, which inspects the records that failed to
+//! be processed and replays them to the topics they were originally published to.
+
+#![deny(clippy::pedantic)]
+#![deny(unsafe_code)]
+#![deny(warnings)]
+
+use clap::Arg;
+use clap::ArgAction;
+use clap::Command;
+use hartex_discord_core::tokio;
+
+mod cmdline;
+mod commands;
+mod deadletters;
+
+/// Manager entry point.
+#[tokio::main(flavor = "multi_thread")]
+pub async fn main() -> miette::Result<()> {
+    hartex_log::initialize();
+
+    let command = Command::new("dlqmgr")
+        .subcommand(
+            Command::new("inspect")
+                .about("Lists the records in the dead-letter topic.")
+                .arg(
+                    Arg::new("service")
+                        .long("service")
+                        .short('s')
+                        .action(ArgAction::Set),
+                )
+                .arg(
+                    Arg::new("stage")
+                        .long("stage")
+                        .short('t')
+                        .action(ArgAction::Set),
+                )
+                .arg(
+                    Arg::new("with-payloads")
+                        .long("with-payloads")
+                        .short('p')
+                        .num_args(0)
+                        .action(ArgAction::SetTrue),
+                ),
+        )
+        .subcommand(
+            Command::new("replay")
+                .about(
+                    "Republishes records in the dead-letter topic to the topics they were originally published to.",
+                )
+                .arg(
+                    Arg::new("records")
+                        .help("The records to replay, each in the form partition:offset.")
+                        .num_args(1..)
+                        .required_unless_present("all")
+                        .action(ArgAction::Append),
+                )
+                .arg(
+                    Arg::new("all")
+                        .long("all")
+                        .short('a')
+                        .num_args(0)
+                        .action(ArgAction::SetTrue),
+                ),
+        );
+
+    let matches = command.get_matches();
+
+    cmdline::handle(matches).await?;
+
+    Ok(())
+}
@@ -25,6 +25,7 @@
 //! An implementation of a cache updater for the guild create event.
 
 use hartex_discord_core::discord::model::gateway::payload::incoming::GuildCreate;
+use hartex_discord_core::discord::model::gateway::Intents;
 use hartex_discord_entitycache_core::error::CacheResult;
 use hartex_discord_entitycache_core::traits::Repository;
 use hartex_discord_entitycache_entities::emoji::EmojiEntity;
@@ -37,6 +38,10 @@ use hartex_discord_entitycache_repositories::role::CachedRoleRepository;
 use crate::CacheUpdater;
 
 impl CacheUpdater for GuildCreate {
+    fn intents() -> Intents {
+        Intents::GUILDS
+    }
+
     async fn update(&self) -> CacheResult<()> {
         let entity = GuildEntity::from(self.0.clone());
 
@@ -21,6 +21,7 @@
  */
 
 use hartex_discord_core::discord::model::gateway::payload::incoming::MemberChunk;
+use hartex_discord_core::discord::model::gateway::Intents;
 use hartex_discord_entitycache_core::error::CacheResult;
 use hartex_discord_entitycache_core::traits::Repository;
 use hartex_discord_entitycache_entities::member::MemberEntity;
@@ -31,6 +32,11 @@ use hartex_discord_entitycache_repositories::user::CachedUserRepository;
 use crate::CacheUpdater;
 
 impl CacheUpdater for MemberChunk {
+    fn intents() -> Intents {
+        // requesting every member of a guild requires the privileged guild members intent
+        Intents::GUILD_MEMBERS
+    }
+
     async fn update(&self) -> CacheResult<()> {
         for member in &self.members {
             let member_entity = MemberEntity::from((self.guild_id, member.user.id, member.clone()));
@@ -29,6 +29,9 @@
 #![deny(unsafe_code)]
 #![deny(warnings)]
 
+use hartex_discord_core::discord::model::gateway::payload::incoming::GuildCreate;
+use hartex_discord_core::discord::model::gateway::payload::incoming::MemberChunk;
+use hartex_discord_core::discord::model::gateway::Intents;
 use hartex_discord_entitycache_core::error::CacheResult;
 
 pub mod guild_create;
@@ -36,7 +39,22 @@ pub mod guild_member_chunk;
 
 /// A trait for all cache updaters to implement.
 pub trait CacheUpdater {
+    /// The gateway intents required to receive the event this cache updater handles.
+    fn intents() -> Intents
+    where
+        Self: Sized;
+
     /// Update the cache.
     #[allow(async_fn_in_trait)]
     async fn update(&self) -> CacheResult<()>;
 }
+
+/// Returns the gateway intents required by each cache updater, along with the name of the event
+/// it handles.
+#[must_use]
+pub fn intents() -> Vec<(&'static str, Intents)> {
+    vec![
+        ("GUILD_CREATE", GuildCreate::intents()),
+        ("GUILD_MEMBERS_CHUNK", MemberChunk::intents()),
+    ]
+}
@@ -22,8 +22,11 @@
 
 use std::pin::Pin;
 
+use hartex_database_queries::discord_frontend::queries::cached_emoji_select_by_guild_id::cached_emoji_select_by_guild_id;
 use hartex_database_queries::discord_frontend::queries::cached_emoji_select_by_id::cached_emoji_select_by_id;
 use hartex_database_queries::discord_frontend::queries::cached_emoji_upsert::cached_emoji_upsert;
+use hartex_discord_core::discord::model::id::marker::GuildMarker;
+use hartex_discord_core::discord::model::id::Id;
 use hartex_discord_entitycache_core::error::CacheResult;
 use hartex_discord_entitycache_core::traits::Entity;
 use hartex_discord_entitycache_core::traits::Repository;
@@ -34,6 +37,25 @@ use tokio_postgres::GenericClient;
 /// Repository for emoji entities.
 pub struct CachedEmojiRepository;
 
+impl CachedEmojiRepository {
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn emojis_in_guild(
+        &self,
+        guild_id: Id<GuildMarker>,
+    ) -> CacheResult<Vec<EmojiEntity>> {
+        let pinned = Pin::static_ref(&DATABASE_POOL).await;
+        let pooled = pinned.get().await?;
+        let client = pooled.client();
+
+        let emojis = cached_emoji_select_by_guild_id()
+            .bind(client, &guild_id.to_string())
+            .all()
+            .await?;
+
+        Ok(emojis.into_iter().map(EmojiEntity::from).collect())
+    }
+}
+
 impl Repository<EmojiEntity> for CachedEmojiRepository {
     async fn get(&self, id: <EmojiEntity as Entity>::Id) -> CacheResult<EmojiEntity> {
         let pinned = Pin::static_ref(&DATABASE_POOL).await;
@@ -33,23 +33,31 @@ use std::env;
 use std::io::Error;
 use std::io::ErrorKind;
 use std::str;
-use std::str::Utf8Error;
+use std::sync::Arc;
 
 use futures_util::StreamExt;
 use hartex_discord_core::discord::model::gateway::event::GatewayEventDeserializer;
 use hartex_discord_core::dotenvy;
+use hartex_discord_core::shutdown::ShutdownController;
+use hartex_discord_core::shutdown::DEFAULT_DRAIN_TIMEOUT;
 use hartex_discord_core::tokio;
-use hartex_discord_core::tokio::signal;
+use hartex_kafka_utils::deadletter::DeadLetterProducer;
+use hartex_kafka_utils::deadletter::DeadLetterStage;
+use hartex_kafka_utils::envelope::InboundGatewayEnvelope;
+use hartex_kafka_utils::offsets::OffsetCommitter;
+use hartex_kafka_utils::offsets::OffsetCommitterContext;
 use hartex_kafka_utils::traits::ClientConfigUtils;
+use hartex_kafka_utils::types::CompressionType;
 use hartex_log::log;
 use miette::IntoDiagnostic;
 use rdkafka::consumer::Consumer;
 use rdkafka::consumer::StreamConsumer;
 use rdkafka::error::KafkaError;
+use rdkafka::message::BorrowedMessage;
+use rdkafka::producer::FutureProducer;
 use rdkafka::ClientConfig;
 use rdkafka::Message;
 use serde::de::DeserializeSeed;
-use serde_scan::scan;
 
 mod entitycache;
 
@@ -68,15 +76,51 @@ pub async fn main() -> miette::Result<()> {
         .collect::<Vec<_>>();
     let topic = env::var("KAFKA_TOPIC_INBOUND_DISCORD_GATEWAY_PAYLOAD_CACHE").into_diagnostic()?;
 
+    let producer = ClientConfig::new()
+        .bootstrap_servers(bootstrap_servers.clone().into_iter())
+        .compression_type(CompressionType::Lz4)
+        .delivery_timeout_ms(30000)
+        .create::<FutureProducer>()
+        .into_diagnostic()?;
     let consumer = ClientConfig::new()
         .bootstrap_servers(bootstrap_servers.into_iter())
         .group_id("com.github.teamhartex.hartex.inbound.gateway.payload.consumer")
-        .create::<StreamConsumer>()
+        .enable_auto_commit(false)
+        .enable_auto_offset_store(false)
+        .create_with_context::<_, StreamConsumer<OffsetCommitterContext>>(
+            OffsetCommitterContext::default(),
+        )
         .into_diagnostic()?;
+    let consumer = Arc::new(consumer);
+
+    // offsets are committed once events are processed, such that events being processed when the
+    // service stops are consumed again when it restarts; updating the cache is idempotent, so
+    // consuming an event again is harmless
+    let committer = OffsetCommitter::new(consumer.clone());
+
+    let shutdown = ShutdownController::new();
+    shutdown.listen_for_signals();
+
+    let dead_letters = DeadLetterProducer::new(
+        producer,
+        env::var("KAFKA_TOPIC_DEAD_LETTER").into_diagnostic()?,
+        "entitycache",
+        shutdown.clone(),
+    );
 
     consumer.subscribe(&[&topic]).into_diagnostic()?;
 
-    while let Some(result) = consumer.stream().next().await {
+    // events are processed one at a time, so no event is in flight once the loop is left
+    let mut stream = consumer.stream();
+    loop {
+        let result = tokio::select! {
+            () = shutdown.requested() => break,
+            result = stream.next() => result,
+        };
+        let Some(result) = result else {
+            break;
+        };
+
         let Ok(message) = result else {
             let error = result.unwrap_err();
             println!("{:?}", Err::<(), KafkaError>(error).into_diagnostic());
@@ -84,57 +128,110 @@ pub async fn main() -> miette::Result<()> {
             continue;
         };
 
+        committer.track(&message);
+
         let bytes = message.payload().unwrap();
 
         let (gateway_deserializer, mut json_deserializer) = {
             let result = str::from_utf8(bytes);
             if let Err(error) = result {
-                println!("{:?}", Err::<(), Utf8Error>(error).into_diagnostic());
+                dead_letters
+                    .dead_letter(&committer, &message, DeadLetterStage::Decode, error)
+                    .await;
 
                 continue;
             }
 
-            let result = GatewayEventDeserializer::from_json(result.unwrap())
-                .ok_or(Error::new(ErrorKind::Other, ""))
-                .into_diagnostic()?;
+            let Some(result) = GatewayEventDeserializer::from_json(result.unwrap()) else {
+                let error = Error::new(ErrorKind::InvalidData, "invalid gateway payload");
+                dead_letters
+                    .dead_letter(&committer, &message, DeadLetterStage::Decode, error)
+                    .await;
+
+                continue;
+            };
 
             let json_deserializer = serde_json::Deserializer::from_slice(bytes);
 
             (result, json_deserializer)
         };
 
-        let key_bytes = message.key().unwrap();
-        let result = str::from_utf8(key_bytes);
-        if let Err(error) = result {
-            println!("{:?}", Err::<(), Utf8Error>(error).into_diagnostic());
+        let Some(headers) = message.headers() else {
+            let error = Error::new(ErrorKind::InvalidData, "payload without headers");
+            dead_letters
+                .dead_letter(&committer, &message, DeadLetterStage::Envelope, error)
+                .await;
 
             continue;
-        }
+        };
+        let envelope = match InboundGatewayEnvelope::from_headers(headers) {
+            Ok(envelope) => envelope,
+            Err(error) => {
+                dead_letters
+                    .dead_letter(&committer, &message, DeadLetterStage::Envelope, error)
+                    .await;
 
-        let key = result.unwrap();
-        let scanned: u8 = scan!("INBOUND_GATEWAY_PAYLOAD_SHARD_{}" <- key).into_diagnostic()?;
+                continue;
+            }
+        };
 
         log::trace!(
-            "[shard {scanned}] received {} event; attempting to deserialize",
-            gateway_deserializer.event_type().unwrap_or("UNKNOWN")
+            "[shard {}/{}] received {} event; attempting to deserialize",
+            envelope.shard_id,
+            envelope.shard_count,
+            envelope.event_type.as_deref().unwrap_or("UNKNOWN")
         );
         let result = gateway_deserializer.deserialize(&mut json_deserializer);
         if let Err(error) = result {
-            println!(
-                "{:?}",
-                Err::<(), serde_json::Error>(error).into_diagnostic()
-            );
+            dead_letters
+                .dead_letter(&committer, &message, DeadLetterStage::Deserialize, error)
+                .await;
 
             continue;
         }
 
         let event = result.unwrap();
 
-        entitycache::update(event).await?;
+        // a failing update only affects the entities of the event, so the service keeps running
+        if let Err(report) = entitycache::update(event).await {
+            println!("{report:?}");
+
+            // the event is left unprocessed if it cannot be published, such that it is consumed
+            // again after a restart
+            if let Err(error) = dead_letters
+                .publish(&message, DeadLetterStage::Handle, report)
+                .await
+            {
+                println!("{:?}", Err::<(), KafkaError>(error).into_diagnostic());
+
+                continue;
+            }
+        }
+
+        complete(&committer, &message);
     }
 
-    signal::ctrl_c().await.into_diagnostic()?;
-    log::warn!("ctrl-c signal received, shutting down");
+    log::warn!("shutting down");
+
+    log::trace!("committing offsets");
+    if let Err(error) = committer.commit_all() {
+        println!("{:?}", Err::<(), KafkaError>(error).into_diagnostic());
+    }
+
+    log::trace!("waiting for database connections to be returned");
+    if !hartex_discord_utils::drain_database_pool(DEFAULT_DRAIN_TIMEOUT).await {
+        log::warn!("database connections are still in use");
+    }
 
     Ok(())
 }
+
+/// Mark a record as processed, committing its offset.
+fn complete(
+    committer: &OffsetCommitter<StreamConsumer<OffsetCommitterContext>>,
+    message: &BorrowedMessage<'_>,
+) {
+    if let Err(error) = committer.complete(message) {
+        println!("{:?}", Err::<(), KafkaError>(error).into_diagnostic());
+    }
+}
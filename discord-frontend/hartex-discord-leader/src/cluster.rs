@@ -0,0 +1,78 @@
+/*
+ * SPDX-License-Identifier: AGPL-3.0-only
+ *
+ * This file is part of HarTex.
+ *
+ * HarTex
+ * Copyright (c) 2021-2024 HarTex Project Developers
+ *
+ * HarTex is free software; you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation; either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * HarTex is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License along
+ * with HarTex. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::env;
+use std::ops::Range;
+
+use miette::IntoDiagnostic;
+use miette::Report;
+
+/// A cluster of shards run by a single leader process.
+///
+/// Running several leader processes, each with its own cluster, allows spreading the gateway load
+/// across several containers.
+#[derive(Clone, Debug)]
+pub struct Cluster {
+    /// The identifier of the cluster.
+    pub id: u32,
+    /// The range of shard numbers run by the cluster.
+    pub shards: Range<u32>,
+    /// The total number of shards across all clusters.
+    pub total: u32,
+}
+
+/// Obtain the cluster this leader process runs.
+///
+/// Returns `None` if the leader is not running in cluster mode, which is the case when the
+/// `CLUSTER_ID` environment variable is not set. Otherwise, the `CLUSTER_SHARD_START`,
+/// `CLUSTER_SHARD_END` (exclusive) and `CLUSTER_SHARD_TOTAL` environment variables are required.
+pub fn obtain() -> miette::Result<Option<Cluster>> {
+    let Ok(id) = env::var("CLUSTER_ID") else {
+        return Ok(None);
+    };
+
+    let id = id.parse::<u32>().into_diagnostic()?;
+    let start = env::var("CLUSTER_SHARD_START")
+        .into_diagnostic()?
+        .parse::<u32>()
+        .into_diagnostic()?;
+    let end = env::var("CLUSTER_SHARD_END")
+        .into_diagnostic()?
+        .parse::<u32>()
+        .into_diagnostic()?;
+    let total = env::var("CLUSTER_SHARD_TOTAL")
+        .into_diagnostic()?
+        .parse::<u32>()
+        .into_diagnostic()?;
+
+    if start >= end || end > total {
+        return Err(Report::msg(format!(
+            "invalid shard range {start}..{end} for cluster {id} with {total} shards in total"
+        )));
+    }
+
+    Ok(Some(Cluster {
+        id,
+        shards: start..end,
+        total,
+    }))
+}
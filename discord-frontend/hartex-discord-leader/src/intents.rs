@@ -0,0 +1,49 @@
+/*
+ * SPDX-License-Identifier: AGPL-3.0-only
+ *
+ * This file is part of HarTex.
+ *
+ * HarTex
+ * Copyright (c) 2021-2024 HarTex Project Developers
+ *
+ * HarTex is free software; you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation; either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * HarTex is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License along
+ * with HarTex. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use hartex_discord_commands_core::traits::Plugin;
+use hartex_discord_commands_core::traits::PluginMetadata;
+use hartex_discord_core::discord::gateway::Intents;
+use hartex_log::log;
+
+/// Obtain the gateway intents to request, which is the union of the intents required by every
+/// plugin and every cache updater.
+pub fn obtain() -> Intents {
+    let mut intents = Intents::empty();
+
+    for plugin in hartex_discord_commands::plugins() {
+        let required = plugin.intents();
+        log::info!("plugin {} requires intents {required:?}", plugin.name());
+
+        intents |= required;
+    }
+
+    for (event, required) in hartex_discord_entitycache_cacheupdaters::intents() {
+        log::info!("cache updater for {event} requires intents {required:?}");
+
+        intents |= required;
+    }
+
+    log::info!("requesting intents {intents:?}");
+
+    intents
+}
@@ -22,105 +22,158 @@
 
 use std::env;
 use std::str;
-use std::sync::Arc;
 use std::time::Duration;
 
 use futures_util::StreamExt as FutureStreamExt;
 use hartex_discord_core::discord::gateway::queue::Queue;
+use hartex_discord_core::discord::gateway::CloseFrame;
 use hartex_discord_core::discord::gateway::Message as GatewayMessage;
 use hartex_discord_core::discord::gateway::MessageSender;
 use hartex_discord_core::discord::gateway::Shard;
-use hartex_discord_core::discord::model::gateway::payload::outgoing::RequestGuildMembers;
+use hartex_discord_core::discord::gateway::ShardId;
+use hartex_discord_core::discord::model::gateway::event::GatewayEventDeserializer;
+use hartex_discord_core::discord::model::gateway::payload::outgoing::UpdatePresence;
+use hartex_discord_core::discord::model::gateway::OpCode;
 use hartex_discord_core::tokio;
+use hartex_discord_core::tokio::sync::broadcast;
+use hartex_discord_core::tokio::sync::broadcast::error::RecvError;
+use hartex_kafka_utils::envelope::InboundGatewayEnvelope;
+use hartex_kafka_utils::keys;
+use hartex_kafka_utils::lifecycle::ShardDisconnectKind;
+use hartex_kafka_utils::outbound::OutboundCommand;
+use hartex_kafka_utils::outbound::OutboundEnvelope;
 use hartex_log::log;
 use miette::IntoDiagnostic;
+use miette::Report;
 use rdkafka::consumer::StreamConsumer;
 use rdkafka::error::KafkaError;
 use rdkafka::producer::FutureProducer;
 use rdkafka::producer::FutureRecord;
 use rdkafka::util::Timeout;
 use rdkafka::Message;
-use serde_scan::scan;
+
+use crate::lifecycle;
+use crate::presence;
+use crate::presence::PresenceTemplate;
+use crate::routing::Route;
+use crate::shardset::ShardContext;
 
 /// Handle inbound AND outbound messages for a given shard.
 pub async fn handle<'a, Q>(
     shard: &mut Shard<Q>,
     producer: FutureProducer,
-    consumer: Arc<StreamConsumer>,
+    rx: broadcast::Receiver<OutboundMessage>,
+    context: ShardContext,
 ) -> miette::Result<()>
 where
     Q: Queue + Send + Sync + Sized + Unpin + 'static,
 {
-    let shard_id = shard.id().number();
+    let shard_id = shard.id();
     let sender = shard.sender();
     tokio::select! {
-        _ = inbound(shard, producer) => {},
-        _ = outbound((shard_id, sender), consumer) => {}
+        result = inbound(shard, producer, context) => result?,
+        _ = outbound((shard_id, sender), rx) => {}
     }
 
     Ok(())
 }
 
 /// Handle inbound traffic.
-#[allow(clippy::match_wildcard_for_single_variants)]
-async fn inbound<Q>(shard: &mut Shard<Q>, producer: FutureProducer) -> miette::Result<()>
+///
+/// Payloads are only forwarded while the shard set of the shard is active. Returns an error if
+/// the gateway connection of the shard was fatally closed.
+async fn inbound<Q>(
+    shard: &mut Shard<Q>,
+    producer: FutureProducer,
+    context: ShardContext,
+) -> miette::Result<()>
 where
     Q: Queue + Send + Sync + Sized + Unpin + 'static,
 {
-    let topic = env::var("KAFKA_TOPIC_INBOUND_DISCORD_GATEWAY_PAYLOAD").into_diagnostic()?;
-    let topic_2 =
-        env::var("KAFKA_TOPIC_INBOUND_DISCORD_GATEWAY_PAYLOAD_CACHE").into_diagnostic()?;
+    let routes = Route::ALL
+        .into_iter()
+        .map(|route| Ok((route, route.topic()?)))
+        .collect::<miette::Result<Vec<_>>>()?;
+    let lifecycle_topic =
+        env::var("KAFKA_TOPIC_INBOUND_DISCORD_GATEWAY_LIFECYCLE").into_diagnostic()?;
+    let key = keys::shard_key(
+        keys::INBOUND_GATEWAY_PAYLOAD,
+        shard.id().number(),
+        shard.id().total(),
+    );
 
     while let Some(result) = shard.next().await {
         match result {
             Ok(message) => {
-                let Some(bytes) = (match message {
-                    // todo: handle close frame
-                    GatewayMessage::Text(string) => Some(string.into_bytes()),
-                    _ => None,
-                }) else {
-                    continue;
+                let (bytes, envelope) = match message {
+                    GatewayMessage::Text(string) => {
+                        let deserializer = GatewayEventDeserializer::from_json(&string);
+                        let event_type = deserializer
+                            .as_ref()
+                            .and_then(GatewayEventDeserializer::event_type);
+                        if matches!(event_type, Some("READY" | "RESUMED")) {
+                            // the shard set may no longer be waiting for its shards
+                            let _ = context.ready.send(shard.id().number());
+                        }
+
+                        // the latency of the shard is recorded when the heartbeat is acknowledged
+                        let op = deserializer.as_ref().map(GatewayEventDeserializer::op);
+                        if op == Some(OpCode::HeartbeatAck as u8) && *context.active.borrow() {
+                            if let Err(error) = lifecycle::publish_heartbeat(
+                                &producer,
+                                &lifecycle_topic,
+                                shard.id(),
+                                shard.latency(),
+                            ) {
+                                println!("{error:?}");
+                            }
+                        }
+
+                        let envelope = InboundGatewayEnvelope::new(
+                            shard.id().number(),
+                            shard.id().total(),
+                            event_type,
+                            deserializer
+                                .as_ref()
+                                .and_then(GatewayEventDeserializer::sequence),
+                        );
+
+                        (string.into_bytes(), envelope)
+                    }
+                    GatewayMessage::Close(frame) => {
+                        handle_close(shard.id(), frame.as_ref(), &producer).await?;
+
+                        continue;
+                    }
                 };
 
+                if !*context.active.borrow() {
+                    continue;
+                }
+
                 log::trace!(
                     "[shard {shard_id}] received binary payload from gateway",
                     shard_id = shard.id().number()
                 );
 
-                // send payload to worker process
-                if let Err((error, _)) = producer
-                    .send(
-                        FutureRecord::to(&topic)
-                            .key(&format!(
-                                "INBOUND_GATEWAY_PAYLOAD_SHARD_{shard_id}",
-                                shard_id = shard.id().number()
-                            ))
-                            .payload(&bytes),
-                        Timeout::After(Duration::from_secs(0)),
-                    )
-                    .await
-                {
-                    println!("{:?}", Err::<(), KafkaError>(error).into_diagnostic());
-
-                    continue;
-                }
+                // send payload to the worker process and the caching process, if they consume it
+                for (route, topic) in &routes {
+                    if !route.accepts(envelope.event_type.as_deref()) {
+                        continue;
+                    }
 
-                // send payloaad to caching process
-                if let Err((error, _)) = producer
-                    .send(
-                        FutureRecord::to(&topic_2)
-                            .key(&format!(
-                                "INBOUND_GATEWAY_PAYLOAD_SHARD_{shard_id}",
-                                shard_id = shard.id().number()
-                            ))
-                            .payload(&bytes),
-                        Timeout::After(Duration::from_secs(0)),
-                    )
-                    .await
-                {
-                    println!("{:?}", Err::<(), KafkaError>(error).into_diagnostic());
-
-                    continue;
+                    if let Err((error, _)) = producer
+                        .send(
+                            FutureRecord::to(topic)
+                                .key(&key)
+                                .payload(&bytes)
+                                .headers(envelope.to_headers()),
+                            Timeout::After(Duration::from_secs(0)),
+                        )
+                        .await
+                    {
+                        println!("{:?}", Err::<(), KafkaError>(error).into_diagnostic());
+                    }
                 }
             }
             Err(error) => {
@@ -135,11 +188,64 @@ where
     Ok(())
 }
 
-/// Handle outbound traffic.
-async fn outbound(
-    (shard_id, sender): (u32, MessageSender),
-    consumer: Arc<StreamConsumer>,
+/// Handle a close frame received from the gateway.
+///
+/// Resumable and reconnectable closes are recovered from by the shard itself; fatal closes are
+/// returned as an error as the shard cannot recover from them without intervention.
+async fn handle_close(
+    shard_id: ShardId,
+    frame: Option<&CloseFrame<'_>>,
+    producer: &FutureProducer,
 ) -> miette::Result<()> {
+    let disconnect_kind = lifecycle::classify(frame);
+    let code = frame.map_or(String::from("none"), |frame| frame.code.to_string());
+    let reason = frame.map_or("", |frame| frame.reason.as_ref());
+
+    match disconnect_kind {
+        ShardDisconnectKind::Resumable => log::info!(
+            "[shard {shard_id}] gateway connection closed (code {code}: {reason}); resuming session",
+            shard_id = shard_id.number()
+        ),
+        ShardDisconnectKind::Reconnect => log::warn!(
+            "[shard {shard_id}] gateway connection closed (code {code}: {reason}); session invalidated, reconnecting",
+            shard_id = shard_id.number()
+        ),
+        ShardDisconnectKind::Fatal => log::error!(
+            "[shard {shard_id}] gateway connection fatally closed (code {code}: {reason})",
+            shard_id = shard_id.number()
+        ),
+    }
+
+    if let Err(error) =
+        lifecycle::publish_disconnect(producer, shard_id, frame, disconnect_kind).await
+    {
+        println!("{error:?}");
+    }
+
+    if disconnect_kind == ShardDisconnectKind::Fatal {
+        return Err(Report::msg(format!(
+            "shard {shard_id} cannot reconnect to the gateway: close code {code}",
+            shard_id = shard_id.number()
+        )));
+    }
+
+    Ok(())
+}
+
+/// A message consumed from the outbound topic, to be handled by the shards.
+#[derive(Clone, Debug)]
+pub enum OutboundMessage {
+    /// A gateway command, to be sent by the shards it targets.
+    Command(OutboundEnvelope),
+    /// The presence template was updated, every shard is to update its presence accordingly.
+    PresenceUpdated,
+}
+
+/// Dispatch outbound messages to every shard.
+///
+/// Every message consumed is broadcasted to every shard, such that messages that target every
+/// shard are handled by each of them, rather than by whichever shard consumes it first.
+pub async fn dispatch(consumer: StreamConsumer, tx: broadcast::Sender<OutboundMessage>) {
     while let Some(result) = consumer.stream().next().await {
         let Ok(message) = result else {
             let error = result.unwrap_err();
@@ -148,22 +254,106 @@ async fn outbound(
             continue;
         };
 
-        let key = str::from_utf8(message.key().unwrap()).unwrap();
+        let (Some(Ok(key)), Some(payload)) = (message.key().map(str::from_utf8), message.payload())
+        else {
+            log::warn!("skipping outbound message without a valid key or payload");
+
+            continue;
+        };
+
+        let outbound = match key {
+            keys::OUTBOUND_GATEWAY_COMMAND => {
+                match serde_json::from_slice::<OutboundEnvelope>(payload) {
+                    Ok(envelope) => OutboundMessage::Command(envelope),
+                    Err(error) => {
+                        println!(
+                            "{:?}",
+                            Err::<(), serde_json::Error>(error).into_diagnostic()
+                        );
 
-        if key.contains("REQUEST_GUILD_MEMBERS") {
-            let bytes = message.payload().unwrap();
+                        continue;
+                    }
+                }
+            }
+            keys::OUTBOUND_UPDATE_PRESENCE => {
+                let template = match serde_json::from_slice::<PresenceTemplate>(payload) {
+                    Ok(template) => template,
+                    Err(error) => {
+                        println!(
+                            "{:?}",
+                            Err::<(), serde_json::Error>(error).into_diagnostic()
+                        );
 
-            let command = serde_json::from_slice::<RequestGuildMembers>(bytes).into_diagnostic()?;
-            let scanned: u32 =
-                scan!("OUTBOUND_REQUEST_GUILD_MEMBERS_{}" <- key).into_diagnostic()?;
+                        continue;
+                    }
+                };
+
+                // the presence is updated before any shard handles the message
+                if let Err(error) = presence::update(template).await {
+                    println!("{error:?}");
+
+                    continue;
+                }
+
+                OutboundMessage::PresenceUpdated
+            }
+            _ => {
+                log::warn!("skipping outbound message with unknown key {key}");
 
-            if shard_id != scanned {
                 continue;
             }
+        };
 
-            sender.command(&command).into_diagnostic()?;
-        }
+        // there may be no shards to receive the message while resharding
+        let _ = tx.send(outbound);
     }
+}
 
-    Ok(())
+/// Handle outbound traffic.
+async fn outbound(
+    (shard_id, sender): (ShardId, MessageSender),
+    mut rx: broadcast::Receiver<OutboundMessage>,
+) {
+    loop {
+        let message = match rx.recv().await {
+            Ok(message) => message,
+            Err(RecvError::Lagged(skipped)) => {
+                log::warn!(
+                    "[shard {shard_id}] skipped {skipped} outbound message(s)",
+                    shard_id = shard_id.number()
+                );
+
+                continue;
+            }
+            Err(RecvError::Closed) => break,
+        };
+
+        let result = match message {
+            OutboundMessage::Command(envelope) => {
+                // with two shard sets coexisting while resharding, a shard number alone is
+                // ambiguous
+                if !envelope.target.matches(shard_id.number(), shard_id.total()) {
+                    continue;
+                }
+
+                match envelope.command {
+                    OutboundCommand::RequestGuildMembers(command) => sender.command(&command),
+                    OutboundCommand::UpdatePresence(command) => sender.command(&command),
+                    OutboundCommand::UpdateVoiceState(command) => sender.command(&command),
+                    OutboundCommand::Raw(json) => sender.send(json),
+                }
+            }
+            OutboundMessage::PresenceUpdated => sender.command(&UpdatePresence {
+                d: presence::render(shard_id),
+                op: OpCode::PresenceUpdate,
+            }),
+        };
+
+        if let Err(error) = result {
+            log::warn!(
+                "[shard {shard_id}] failed to send gateway command: {error}",
+                shard_id = shard_id.number()
+            );
+        }
+    }
 }
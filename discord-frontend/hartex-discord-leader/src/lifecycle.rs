@@ -0,0 +1,191 @@
+/*
+ * SPDX-License-Identifier: AGPL-3.0-only
+ *
+ * This file is part of HarTex.
+ *
+ * HarTex
+ * Copyright (c) 2021-2024 HarTex Project Developers
+ *
+ * HarTex is free software; you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation; either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * HarTex is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License along
+ * with HarTex. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::env;
+use std::time::Duration;
+
+use hartex_discord_core::discord::gateway::CloseFrame;
+use hartex_discord_core::discord::gateway::Latency;
+use hartex_discord_core::discord::gateway::ShardId;
+use hartex_discord_core::discord::model::gateway::CloseCode;
+use hartex_kafka_utils::keys;
+use hartex_kafka_utils::lifecycle::ShardDisconnectKind;
+use hartex_kafka_utils::lifecycle::ShardLifecycleEvent;
+use hartex_kafka_utils::lifecycle::ShardLifecycleEventKind;
+use miette::IntoDiagnostic;
+use rdkafka::error::KafkaError;
+use rdkafka::producer::FutureProducer;
+use rdkafka::producer::FutureRecord;
+use rdkafka::util::Timeout;
+
+/// Classify a close frame received from the gateway by how the shard recovers from it.
+///
+/// Close frames without a close code, or with a close code not specific to the gateway, are
+/// treated as resumable, as the shard keeps its session in those cases.
+pub fn classify(frame: Option<&CloseFrame<'_>>) -> ShardDisconnectKind {
+    let Some(Ok(code)) = frame.map(|frame| CloseCode::try_from(frame.code)) else {
+        return ShardDisconnectKind::Resumable;
+    };
+
+    match code {
+        _ if !code.can_reconnect() => ShardDisconnectKind::Fatal,
+        CloseCode::InvalidSequence | CloseCode::SessionTimedOut => ShardDisconnectKind::Reconnect,
+        _ => ShardDisconnectKind::Resumable,
+    }
+}
+
+/// Publish a disconnect lifecycle event of a shard.
+pub async fn publish_disconnect(
+    producer: &FutureProducer,
+    shard_id: ShardId,
+    frame: Option<&CloseFrame<'_>>,
+    disconnect_kind: ShardDisconnectKind,
+) -> miette::Result<()> {
+    publish(
+        producer,
+        shard_id,
+        ShardLifecycleEventKind::Disconnected {
+            close_code: frame.map(|frame| frame.code),
+            reason: frame.map(|frame| frame.reason.to_string()),
+            disconnect_kind,
+        },
+    )
+    .await
+}
+
+/// Publish a heartbeat acknowledged lifecycle event of a shard to a topic, with the latency of
+/// the shard.
+///
+/// The event is only enqueued to be delivered, such that the shard does not wait for its delivery
+/// before receiving further messages.
+#[allow(clippy::cast_possible_truncation)]
+pub fn publish_heartbeat(
+    producer: &FutureProducer,
+    topic: &str,
+    shard_id: ShardId,
+    latency: &Latency,
+) -> miette::Result<()> {
+    let event = ShardLifecycleEvent {
+        shard_id: shard_id.number(),
+        shard_count: shard_id.total(),
+        kind: ShardLifecycleEventKind::HeartbeatAcknowledged {
+            average_latency: latency.average().map(|average| average.as_millis() as u64),
+            latest_latency: latency
+                .recent()
+                .first()
+                .map(|latest| latest.as_millis() as u64),
+        },
+    };
+    let bytes = serde_json::to_vec(&event).into_diagnostic()?;
+
+    if let Err((error, _)) = producer.send_result(
+        FutureRecord::to(topic)
+            .key(&keys::shard_key(
+                keys::INBOUND_GATEWAY_LIFECYCLE,
+                shard_id.number(),
+                shard_id.total(),
+            ))
+            .payload(&bytes),
+    ) {
+        return Err::<(), KafkaError>(error).into_diagnostic();
+    }
+
+    Ok(())
+}
+
+/// Publish a lifecycle event of a shard.
+async fn publish(
+    producer: &FutureProducer,
+    shard_id: ShardId,
+    kind: ShardLifecycleEventKind,
+) -> miette::Result<()> {
+    let topic = env::var("KAFKA_TOPIC_INBOUND_DISCORD_GATEWAY_LIFECYCLE").into_diagnostic()?;
+
+    let event = ShardLifecycleEvent {
+        shard_id: shard_id.number(),
+        shard_count: shard_id.total(),
+        kind,
+    };
+    let bytes = serde_json::to_vec(&event).into_diagnostic()?;
+
+    if let Err((error, _)) = producer
+        .send(
+            FutureRecord::to(&topic)
+                .key(&keys::shard_key(
+                    keys::INBOUND_GATEWAY_LIFECYCLE,
+                    shard_id.number(),
+                    shard_id.total(),
+                ))
+                .payload(&bytes),
+            Timeout::After(Duration::from_secs(0)),
+        )
+        .await
+    {
+        return Err::<(), KafkaError>(error).into_diagnostic();
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use hartex_discord_core::discord::gateway::CloseFrame;
+    use hartex_kafka_utils::lifecycle::ShardDisconnectKind;
+
+    use super::classify;
+
+    #[test]
+    fn missing_close_frame_is_resumable() {
+        assert_eq!(classify(None), ShardDisconnectKind::Resumable);
+    }
+
+    #[test]
+    fn close_codes_are_classified() {
+        let cases = [
+            // not specific to the gateway
+            (1000, ShardDisconnectKind::Resumable),
+            (1006, ShardDisconnectKind::Resumable),
+            // reconnectable gateway close codes
+            (4000, ShardDisconnectKind::Resumable),
+            (4001, ShardDisconnectKind::Resumable),
+            (4008, ShardDisconnectKind::Resumable),
+            // gateway close codes invalidating the session
+            (4007, ShardDisconnectKind::Reconnect),
+            (4009, ShardDisconnectKind::Reconnect),
+            // gateway close codes requiring intervention
+            (4004, ShardDisconnectKind::Fatal),
+            (4010, ShardDisconnectKind::Fatal),
+            (4011, ShardDisconnectKind::Fatal),
+            (4012, ShardDisconnectKind::Fatal),
+            (4013, ShardDisconnectKind::Fatal),
+            (4014, ShardDisconnectKind::Fatal),
+        ];
+
+        for (code, kind) in cases {
+            assert_eq!(
+                classify(Some(&CloseFrame::new(code, ""))),
+                kind,
+                "close code {code}"
+            );
+        }
+    }
+}
@@ -30,14 +30,14 @@
 #![deny(warnings)]
 
 use std::env;
-use std::sync::Arc;
 
-use hartex_discord_core::discord::gateway::CloseFrame;
 use hartex_discord_core::dotenvy;
+use hartex_discord_core::shutdown::ShutdownController;
+use hartex_discord_core::shutdown::DEFAULT_DRAIN_TIMEOUT;
 use hartex_discord_core::tokio;
-use hartex_discord_core::tokio::signal;
-use hartex_discord_core::tokio::sync::watch;
-use hartex_discord_core::tokio::task::JoinSet;
+use hartex_discord_core::tokio::sync::broadcast;
+use hartex_discord_core::tokio::task::JoinError;
+use hartex_discord_core::tokio::task::JoinHandle;
 use hartex_discord_utils::CLIENT;
 use hartex_discord_utils::TOKEN;
 use hartex_kafka_utils::traits::ClientConfigUtils;
@@ -50,9 +50,20 @@ use rdkafka::consumer::StreamConsumer;
 use rdkafka::producer::FutureProducer;
 use rdkafka::ClientConfig;
 
+use crate::shardset::ShardSet;
+use crate::shardset::ShutdownKind;
+
+mod cluster;
+mod intents;
 mod kafka;
+mod lifecycle;
+mod presence;
 mod queue;
+mod resharding;
+mod routing;
+mod sessions;
 mod shards;
+mod shardset;
 
 /// Entry point.
 #[tokio::main(flavor = "multi_thread")]
@@ -78,47 +89,102 @@ pub async fn main() -> miette::Result<()> {
         .delivery_timeout_ms(30000)
         .create::<FutureProducer>()
         .into_diagnostic()?;
-    let consumer = Arc::new(
-        ClientConfig::new()
-            .bootstrap_servers(bootstrap_servers.into_iter())
-            .group_id("com.github.teamhartex.hartex.inbound.gateway.command.consumer")
-            .create::<StreamConsumer>()
-            .into_diagnostic()?,
-    );
+
+    // every leader process consumes every outbound message, as messages may target every shard
+    let cluster = cluster::obtain()?;
+    let group_id = match &cluster {
+        Some(cluster) => format!(
+            "com.github.teamhartex.hartex.inbound.gateway.command.consumer.cluster{}",
+            cluster.id
+        ),
+        None => String::from("com.github.teamhartex.hartex.inbound.gateway.command.consumer"),
+    };
+    let consumer = ClientConfig::new()
+        .bootstrap_servers(bootstrap_servers.into_iter())
+        .group_id(&group_id)
+        .create::<StreamConsumer>()
+        .into_diagnostic()?;
 
     consumer.subscribe(&[&topic]).into_diagnostic()?;
 
+    let (outbound_tx, _) = broadcast::channel(1024);
+    tokio::spawn(kafka::dispatch(consumer, outbound_tx.clone()));
+
+    tokio::spawn(routing::report());
+
+    log::trace!("initializing presence");
+    presence::initialize().await?;
+    tokio::spawn(presence::refresh(outbound_tx.clone()));
+
     log::trace!("building clusters");
     let queue = queue::obtain()?;
-    let shards = shards::obtain(queue).await?;
-
-    let (tx, rx) = watch::channel(false);
-
-    log::trace!("launching {} shard(s)", shards.len());
-    let mut set = JoinSet::new();
-    for mut shard in shards {
-        let mut rx = rx.clone();
-        let consumer_clone = consumer.clone();
-        let producer_clone = producer.clone();
-
-        set.spawn(async move {
-            tokio::select! {
-                _ = kafka::handle(&mut shard, producer_clone, consumer_clone) => {},
-                _ = rx.changed() => {
-                    shard.close(CloseFrame::NORMAL);
+    let sessions = sessions::load().await?;
+    let (numbers, total, interval) = if let Some(cluster) = cluster {
+        log::info!(
+            "running cluster {} with shards {}..{} out of {}",
+            cluster.id,
+            cluster.shards.start,
+            cluster.shards.end,
+            cluster.total
+        );
+
+        (cluster.shards, cluster.total, None)
+    } else {
+        let total = shards::recommended_total().await?;
+
+        (0..total, total, resharding::interval()?)
+    };
+    let intents = intents::obtain();
+    let shards = shards::obtain(queue.clone(), numbers, total, intents, &sessions);
+
+    let mut current = ShardSet::launch(shards, total, true, &producer, &outbound_tx);
+
+    let shutdown = ShutdownController::new();
+    shutdown.listen_for_signals();
+
+    // resharding runs as a task, such that shutdown is not held up by it
+    let mut resharding = None::<JoinHandle<Option<ShardSet>>>;
+
+    loop {
+        tokio::select! {
+            () = shutdown.requested() => break,
+            () = resharding::tick(interval), if resharding.is_none() => {
+                resharding = Some(tokio::spawn(resharding::reshard(
+                    current.total(),
+                    queue.clone(),
+                    intents,
+                    producer.clone(),
+                    outbound_tx.clone(),
+                    shutdown.clone(),
+                )));
+            }
+            Some(result) = async { Some(resharding.as_mut()?.await) } => {
+                resharding = None;
+
+                match result {
+                    Ok(Some(new)) => current = resharding::hand_over(current, new).await,
+                    Ok(None) => {}
+                    // the current shard set is kept, and resharding is attempted again on the
+                    // next tick
+                    Err(error) => println!("{:?}", Err::<(), JoinError>(error).into_diagnostic()),
                 }
             }
-        });
+        }
     }
 
-    signal::ctrl_c().await.into_diagnostic()?;
+    log::warn!("shutting down");
 
-    log::warn!("ctrl-c signal received, shutting down");
+    // the new shard set is shut down by the resharding task itself once shutdown is requested
+    if let Some(task) = resharding {
+        let _ = task.await;
+    }
 
-    tx.send(true).into_diagnostic()?;
+    current.shutdown(ShutdownKind::Restart).await;
 
-    // wait for all tasks to complete
-    while set.join_next().await.is_some() {}
+    log::trace!("waiting for database connections to be returned");
+    if !hartex_discord_utils::drain_database_pool(DEFAULT_DRAIN_TIMEOUT).await {
+        log::warn!("database connections are still in use");
+    }
 
     Ok(())
 }
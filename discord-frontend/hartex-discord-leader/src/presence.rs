@@ -0,0 +1,244 @@
+/*
+ * SPDX-License-Identifier: AGPL-3.0-only
+ *
+ * This file is part of HarTex.
+ *
+ * HarTex
+ * Copyright (c) 2021-2024 HarTex Project Developers
+ *
+ * HarTex is free software; you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation; either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * HarTex is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License along
+ * with HarTex. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::env;
+use std::pin::Pin;
+use std::sync::PoisonError;
+use std::sync::RwLock;
+use std::time::Duration;
+
+use hartex_database_queries::discord_frontend::queries::cached_guild_count::cached_guild_count;
+use hartex_discord_core::discord::gateway::ShardId;
+use hartex_discord_core::discord::model::gateway::payload::outgoing::update_presence::UpdatePresencePayload;
+use hartex_discord_core::discord::model::gateway::presence::Activity;
+use hartex_discord_core::discord::model::gateway::presence::ActivityType;
+use hartex_discord_core::discord::model::gateway::presence::Status;
+use hartex_discord_core::tokio::sync::broadcast;
+use hartex_discord_core::tokio::time;
+use hartex_discord_core::tokio::time::MissedTickBehavior;
+use hartex_discord_utils::DATABASE_POOL;
+use hartex_log::log;
+use miette::IntoDiagnostic;
+use once_cell::sync::Lazy;
+use serde::Deserialize;
+use tokio_postgres::GenericClient;
+
+use crate::kafka::OutboundMessage;
+
+/// How often the number of guilds the bot is in is refreshed.
+const REFRESH_INTERVAL: Duration = Duration::from_secs(300);
+
+/// The presence of the bot, shared by every shard.
+pub static PRESENCE: Lazy<RwLock<Presence>> = Lazy::new(|| {
+    RwLock::new(Presence {
+        template: PresenceTemplate::default(),
+        guild_count: 0,
+    })
+});
+
+/// The presence of the bot, along with the values of the variables that are not specific to a
+/// shard.
+#[derive(Clone, Debug)]
+pub struct Presence {
+    /// The presence template.
+    pub template: PresenceTemplate,
+    /// The number of guilds the bot is in.
+    pub guild_count: i64,
+}
+
+/// A presence template.
+///
+/// The following variables are substituted in the activity name:
+/// - `{shard_id}`: the number of the shard
+/// - `{shard_count}`: the total number of shards
+/// - `{guild_count}`: the number of guilds the bot is in
+/// - `{version}`: the version of the bot
+#[derive(Clone, Debug, Deserialize)]
+pub struct PresenceTemplate {
+    /// The kind of activity.
+    pub activity: PresenceActivity,
+    /// The name of the activity.
+    pub name: String,
+    /// The status.
+    pub status: Status,
+}
+
+impl Default for PresenceTemplate {
+    fn default() -> Self {
+        Self {
+            activity: PresenceActivity::Watching,
+            name: String::from("development | shard {shard_id}"),
+            status: Status::Online,
+        }
+    }
+}
+
+/// The kind of activity of a presence.
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PresenceActivity {
+    /// Competing in something.
+    Competing,
+    /// A custom status.
+    Custom,
+    /// Listening to something.
+    Listening,
+    /// Playing something.
+    Playing,
+    /// Watching something.
+    Watching,
+}
+
+impl From<PresenceActivity> for ActivityType {
+    fn from(activity: PresenceActivity) -> Self {
+        match activity {
+            PresenceActivity::Competing => Self::Competing,
+            PresenceActivity::Custom => Self::Custom,
+            PresenceActivity::Listening => Self::Listening,
+            PresenceActivity::Playing => Self::Playing,
+            PresenceActivity::Watching => Self::Watching,
+        }
+    }
+}
+
+/// Initialize the presence of the bot.
+///
+/// The template is read from the `PRESENCE_TEMPLATE` environment variable as JSON if it is set,
+/// for instance `{"activity": "watching", "name": "{guild_count} servers", "status": "online"}`.
+pub async fn initialize() -> miette::Result<()> {
+    let template = match env::var("PRESENCE_TEMPLATE") {
+        Ok(json) => serde_json::from_str::<PresenceTemplate>(&json).into_diagnostic()?,
+        Err(_) => PresenceTemplate::default(),
+    };
+
+    update(template).await
+}
+
+/// Update the presence template of the bot, refreshing the number of guilds the bot is in.
+pub async fn update(template: PresenceTemplate) -> miette::Result<()> {
+    let guild_count = guild_count().await?;
+
+    *PRESENCE.write().unwrap_or_else(PoisonError::into_inner) = Presence {
+        template,
+        guild_count,
+    };
+
+    Ok(())
+}
+
+/// Refresh the number of guilds the bot is in periodically, updating the presence of every shard
+/// if the number changed and is shown in the presence.
+pub async fn refresh(tx: broadcast::Sender<OutboundMessage>) {
+    let mut interval = time::interval(REFRESH_INTERVAL);
+    interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    // the first tick completes immediately, right after the presence was initialized
+    interval.tick().await;
+
+    loop {
+        interval.tick().await;
+
+        let guild_count = match guild_count().await {
+            Ok(guild_count) => guild_count,
+            Err(error) => {
+                println!("{error:?}");
+
+                continue;
+            }
+        };
+
+        let changed = {
+            let mut presence = PRESENCE.write().unwrap_or_else(PoisonError::into_inner);
+            let changed = presence.guild_count != guild_count
+                && presence.template.name.contains("{guild_count}");
+            presence.guild_count = guild_count;
+
+            changed
+        };
+
+        if changed {
+            log::trace!("number of guilds changed to {guild_count}, updating presence");
+
+            // there may be no shards to receive the message while resharding
+            let _ = tx.send(OutboundMessage::PresenceUpdated);
+        }
+    }
+}
+
+/// Query the number of guilds the bot is in.
+async fn guild_count() -> miette::Result<i64> {
+    let pinned = Pin::static_ref(&DATABASE_POOL).await;
+    let pooled = pinned.get().await.into_diagnostic()?;
+    let client = pooled.client();
+
+    cached_guild_count()
+        .bind(client)
+        .one()
+        .await
+        .into_diagnostic()
+}
+
+/// Render the presence of the bot for a shard.
+pub fn render(shard_id: ShardId) -> UpdatePresencePayload {
+    let presence = PRESENCE
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone();
+
+    let name = presence
+        .template
+        .name
+        .replace("{shard_id}", &shard_id.number().to_string())
+        .replace("{shard_count}", &shard_id.total().to_string())
+        .replace("{guild_count}", &presence.guild_count.to_string())
+        .replace("{version}", env!("CARGO_PKG_VERSION"));
+
+    // custom statuses display the state of the activity rather than its name
+    let (name, state) = match presence.template.activity {
+        PresenceActivity::Custom => (String::from("Custom Status"), Some(name)),
+        _ => (name, None),
+    };
+
+    UpdatePresencePayload {
+        activities: vec![Activity {
+            application_id: None,
+            assets: None,
+            buttons: vec![],
+            created_at: None,
+            details: None,
+            emoji: None,
+            flags: None,
+            id: None,
+            instance: None,
+            kind: presence.template.activity.into(),
+            name,
+            party: None,
+            secrets: None,
+            state,
+            timestamps: None,
+            url: None,
+        }],
+        afk: false,
+        since: None,
+        status: presence.template.status,
+    }
+}
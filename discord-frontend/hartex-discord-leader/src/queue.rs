@@ -20,8 +20,11 @@
  * with HarTex. If not, see <https://www.gnu.org/licenses/>.
  */
 
+use std::env;
+use std::pin::Pin;
 use std::time::Duration;
 
+use hartex_database_queries::discord_frontend::queries::identify_bucket_lock::identify_bucket_lock;
 use hartex_discord_core::discord::gateway::queue::Queue;
 use hartex_discord_core::tokio;
 use hartex_discord_core::tokio::sync::mpsc::unbounded_channel;
@@ -31,9 +34,13 @@ use hartex_discord_core::tokio::sync::oneshot;
 use hartex_discord_core::tokio::sync::oneshot::Receiver;
 use hartex_discord_core::tokio::sync::oneshot::Sender;
 use hartex_discord_core::tokio::time::sleep;
+use hartex_discord_utils::DATABASE_POOL;
 use hartex_log::log;
 use miette::IntoDiagnostic;
 
+/// How long to wait before retrying to acquire the lock of a bucket after failing to.
+const LOCK_RETRY_INTERVAL: Duration = Duration::from_secs(1);
+
 /// A discriminated union of supported bot queues.
 #[allow(clippy::module_name_repetitions)]
 #[derive(Clone, Debug)]
@@ -42,6 +49,8 @@ pub enum BotQueue {
     Local(LocalQueue),
     /// A large bot queue.
     LargeBot(LargeBotQueue),
+    /// A queue shared between leader processes.
+    Postgres(PostgresQueue),
 }
 
 impl Queue for BotQueue {
@@ -49,6 +58,7 @@ impl Queue for BotQueue {
         match self {
             Self::Local(local) => local.enqueue(id),
             Self::LargeBot(large_bot) => large_bot.enqueue(id),
+            Self::Postgres(postgres) => postgres.enqueue(id),
         }
     }
 }
@@ -114,6 +124,99 @@ impl Queue for LargeBotQueue {
     }
 }
 
+/// A queue whose bucket state lives in PostgreSQL, shared between leader processes.
+///
+/// Each bucket is guarded by an advisory lock, which is held for the duration to wait between
+/// identifies. Multiple leader processes using the same database therefore never identify more
+/// shards at once than the buckets allow.
+#[allow(clippy::module_name_repetitions)]
+#[derive(Clone, Debug)]
+pub struct PostgresQueue(Vec<UnboundedSender<Sender<()>>>);
+
+impl PostgresQueue {
+    /// Create a queue shared between leader processes.
+    pub fn new(buckets: usize, duration: Duration) -> Self {
+        let mut queues = Vec::with_capacity(buckets);
+        for bucket in 0..buckets {
+            #[allow(clippy::cast_possible_truncation)]
+            #[allow(clippy::cast_possible_wrap)]
+            let bucket = bucket as i32;
+            let (tx, rx) = unbounded_channel();
+            tokio::spawn(wait_for_lock(rx, bucket, duration));
+            queues.push(tx);
+        }
+
+        Self(queues)
+    }
+}
+
+impl Queue for PostgresQueue {
+    #[allow(unused_must_use)]
+    fn enqueue(&'_ self, shard_id: u32) -> Receiver<()> {
+        #[allow(clippy::cast_possible_truncation)]
+        let bucket = (shard_id % (self.0.len() as u32)) as usize;
+        let (tx, rx) = oneshot::channel();
+        if let Err(error) = self.0[bucket].clone().send(tx) {
+            log::warn!("skipping, send failed: {:?}", error);
+        }
+
+        rx
+    }
+}
+
+/// Waits for the lock of a bucket whenever an unbounded receiver receives something.
+///
+/// The shard is only allowed to identify once the lock of its bucket is held; acquiring the lock
+/// is retried until it succeeds or the shard stops waiting.
+async fn wait_for_lock(mut rx: UnboundedReceiver<Sender<()>>, bucket: i32, duration: Duration) {
+    while let Some(tx) = rx.recv().await {
+        let mut tx = Some(tx);
+
+        loop {
+            if let Err(error) = hold_lock(bucket, &mut tx, duration).await {
+                log::warn!("failed to hold lock of identify bucket {bucket}: {error:?}");
+            }
+
+            // the shard was allowed to identify, or is no longer waiting to
+            match &tx {
+                Some(waiting) if !waiting.is_closed() => {}
+                _ => break,
+            }
+
+            sleep(LOCK_RETRY_INTERVAL).await;
+        }
+    }
+}
+
+/// Acquires the lock of a bucket, and holds it for a while after allowing the shard to identify.
+///
+/// The sender is only taken once the lock is held, such that it is left in place if acquiring the
+/// lock fails.
+async fn hold_lock(
+    bucket: i32,
+    tx: &mut Option<Sender<()>>,
+    duration: Duration,
+) -> miette::Result<()> {
+    let pinned = Pin::static_ref(&DATABASE_POOL).await;
+    let mut pooled = pinned.get().await.into_diagnostic()?;
+    let transaction = pooled.transaction().await.into_diagnostic()?;
+
+    // the lock is released when the transaction ends
+    identify_bucket_lock()
+        .bind(&transaction, &bucket)
+        .one()
+        .await
+        .into_diagnostic()?;
+
+    if let Some(Err(error)) = tx.take().map(|tx| tx.send(())) {
+        log::warn!("skipping, send failed: {:?}", error);
+    }
+
+    sleep(duration).await;
+
+    transaction.commit().await.into_diagnostic()
+}
+
 /// Waits for a while until an unbounded receiver receives something.
 async fn wait_for_while(mut rx: UnboundedReceiver<Sender<()>>, duration: Duration) {
     while let Some(tx) = rx.recv().await {
@@ -126,21 +229,69 @@ async fn wait_for_while(mut rx: UnboundedReceiver<Sender<()>>, duration: Duratio
 }
 
 /// Obtain a queue to use for the startup of the bot.
+///
+/// The queue is shared between leader processes if the `SHARD_QUEUE` environment variable is set
+/// to `postgres`.
 pub fn obtain() -> miette::Result<BotQueue> {
-    let concurrency = std::env::var("SHARD_CONCURRENCY")
+    let concurrency = env::var("SHARD_CONCURRENCY")
         .into_diagnostic()?
         .parse::<usize>()
         .into_diagnostic()?;
     let wait = Duration::from_secs(
-        std::env::var("SHARD_CONCURRENCY_WAIT_SECONDS")
+        env::var("SHARD_CONCURRENCY_WAIT_SECONDS")
             .into_diagnostic()?
             .parse::<u64>()
             .into_diagnostic()?,
     );
 
+    if env::var("SHARD_QUEUE").is_ok_and(|queue| queue == "postgres") {
+        return Ok(BotQueue::Postgres(PostgresQueue::new(concurrency, wait)));
+    }
+
     Ok(if concurrency == 1 {
         BotQueue::Local(LocalQueue::new(wait))
     } else {
         BotQueue::LargeBot(LargeBotQueue::new(concurrency, wait))
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use std::time::Instant;
+
+    use hartex_discord_core::discord::gateway::queue::Queue;
+    use hartex_discord_core::dotenvy;
+    use hartex_discord_core::tokio;
+
+    use super::PostgresQueue;
+
+    /// Two leader processes sharing a bucket must not let their shards identify within the wait of
+    /// each other.
+    ///
+    /// This requires a local PostgreSQL database, whose URL is read from the
+    /// `HARTEX_NIGHTLY_PGSQL_URL` environment variable, and is run with `cargo test -- --ignored`.
+    #[ignore = "requires a local PostgreSQL database"]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn competing_holders_identify_one_after_another() {
+        dotenvy::dotenv().ok();
+
+        let wait = Duration::from_secs(2);
+        let first = PostgresQueue::new(1, wait);
+        let second = PostgresQueue::new(1, wait);
+
+        let started = Instant::now();
+        let (first, second) = tokio::join!(
+            async {
+                first.enqueue(0).await.unwrap();
+                started.elapsed()
+            },
+            async {
+                second.enqueue(0).await.unwrap();
+                started.elapsed()
+            },
+        );
+
+        assert!(first.max(second) - first.min(second) >= wait);
+    }
+}
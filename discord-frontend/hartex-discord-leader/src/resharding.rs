@@ -0,0 +1,165 @@
+/*
+ * SPDX-License-Identifier: AGPL-3.0-only
+ *
+ * This file is part of HarTex.
+ *
+ * HarTex
+ * Copyright (c) 2021-2024 HarTex Project Developers
+ *
+ * HarTex is free software; you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation; either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * HarTex is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License along
+ * with HarTex. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::collections::HashMap;
+use std::env;
+use std::future;
+use std::time::Duration;
+
+use hartex_discord_core::discord::gateway::queue::Queue;
+use hartex_discord_core::discord::gateway::Intents;
+use hartex_discord_core::shutdown::ShutdownController;
+use hartex_discord_core::tokio;
+use hartex_discord_core::tokio::sync::broadcast;
+use hartex_discord_core::tokio::time;
+use hartex_discord_core::tokio::time::sleep;
+use hartex_log::log;
+use miette::IntoDiagnostic;
+use rdkafka::producer::FutureProducer;
+
+use crate::kafka::OutboundMessage;
+use crate::shards;
+use crate::shardset::ShardSet;
+use crate::shardset::ShutdownKind;
+
+/// The time given to the shards of a new shard set to become ready, regardless of their number.
+const READY_TIMEOUT_BASE: Duration = Duration::from_secs(60);
+
+/// The additional time given to the shards of a new shard set to become ready, for each shard.
+const READY_TIMEOUT_PER_SHARD: Duration = Duration::from_secs(10);
+
+/// Obtain the interval between checks of whether resharding is necessary.
+///
+/// Returns `None` if resharding is disabled, which is the case when the
+/// `RESHARDING_CHECK_INTERVAL_SECONDS` environment variable is not set. Resharding is not
+/// available in cluster mode, as the shard ranges of the clusters are fixed.
+pub fn interval() -> miette::Result<Option<Duration>> {
+    let Ok(seconds) = env::var("RESHARDING_CHECK_INTERVAL_SECONDS") else {
+        return Ok(None);
+    };
+
+    Ok(Some(Duration::from_secs(
+        seconds.parse::<u64>().into_diagnostic()?,
+    )))
+}
+
+/// Wait until the next check of whether resharding is necessary is due.
+///
+/// Never completes if resharding is disabled.
+pub async fn tick(interval: Option<Duration>) {
+    match interval {
+        Some(duration) => sleep(duration).await,
+        None => future::pending().await,
+    }
+}
+
+/// Launch a new shard set if the number of shards recommended by Discord has grown beyond the
+/// total number of shards of the current shard set.
+///
+/// The new shard set is launched alongside the current one, and is returned once every shard in it
+/// has become ready, such that it can be handed over to. If any shard of the new set exits before
+/// then, if the shards do not become ready in time or if shutdown is requested, the new set is shut
+/// down instead and the current set is kept.
+pub async fn reshard<Q>(
+    current_total: u32,
+    queue: Q,
+    intents: Intents,
+    producer: FutureProducer,
+    outbound: broadcast::Sender<OutboundMessage>,
+    shutdown: ShutdownController,
+) -> Option<ShardSet>
+where
+    Q: Queue + Clone + Send + Sync + Sized + Unpin + 'static,
+{
+    let recommended = match shards::recommended_total().await {
+        Ok(recommended) => recommended,
+        Err(error) => {
+            log::warn!("failed to obtain recommended number of shards: {error:?}");
+
+            return None;
+        }
+    };
+
+    if recommended <= current_total {
+        return None;
+    }
+
+    log::info!("resharding from {current_total} to {recommended} shard(s)");
+
+    let shards = shards::obtain(queue, 0..recommended, recommended, intents, &HashMap::new());
+    let mut new = ShardSet::launch(shards, recommended, false, &producer, &outbound);
+
+    let timeout = ready_timeout(recommended);
+    let ready = tokio::select! {
+        result = time::timeout(timeout, new.wait_until_ready()) => match result {
+            Ok(true) => true,
+            Ok(false) => {
+                log::error!(
+                    "a shard of the new shard set exited before becoming ready; aborting resharding"
+                );
+
+                false
+            }
+            Err(_) => {
+                log::error!(
+                    "the new shard set did not become ready within {} seconds; aborting resharding",
+                    timeout.as_secs()
+                );
+
+                false
+            }
+        },
+        () = shutdown.requested() => {
+            log::warn!("shutdown requested while resharding; aborting resharding");
+
+            false
+        }
+    };
+
+    if !ready {
+        new.shutdown(ShutdownKind::Retire).await;
+
+        return None;
+    }
+
+    Some(new)
+}
+
+/// Hand over from the current shard set to a new shard set that has become ready, returning the
+/// new shard set.
+pub async fn hand_over(current: ShardSet, new: ShardSet) -> ShardSet {
+    // payloads may briefly be forwarded by both shard sets during the handover
+    new.activate();
+    current.shutdown(ShutdownKind::Retire).await;
+
+    log::info!("resharded to {} shard(s)", new.total());
+
+    new
+}
+
+/// How long the shards of a new shard set are given to become ready.
+///
+/// Shards identify one after another subject to the identify rate limit, such that the time
+/// needed grows with the number of shards.
+fn ready_timeout(total: u32) -> Duration {
+    READY_TIMEOUT_BASE + READY_TIMEOUT_PER_SHARD * total
+}
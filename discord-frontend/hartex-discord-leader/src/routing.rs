@@ -0,0 +1,131 @@
+/*
+ * SPDX-License-Identifier: AGPL-3.0-only
+ *
+ * This file is part of HarTex.
+ *
+ * HarTex
+ * Copyright (c) 2021-2024 HarTex Project Developers
+ *
+ * HarTex is free software; you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation; either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * HarTex is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License along
+ * with HarTex. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! # Event Routing
+//!
+//! Gateway payloads are only published to the topics whose consumers handle their event types,
+//! such that floods of events no consumer is interested in (for instance `PRESENCE_UPDATE` and
+//! `TYPING_START`) do not reach them. Payloads without an event type, which are not dispatch
+//! events, are not consumed by any component.
+
+use std::collections::HashMap;
+use std::env;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use hartex_discord_core::discord::model::gateway::event::EventType;
+use hartex_discord_core::tokio::time::sleep;
+use hartex_kafka_utils::envelope::WORKER_EVENT_TYPES;
+use hartex_log::log;
+use miette::IntoDiagnostic;
+use once_cell::sync::Lazy;
+
+/// The interval between reports of the number of events dropped by each route.
+const REPORT_INTERVAL: Duration = Duration::from_secs(300);
+
+/// The routes of each event type.
+static ROUTING_TABLE: Lazy<HashMap<&'static str, Vec<Route>>> = Lazy::new(|| {
+    let mut table = HashMap::<&'static str, Vec<Route>>::new();
+
+    let plugin_event_types = hartex_discord_commands::plugins()
+        .iter()
+        .flat_map(|plugin| plugin.event_handlers())
+        .flat_map(|handler| handler.event_types())
+        .collect::<Vec<_>>();
+    for event_type in WORKER_EVENT_TYPES
+        .into_iter()
+        .chain(plugin_event_types)
+        .filter_map(EventType::name)
+    {
+        let routes = table.entry(event_type).or_default();
+        if !routes.contains(&Route::Worker) {
+            routes.push(Route::Worker);
+        }
+    }
+
+    for (event_type, _) in hartex_discord_entitycache_cacheupdaters::intents() {
+        table.entry(event_type).or_default().push(Route::Cache);
+    }
+
+    table
+});
+
+/// The number of events dropped by each route, indexed by route.
+static DROPPED: [AtomicU64; 2] = [AtomicU64::new(0), AtomicU64::new(0)];
+
+/// A component consuming gateway payloads from a topic.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Route {
+    /// The worker process.
+    Worker,
+    /// The entitycache update service.
+    Cache,
+}
+
+impl Route {
+    /// Every route.
+    pub const ALL: [Self; 2] = [Self::Worker, Self::Cache];
+
+    /// Whether a payload of the given event type is to be published to the topic of this route.
+    ///
+    /// The payload is counted as dropped by this route if it is not.
+    pub fn accepts(self, event_type: Option<&str>) -> bool {
+        let accepted = event_type
+            .and_then(|event_type| ROUTING_TABLE.get(event_type))
+            .is_some_and(|routes| routes.contains(&self));
+
+        if !accepted {
+            DROPPED[self as usize].fetch_add(1, Ordering::Relaxed);
+        }
+
+        accepted
+    }
+
+    /// The number of payloads dropped by this route since startup.
+    pub fn dropped(self) -> u64 {
+        DROPPED[self as usize].load(Ordering::Relaxed)
+    }
+
+    /// The topic of this route, read from the environment.
+    pub fn topic(self) -> miette::Result<String> {
+        env::var(match self {
+            Self::Worker => "KAFKA_TOPIC_INBOUND_DISCORD_GATEWAY_PAYLOAD",
+            Self::Cache => "KAFKA_TOPIC_INBOUND_DISCORD_GATEWAY_PAYLOAD_CACHE",
+        })
+        .into_diagnostic()
+    }
+}
+
+/// Periodically report the number of payloads dropped by each route.
+pub async fn report() {
+    loop {
+        sleep(REPORT_INTERVAL).await;
+
+        for route in Route::ALL {
+            log::info!(
+                "{route:?} route has dropped {} payload(s) since startup",
+                route.dropped()
+            );
+        }
+    }
+}
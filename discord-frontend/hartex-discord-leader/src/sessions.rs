@@ -0,0 +1,119 @@
+/*
+ * SPDX-License-Identifier: AGPL-3.0-only
+ *
+ * This file is part of HarTex.
+ *
+ * HarTex
+ * Copyright (c) 2021-2024 HarTex Project Developers
+ *
+ * HarTex is free software; you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation; either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * HarTex is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License along
+ * with HarTex. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::collections::HashMap;
+use std::pin::Pin;
+
+use hartex_database_queries::discord_frontend::queries::gateway_session_select_all::gateway_session_select_all;
+use hartex_database_queries::discord_frontend::queries::gateway_session_upsert::gateway_session_upsert;
+use hartex_discord_core::discord::gateway::queue::Queue;
+use hartex_discord_core::discord::gateway::Session;
+use hartex_discord_core::discord::gateway::Shard;
+use hartex_discord_core::discord::gateway::ShardId;
+use hartex_discord_utils::DATABASE_POOL;
+use hartex_log::log;
+use miette::IntoDiagnostic;
+use tokio_postgres::GenericClient;
+
+/// A gateway session that can be resumed, along with the URL to resume it with.
+#[derive(Clone, Debug)]
+pub struct ResumableSession {
+    /// The gateway session.
+    pub session: Session,
+    /// The URL to connect to when resuming the session.
+    pub resume_url: String,
+}
+
+/// Load the gateway sessions persisted by a previous run of the leader.
+///
+/// The sessions are keyed by the shard number and the total number of shards, as a session can
+/// only be resumed by a shard with the same identity.
+pub async fn load() -> miette::Result<HashMap<(u32, u32), ResumableSession>> {
+    let pinned = Pin::static_ref(&DATABASE_POOL).await;
+    let pooled = pinned.get().await.into_diagnostic()?;
+    let client = pooled.client();
+
+    let rows = gateway_session_select_all()
+        .bind(client)
+        .all()
+        .await
+        .into_diagnostic()?;
+
+    let mut sessions = HashMap::with_capacity(rows.len());
+    for row in rows {
+        let (Ok(number), Ok(total), Ok(sequence)) = (
+            u32::try_from(row.shard_id),
+            u32::try_from(row.shard_count),
+            u64::try_from(row.sequence),
+        ) else {
+            log::warn!(
+                "skipping persisted session of shard {}/{}: value out of range",
+                row.shard_id,
+                row.shard_count
+            );
+
+            continue;
+        };
+
+        sessions.insert(
+            (number, total),
+            ResumableSession {
+                session: Session::new(sequence, row.session_id),
+                resume_url: row.resume_url,
+            },
+        );
+    }
+
+    Ok(sessions)
+}
+
+/// Persist the current gateway session of a shard, such that it can be resumed on the next boot.
+pub async fn persist(shard_id: ShardId, session: ResumableSession) -> miette::Result<()> {
+    let pinned = Pin::static_ref(&DATABASE_POOL).await;
+    let pooled = pinned.get().await.into_diagnostic()?;
+    let client = pooled.client();
+
+    gateway_session_upsert()
+        .bind(
+            client,
+            &i64::from(shard_id.number()),
+            &i64::from(shard_id.total()),
+            &session.session.id().to_string(),
+            &i64::try_from(session.session.sequence()).into_diagnostic()?,
+            &session.resume_url,
+        )
+        .await
+        .into_diagnostic()?;
+
+    Ok(())
+}
+
+/// Obtain the resumable session of a shard, if it has one.
+pub fn resumable<Q>(shard: &Shard<Q>) -> Option<ResumableSession>
+where
+    Q: Queue,
+{
+    Some(ResumableSession {
+        session: shard.session()?.clone(),
+        resume_url: shard.resume_url()?.to_string(),
+    })
+}
@@ -20,64 +20,79 @@
  * with HarTex. If not, see <https://www.gnu.org/licenses/>.
  */
 
+use std::collections::HashMap;
 use std::ops::Deref;
+use std::ops::Range;
 
-use hartex_discord_core::discord::gateway::create_recommended;
+use hartex_discord_core::discord::gateway::create_iterator;
 use hartex_discord_core::discord::gateway::queue::Queue;
 use hartex_discord_core::discord::gateway::ConfigBuilder;
 use hartex_discord_core::discord::gateway::Intents;
 use hartex_discord_core::discord::gateway::Shard;
 use hartex_discord_core::discord::gateway::ShardId;
-use hartex_discord_core::discord::model::gateway::payload::outgoing::update_presence::UpdatePresencePayload;
-use hartex_discord_core::discord::model::gateway::presence::Activity;
-use hartex_discord_core::discord::model::gateway::presence::ActivityType;
-use hartex_discord_core::discord::model::gateway::presence::Status;
 use hartex_discord_utils::CLIENT;
 use hartex_discord_utils::TOKEN;
+use hartex_log::log;
 use miette::IntoDiagnostic;
 
-/// Obtain a list of shards.
-pub async fn obtain<Q>(queue: Q) -> miette::Result<Vec<Shard<Q>>>
+use crate::presence;
+use crate::sessions::ResumableSession;
+
+/// Obtain the number of shards currently recommended by Discord.
+pub async fn recommended_total() -> miette::Result<u32> {
+    let info = CLIENT
+        .gateway()
+        .authed()
+        .await
+        .into_diagnostic()?
+        .model()
+        .await
+        .into_diagnostic()?;
+
+    Ok(info.shards)
+}
+
+/// Obtain a list of shards with the given shard numbers, out of the total number of shards.
+///
+/// Shards that have a session persisted from a previous run of the leader will attempt to resume
+/// that session instead of identifying anew.
+pub fn obtain<Q>(
+    queue: Q,
+    numbers: Range<u32>,
+    total: u32,
+    intents: Intents,
+    sessions: &HashMap<(u32, u32), ResumableSession>,
+) -> Vec<Shard<Q>>
 where
     Q: Queue + Clone + Send + Sync + Sized,
 {
-    let config = ConfigBuilder::new(TOKEN.deref().clone(), Intents::all())
+    let config = ConfigBuilder::new(TOKEN.deref().clone(), intents)
         .queue(queue.clone())
         .build();
 
-    Ok(create_recommended::<_, Q>(
-        &CLIENT,
+    create_iterator::<_, Q>(
+        numbers,
+        total,
         config,
         |shard_id: ShardId, builder: ConfigBuilder<Q>| {
-            builder
-                .presence(UpdatePresencePayload {
-                    activities: vec![Activity {
-                        application_id: None,
-                        assets: None,
-                        buttons: vec![],
-                        created_at: None,
-                        details: None,
-                        emoji: None,
-                        flags: None,
-                        id: None,
-                        instance: None,
-                        kind: ActivityType::Watching,
-                        name: format!("development | shard {}", shard_id.number()),
-                        party: None,
-                        secrets: None,
-                        state: None,
-                        timestamps: None,
-                        url: None,
-                    }],
-                    afk: false,
-                    since: None,
-                    status: Status::Online,
-                })
-                .queue(queue.clone())
-                .build()
+            let mut builder = builder
+                .presence(presence::render(shard_id))
+                .queue(queue.clone());
+
+            if let Some(resumable) = sessions.get(&(shard_id.number(), shard_id.total())) {
+                log::trace!(
+                    "[shard {shard_id}] resuming session {session_id}",
+                    shard_id = shard_id.number(),
+                    session_id = resumable.session.id()
+                );
+
+                builder = builder
+                    .session(resumable.session.clone())
+                    .resume_url(resumable.resume_url.clone());
+            }
+
+            builder.build()
         },
     )
-    .await
-    .into_diagnostic()?
-    .collect::<Vec<_>>())
+    .collect::<Vec<_>>()
 }
@@ -0,0 +1,208 @@
+/*
+ * SPDX-License-Identifier: AGPL-3.0-only
+ *
+ * This file is part of HarTex.
+ *
+ * HarTex
+ * Copyright (c) 2021-2024 HarTex Project Developers
+ *
+ * HarTex is free software; you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation; either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * HarTex is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License along
+ * with HarTex. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::collections::HashSet;
+
+use futures_util::StreamExt;
+use hartex_discord_core::discord::gateway::queue::Queue;
+use hartex_discord_core::discord::gateway::CloseFrame;
+use hartex_discord_core::discord::gateway::Message as GatewayMessage;
+use hartex_discord_core::discord::gateway::Shard;
+use hartex_discord_core::tokio;
+use hartex_discord_core::tokio::sync::broadcast;
+use hartex_discord_core::tokio::sync::mpsc::unbounded_channel;
+use hartex_discord_core::tokio::sync::mpsc::UnboundedReceiver;
+use hartex_discord_core::tokio::sync::mpsc::UnboundedSender;
+use hartex_discord_core::tokio::sync::watch;
+use hartex_discord_core::tokio::task::JoinSet;
+use hartex_log::log;
+use rdkafka::producer::FutureProducer;
+
+use crate::kafka;
+use crate::kafka::OutboundMessage;
+use crate::sessions;
+
+/// How the shards of a shard set are shut down.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ShutdownKind {
+    /// The leader is restarting; the sessions of the shards are kept and persisted such that they
+    /// can be resumed on the next boot.
+    Restart,
+    /// The shard set is being replaced by another; the sessions of the shards are discarded.
+    Retire,
+}
+
+/// The state of a shard set shared with each of its shards.
+#[derive(Clone, Debug)]
+pub struct ShardContext {
+    /// Whether the shard set is active, i.e. whether gateway payloads received by the shard are
+    /// forwarded to other components.
+    pub active: watch::Receiver<bool>,
+    /// Used to notify the shard set of the shard becoming ready.
+    pub ready: UnboundedSender<u32>,
+}
+
+/// A set of shards sharing the same total number of shards.
+///
+/// In cluster mode, a shard set only contains the shards of the cluster.
+pub struct ShardSet {
+    len: usize,
+    total: u32,
+    active: watch::Sender<bool>,
+    ready: UnboundedReceiver<u32>,
+    shutdown: watch::Sender<Option<ShutdownKind>>,
+    tasks: JoinSet<()>,
+}
+
+impl ShardSet {
+    /// Launch a set of shards.
+    ///
+    /// An inactive shard set connects to the gateway, but does not forward the payloads it
+    /// receives until it is activated.
+    pub fn launch<Q>(
+        shards: Vec<Shard<Q>>,
+        total: u32,
+        active: bool,
+        producer: &FutureProducer,
+        outbound: &broadcast::Sender<OutboundMessage>,
+    ) -> Self
+    where
+        Q: Queue + Send + Sync + Sized + Unpin + 'static,
+    {
+        let (active_tx, active_rx) = watch::channel(active);
+        let (ready_tx, mut ready_rx) = unbounded_channel();
+        let (shutdown_tx, shutdown_rx) = watch::channel(None);
+
+        // an active shard set is never waited upon
+        if active {
+            ready_rx.close();
+        }
+
+        let len = shards.len();
+
+        log::trace!("launching {len} shard(s) out of {total}");
+        let mut tasks = JoinSet::new();
+        for mut shard in shards {
+            let context = ShardContext {
+                active: active_rx.clone(),
+                ready: ready_tx.clone(),
+            };
+            let mut shutdown_rx = shutdown_rx.clone();
+            let outbound_rx = outbound.subscribe();
+            let producer_clone = producer.clone();
+
+            tasks.spawn(async move {
+                tokio::select! {
+                    result = kafka::handle(&mut shard, producer_clone, outbound_rx, context) => {
+                        if let Err(error) = result {
+                            println!("{error:?}");
+                        }
+                    },
+                    _ = shutdown_rx.changed() => {
+                        let kind = *shutdown_rx.borrow();
+                        if let Some(kind) = kind {
+                            close(&mut shard, kind).await;
+                        }
+                    }
+                }
+            });
+        }
+
+        Self {
+            len,
+            total,
+            active: active_tx,
+            ready: ready_rx,
+            shutdown: shutdown_tx,
+            tasks,
+        }
+    }
+
+    /// The total number of shards in this shard set.
+    pub fn total(&self) -> u32 {
+        self.total
+    }
+
+    /// Wait until every shard in this shard set has become ready.
+    ///
+    /// Returns `false` if any of the shards exited before every shard has become ready.
+    pub async fn wait_until_ready(&mut self) -> bool {
+        let mut ready = HashSet::new();
+
+        while ready.len() < self.len {
+            tokio::select! {
+                Some(number) = self.ready.recv() => {
+                    ready.insert(number);
+                }
+                _ = self.tasks.join_next() => return false,
+            }
+        }
+
+        self.ready.close();
+
+        true
+    }
+
+    /// Activate this shard set, such that its shards start forwarding the payloads they receive.
+    pub fn activate(&self) {
+        self.active.send_replace(true);
+    }
+
+    /// Shut down every shard in this shard set and wait for them to close.
+    pub async fn shutdown(mut self, kind: ShutdownKind) {
+        self.shutdown.send_replace(Some(kind));
+
+        // wait for all tasks to complete
+        while self.tasks.join_next().await.is_some() {}
+    }
+}
+
+/// Close the gateway connection of a shard.
+async fn close<Q>(shard: &mut Shard<Q>, kind: ShutdownKind)
+where
+    Q: Queue + Unpin,
+{
+    let shard_id = shard.id();
+    let resumable = sessions::resumable(shard);
+
+    shard.close(match kind {
+        // close with a non-1000 close code such that the session is not invalidated
+        ShutdownKind::Restart => CloseFrame::RESUME,
+        ShutdownKind::Retire => CloseFrame::NORMAL,
+    });
+    while let Some(result) = shard.next().await {
+        if let Ok(GatewayMessage::Close(_)) = result {
+            break;
+        }
+    }
+
+    let (ShutdownKind::Restart, Some(resumable)) = (kind, resumable) else {
+        return;
+    };
+
+    if let Err(error) = sessions::persist(shard_id, resumable).await {
+        log::warn!(
+            "[shard {shard_id}] failed to persist gateway session: {error:?}",
+            shard_id = shard_id.number()
+        );
+    }
+}
@@ -0,0 +1,153 @@
+/*
+ * SPDX-License-Identifier: AGPL-3.0-only
+ *
+ * This file is part of HarTex.
+ *
+ * HarTex
+ * Copyright (c) 2021-2024 HarTex Project Developers
+ *
+ * HarTex is free software; you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation; either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * HarTex is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License along
+ * with HarTex. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! # Command Cooldowns
+//!
+//! The uses of commands with cooldowns are counted in the database for each bucket, such that
+//! cooldowns are shared across workers and survive restarts.
+
+use std::pin::Pin;
+
+use hartex_database_queries::discord_frontend::queries::command_cooldown_delete_expired::command_cooldown_delete_expired;
+use hartex_database_queries::discord_frontend::queries::command_cooldown_hit::command_cooldown_hit;
+use hartex_discord_commands_core::cooldown::Cooldown;
+use hartex_discord_commands_core::cooldown::CooldownScope;
+use hartex_discord_commands_core::traits::CommandMetadata;
+use hartex_discord_configuration_provider::ConfigurationProvider;
+use hartex_discord_core::discord::model::application::interaction::Interaction;
+use hartex_discord_utils::DATABASE_POOL;
+use hartex_log::log;
+use miette::IntoDiagnostic;
+use time::OffsetDateTime;
+use tokio_postgres::GenericClient;
+
+/// Record a use of a command for an interaction, returning the number of seconds until the
+/// command can be used again if it is on cooldown.
+///
+/// The cooldown declared by the command is overridden by the configuration of the guild the
+/// interaction was invoked in, if any.
+pub async fn hit<M>(metadata: &M, interaction: &Interaction) -> miette::Result<Option<u64>>
+where
+    M: CommandMetadata + Sync + ?Sized,
+{
+    let Some(cooldown) = cooldown(metadata, interaction).await? else {
+        return Ok(None);
+    };
+
+    let Some(bucket) = bucket(cooldown.scope(), interaction) else {
+        return Ok(None);
+    };
+
+    let pinned = Pin::static_ref(&DATABASE_POOL).await;
+    let pooled = pinned.get().await.into_diagnostic()?;
+    let client = pooled.client();
+
+    #[allow(clippy::cast_possible_wrap)]
+    let hit = command_cooldown_hit()
+        .bind(
+            client,
+            &metadata.name(),
+            &bucket,
+            &(cooldown.duration().as_secs() as i64),
+        )
+        .one()
+        .await
+        .into_diagnostic()?;
+    if hit.uses <= i64::from(cooldown.burst_size()) {
+        return Ok(None);
+    }
+
+    let remaining = (hit.expires_at - OffsetDateTime::now_utc())
+        .as_seconds_f64()
+        .ceil();
+    log::trace!(
+        "command {} on cooldown for bucket {bucket} for {remaining} seconds",
+        metadata.name()
+    );
+
+    #[allow(clippy::cast_possible_truncation)]
+    #[allow(clippy::cast_sign_loss)]
+    Ok(Some(remaining.max(1.0) as u64))
+}
+
+/// Remove the buckets whose windows have expired.
+pub async fn prune() -> miette::Result<()> {
+    let pinned = Pin::static_ref(&DATABASE_POOL).await;
+    let pooled = pinned.get().await.into_diagnostic()?;
+    let client = pooled.client();
+
+    let deleted = command_cooldown_delete_expired()
+        .bind(client)
+        .await
+        .into_diagnostic()?;
+    log::debug!("pruned {deleted} expired cooldown buckets");
+
+    Ok(())
+}
+
+/// The cooldown of a command for an interaction, after applying the overrides configured by the
+/// guild the interaction was invoked in.
+async fn cooldown<M>(metadata: &M, interaction: &Interaction) -> miette::Result<Option<Cooldown>>
+where
+    M: CommandMetadata + Sync + ?Sized,
+{
+    let declared = metadata.cooldown();
+    let Some(guild_id) = interaction.guild_id else {
+        return Ok(declared);
+    };
+
+    let Some(overrides) =
+        ConfigurationProvider::command_cooldown(guild_id, metadata.name()).await?
+    else {
+        return Ok(declared);
+    };
+
+    Ok(Cooldown::overridden(
+        declared,
+        overrides.per.as_deref().and_then(CooldownScope::from_name),
+        overrides
+            .duration
+            .and_then(|duration| u64::try_from(duration).ok()),
+        overrides.burst.and_then(|burst| u32::try_from(burst).ok()),
+    ))
+}
+
+/// The key of the bucket of an interaction for a cooldown scope.
+///
+/// Guild cooldowns apply to each channel outside of guilds.
+fn bucket(scope: CooldownScope, interaction: &Interaction) -> Option<String> {
+    let channel = || {
+        interaction
+            .channel
+            .as_ref()
+            .map(|channel| format!("channel:{}", channel.id))
+    };
+
+    match scope {
+        CooldownScope::User => interaction.author_id().map(|id| format!("user:{id}")),
+        CooldownScope::Channel => channel(),
+        CooldownScope::Guild => interaction
+            .guild_id
+            .map(|id| format!("guild:{id}"))
+            .or_else(channel),
+    }
+}
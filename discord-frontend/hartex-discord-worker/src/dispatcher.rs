@@ -0,0 +1,286 @@
+/*
+ * SPDX-License-Identifier: AGPL-3.0-only
+ *
+ * This file is part of HarTex.
+ *
+ * HarTex
+ * Copyright (c) 2021-2024 HarTex Project Developers
+ *
+ * HarTex is free software; you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation; either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * HarTex is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License along
+ * with HarTex. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! # Event Dispatcher
+//!
+//! The dispatcher processes events concurrently, up to a configurable number of events at once.
+//! Events of the same guild are processed in the order they were received, one after another, by
+//! a queue of the guild that lives as long as it has events to process. As records are keyed by
+//! their shard, and every event of a guild is received by the same shard, Kafka delivers the events
+//! of a guild in order to begin with.
+//!
+//! Events only take up a share of the concurrency limit while being processed, such that events
+//! waiting behind earlier events of their guild do not hold up the events of other guilds. Once
+//! the queue of a guild is full, receiving the next event of the guild waits until an event of the
+//! guild has been processed, such that no more messages are consumed from Kafka than can be
+//! processed.
+//!
+//! The offset of the record of an event is committed once the event is processed, or once the
+//! record is published to the dead-letter topic if the event fails to be handled.
+
+use std::collections::HashMap;
+use std::env;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use hartex_discord_core::discord::model::gateway::event::DispatchEvent;
+use hartex_discord_core::discord::model::gateway::event::Event;
+use hartex_discord_core::discord::model::gateway::event::GatewayEvent;
+use hartex_discord_core::discord::model::gateway::ShardId;
+use hartex_discord_core::discord::model::id::marker::GuildMarker;
+use hartex_discord_core::discord::model::id::Id;
+use hartex_discord_core::shutdown::InFlightGuard;
+use hartex_discord_core::shutdown::ShutdownController;
+use hartex_discord_core::tokio;
+use hartex_discord_core::tokio::sync::mpsc;
+use hartex_discord_core::tokio::sync::mpsc::Receiver;
+use hartex_discord_core::tokio::sync::mpsc::Sender;
+use hartex_discord_core::tokio::sync::OwnedSemaphorePermit;
+use hartex_discord_core::tokio::sync::Semaphore;
+use hartex_kafka_utils::deadletter::DeadLetterProducer;
+use hartex_kafka_utils::deadletter::DeadLetterStage;
+use hartex_kafka_utils::offsets::OffsetCommitter;
+use hartex_kafka_utils::offsets::OffsetCommitterContext;
+use hartex_log::log;
+use miette::IntoDiagnostic;
+use miette::Report;
+use rdkafka::consumer::StreamConsumer;
+use rdkafka::error::KafkaError;
+use rdkafka::message::OwnedMessage;
+
+use crate::eventcallback::EventHandlerRegistry;
+
+/// The number of events of a guild that can wait to be processed before receiving further events
+/// of the guild waits.
+const GUILD_QUEUE_CAPACITY: usize = 64;
+
+/// An event waiting to be processed.
+struct Job {
+    event: GatewayEvent,
+    message: OwnedMessage,
+    shard: ShardId,
+    _in_flight: InFlightGuard,
+}
+
+/// The queues of the guilds with events being processed.
+type GuildQueues = Arc<Mutex<HashMap<Id<GuildMarker>, Sender<Job>>>>;
+
+/// Processes events, publishing the records of events that fail to be handled to the dead-letter
+/// topic and committing the offsets of the records of processed events.
+struct Processor {
+    committer: Arc<OffsetCommitter<StreamConsumer<OffsetCommitterContext>>>,
+    dead_letters: DeadLetterProducer,
+    registry: EventHandlerRegistry,
+    semaphore: Arc<Semaphore>,
+}
+
+/// Dispatches events to the event handler registry concurrently.
+pub struct Dispatcher {
+    processor: Arc<Processor>,
+    queues: GuildQueues,
+    shutdown: ShutdownController,
+}
+
+impl Dispatcher {
+    /// Construct a dispatcher, processing up to the number of events specified by the
+    /// `WORKER_CONCURRENCY` environment variable at once, which must be at least 1.
+    ///
+    /// Events that fail to be handled are published to the dead-letter topic, and the offsets of
+    /// the records of processed events are committed with the committer. Events are tracked as
+    /// in flight from when they are dispatched until they are processed, such that they can be
+    /// waited for on shutdown.
+    pub fn new(
+        registry: EventHandlerRegistry,
+        dead_letters: DeadLetterProducer,
+        committer: Arc<OffsetCommitter<StreamConsumer<OffsetCommitterContext>>>,
+        shutdown: ShutdownController,
+    ) -> miette::Result<Self> {
+        let concurrency = env::var("WORKER_CONCURRENCY")
+            .into_diagnostic()?
+            .parse::<usize>()
+            .into_diagnostic()?;
+        if concurrency == 0 {
+            return Err(Report::msg(
+                "WORKER_CONCURRENCY must be at least 1, as no event would be processed otherwise",
+            ));
+        }
+        log::info!("processing up to {concurrency} events concurrently");
+
+        Ok(Self {
+            processor: Arc::new(Processor {
+                committer,
+                dead_letters,
+                registry,
+                semaphore: Arc::new(Semaphore::new(concurrency)),
+            }),
+            queues: Arc::new(Mutex::new(HashMap::new())),
+            shutdown,
+        })
+    }
+
+    /// Dispatch an event, waiting until the number of events being processed is below the limit
+    /// if the event is not of a guild, or until the queue of its guild is not full otherwise.
+    ///
+    /// The record the event was received in is kept until the event is processed, in case it
+    /// has to be published to the dead-letter topic, and for committing its offset.
+    pub async fn dispatch(&self, event: GatewayEvent, message: OwnedMessage, shard: ShardId) {
+        let (event, guild_id) = match event {
+            GatewayEvent::Dispatch(sequence, dispatch) => {
+                let event = Event::from(dispatch);
+                let guild_id = event.guild_id();
+
+                // every event converted from a dispatch event converts back into one
+                let dispatch = DispatchEvent::try_from(event).unwrap();
+                (GatewayEvent::Dispatch(sequence, dispatch), guild_id)
+            }
+            event => (event, None),
+        };
+        let mut job = Job {
+            event,
+            message,
+            shard,
+            _in_flight: self.shutdown.track(),
+        };
+
+        let Some(guild_id) = guild_id else {
+            let permit = self.processor.acquire().await;
+            let processor = self.processor.clone();
+            tokio::spawn(async move { processor.process(job, permit).await });
+
+            return;
+        };
+
+        loop {
+            let tx = {
+                let mut queues = self.queues.lock().unwrap();
+                let Some(tx) = queues.get(&guild_id) else {
+                    let (tx, rx) = mpsc::channel(GUILD_QUEUE_CAPACITY);
+                    if let Err(error) = tx.try_send(job) {
+                        log::warn!("skipping, send failed: {error:?}");
+                    }
+                    queues.insert(guild_id, tx);
+
+                    tokio::spawn(process_queue(
+                        guild_id,
+                        rx,
+                        self.queues.clone(),
+                        self.processor.clone(),
+                    ));
+
+                    return;
+                };
+
+                tx.clone()
+            };
+
+            if tx.capacity() == 0 {
+                log::debug!(
+                    "queue of guild {guild_id} is full; waiting for its events to be processed"
+                );
+            }
+
+            let Ok(permit) = tx.clone().reserve_owned().await else {
+                continue;
+            };
+
+            // the queue of the guild only closes while the lock is held, so the event is processed
+            // if the queue is still the queue of the guild; otherwise, the event is queued again
+            let queues = self.queues.lock().unwrap();
+            if queues
+                .get(&guild_id)
+                .is_some_and(|queue| queue.same_channel(&tx))
+            {
+                permit.send(job);
+
+                return;
+            }
+        }
+    }
+}
+
+/// Process the events of a guild in order, until there are no events left.
+async fn process_queue(
+    guild_id: Id<GuildMarker>,
+    mut rx: Receiver<Job>,
+    queues: GuildQueues,
+    processor: Arc<Processor>,
+) {
+    loop {
+        let job = if let Ok(job) = rx.try_recv() {
+            job
+        } else {
+            let mut queues = queues.lock().unwrap();
+
+            // events may have been queued before the lock was acquired
+            let Ok(job) = rx.try_recv() else {
+                rx.close();
+                queues.remove(&guild_id);
+
+                return;
+            };
+
+            job
+        };
+
+        let permit = processor.acquire().await;
+        processor.process(job, permit).await;
+    }
+}
+
+impl Processor {
+    /// Wait until the number of events being processed is below the limit, returning the share of
+    /// the limit taken up by the event to be processed.
+    async fn acquire(&self) -> OwnedSemaphorePermit {
+        if self.semaphore.available_permits() == 0 {
+            log::debug!("concurrency limit reached; waiting for events to be processed");
+        }
+
+        // the semaphore is never closed
+        self.semaphore.clone().acquire_owned().await.unwrap()
+    }
+
+    /// Process an event, publishing its record to the dead-letter topic if any event handler
+    /// fails.
+    ///
+    /// Publishing is retried until it succeeds; the offset of the record is only left
+    /// uncommitted if shutdown is requested before then, such that the event is consumed again
+    /// when the worker restarts.
+    async fn process(&self, job: Job, _permit: OwnedSemaphorePermit) {
+        let failures = crate::eventcallback::invoke(job.event, job.shard, &self.registry).await;
+
+        if !failures.is_empty() {
+            if let Err(error) = self
+                .dead_letters
+                .publish(&job.message, DeadLetterStage::Handle, failures.join("\n"))
+                .await
+            {
+                println!("{:?}", Err::<(), KafkaError>(error).into_diagnostic());
+
+                return;
+            }
+        }
+
+        if let Err(error) = self.committer.complete(&job.message) {
+            println!("{:?}", Err::<(), KafkaError>(error).into_diagnostic());
+        }
+    }
+}
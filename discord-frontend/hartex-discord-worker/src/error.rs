@@ -37,6 +37,11 @@ impl Display for ConsumerError {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self.kind {
             ConsumerErrorKind::InvalidGatewayPayload => f.write_str("invalid gateway payload"),
+            ConsumerErrorKind::InvalidShard {
+                shard_id,
+                shard_count,
+            } => write!(f, "payload from invalid shard {shard_id}/{shard_count}"),
+            ConsumerErrorKind::MissingHeaders => f.write_str("payload without headers"),
         }
     }
 }
@@ -47,4 +52,6 @@ impl Error for ConsumerError {}
 #[derive(Clone, Debug)]
 pub enum ConsumerErrorKind {
     InvalidGatewayPayload,
+    InvalidShard { shard_id: u32, shard_count: u32 },
+    MissingHeaders,
 }
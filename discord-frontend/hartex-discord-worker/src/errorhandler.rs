@@ -24,12 +24,27 @@
 //!
 //! This module defines handlers for errors and panics.
 
+use std::any::Any;
+use std::backtrace::Backtrace;
+use std::cell::RefCell;
 use std::env;
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::panic;
+use std::pin::Pin;
 use std::str::FromStr;
 
-use chrono::Utc;
-use hartex_discord_core::discord::http::client::InteractionClient;
-use hartex_discord_core::discord::model::gateway::payload::incoming::InteractionCreate;
+use hartex_database_queries::discord_frontend::queries::error_report_insert::error_report_insert;
+use hartex_discord_commands_core::response::ResponseContext;
+use hartex_discord_core::discord::model::application::interaction::application_command::CommandData;
+use hartex_discord_core::discord::model::application::interaction::application_command::CommandDataOption;
+use hartex_discord_core::discord::model::application::interaction::application_command::CommandOptionValue;
+use hartex_discord_core::discord::model::application::interaction::Interaction;
+use hartex_discord_core::discord::model::application::interaction::InteractionData;
+use hartex_discord_core::discord::model::gateway::event::EventType;
+use hartex_discord_core::discord::model::gateway::ShardId;
+use hartex_discord_core::discord::model::http::attachment::Attachment;
 use hartex_discord_core::discord::model::id::marker::ChannelMarker;
 use hartex_discord_core::discord::model::id::Id;
 use hartex_discord_core::discord::util::builder::embed::EmbedBuilder;
@@ -37,112 +52,471 @@ use hartex_discord_core::discord::util::builder::embed::EmbedFieldBuilder;
 use hartex_discord_utils::interaction::ephemeral_error_response;
 use hartex_discord_utils::markdown::MarkdownStyle;
 use hartex_discord_utils::CLIENT;
+use hartex_discord_utils::DATABASE_POOL;
+use hartex_localization_core::Localizer;
+use hartex_localization_core::LOCALIZATION_HOLDER;
 use hartex_log::log;
+use miette::IntoDiagnostic;
 use miette::Report;
 use sha2::Digest;
 use sha2::Sha224;
+use time::OffsetDateTime;
+use tokio_postgres::GenericClient;
+
+/// The maximum number of characters of an error that is displayed in the error log, such that it
+/// fits in the description of an embed.
+const ERROR_MAX_LENGTH: usize = 4000;
+
+/// The maximum number of characters of the options of a command that are displayed in the error
+/// log, such that they fit in a field of an embed.
+const OPTIONS_MAX_LENGTH: usize = 1000;
+
+thread_local! {
+    /// The backtrace captured when the last panic on the current thread was raised.
+    static PANIC_BACKTRACE: RefCell<Option<Backtrace>> = const { RefCell::new(None) };
+}
+
+/// Install a panic hook capturing a backtrace whenever a panic is raised, such that the backtrace
+/// can be reported along with the panic once it is caught.
+///
+/// Panics are caught on the thread raising them, hence the backtrace is kept per thread.
+pub fn install_panic_hook() {
+    let default_hook = panic::take_hook();
+
+    panic::set_hook(Box::new(move |info| {
+        PANIC_BACKTRACE.with(|backtrace| backtrace.replace(Some(Backtrace::force_capture())));
+
+        default_hook(info);
+    }));
+}
 
 /// This function handle errors from an interaction. It does the following things:
 ///
 /// (1) generate a unique error code;
-/// (2) send a message to a designated channel for error logs in the support server with the error code; and
-/// (3) responds to the interaction with the error message with the error code.
+/// (2) store the error report with the error code in the database, such that it can be looked up
+/// later;
+/// (3) send a message to a designated channel for error logs in the support server with the error
+/// code, the context of the interaction and the backtrace of a panic; and
+/// (4) responds to the interaction with the localized error message with the error code, as a
+/// followup message if the interaction has already been acknowledged.
+///
+/// Failures in any of these steps are logged, such that they do not prevent the other steps.
 pub async fn handle_interaction_error(
     payload: ErrorPayload,
-    interaction_create: Box<InteractionCreate>,
-    interaction_client: &InteractionClient<'_>,
+    interaction: &Interaction,
+    shard: ShardId,
+    response: &ResponseContext,
+) {
+    let timestamp = OffsetDateTime::now_utc();
+
+    let (kind, error_kind, title, color, message, backtrace) = match payload {
+        ErrorPayload::Miette(report) => (
+            "error",
+            "unexpected",
+            "Unexpected Error",
+            0xFF_99_33,
+            strip_ansi_escapes::strip_str(format!("{report:?}")),
+            None,
+        ),
+        ErrorPayload::Panic { message, backtrace } => (
+            "panic",
+            "critical",
+            "Critical Error",
+            0xFF_33_33,
+            strip_ansi_escapes::strip_str(message),
+            backtrace.map(|backtrace| backtrace.to_string()),
+        ),
+    };
+
+    let hash = error_code(&message, timestamp);
+
+    if kind == "panic" {
+        log::error!("interaction command panicked: {message:?}; error hash: {hash}");
+    } else {
+        log::warn!("command errorred: {message:?}; error hash: {hash}");
+    }
+
+    let locale = interaction.locale.as_deref().unwrap_or("en-GB");
+    let localizer = Localizer::new(&LOCALIZATION_HOLDER, locale);
+    if let Err(error) = response
+        .respond(ephemeral_error_response(error_message(
+            &localizer, error_kind, &hash,
+        )))
+        .await
+    {
+        log::warn!("cannot respond to interaction with error {hash}: {error:?}");
+    }
+
+    let context = InteractionContext::from(interaction);
+
+    if let Err(error) = store_report(
+        &hash,
+        kind,
+        &message,
+        &context,
+        interaction,
+        shard,
+        timestamp,
+    )
+    .await
+    {
+        log::warn!("cannot store error report {hash}: {error:?}");
+    }
+
+    if let Err(error) = report_interaction_error(
+        &hash,
+        title,
+        color,
+        &message,
+        backtrace,
+        &context,
+        interaction,
+        shard,
+    )
+    .await
+    {
+        log::warn!("cannot report error {hash} to the error channel: {error:?}");
+    }
+}
+
+/// This function handle errors from an event handler. It does the following things:
+///
+/// (1) generate a unique error code; and
+/// (2) send a message to a designated channel for error logs in the support server with the error code.
+pub async fn handle_event_error(
+    payload: ErrorPayload,
+    handler: String,
+    event_type: EventType,
+    shard: ShardId,
 ) {
+    let (title, color, message) = match payload {
+        ErrorPayload::Miette(report) => (
+            "Event Handler Error",
+            0xFF_99_33,
+            strip_ansi_escapes::strip_str(report.to_string()),
+        ),
+        ErrorPayload::Panic { message, .. } => (
+            "Event Handler Panic",
+            0xFF_33_33,
+            strip_ansi_escapes::strip_str(message),
+        ),
+    };
+    let event_type = event_type.name().unwrap_or("UNKNOWN");
+    let hash = error_code(&message, OffsetDateTime::now_utc());
+
+    log::error!(
+        "event handler {handler} failed to handle {event_type} event on shard {}: {message:?}; error hash: {hash}",
+        shard.number()
+    );
+
+    let fields = vec![
+        EmbedFieldBuilder::new("Event Handler", handler.clone().discord_inline_code()).inline(),
+        EmbedFieldBuilder::new("Event", event_type.to_string().discord_inline_code()).inline(),
+        EmbedFieldBuilder::new("Shard", shard.to_string().discord_inline_code()).inline(),
+    ];
+
+    if let Err(error) = report_error(&hash, title, color, &message, None, fields).await {
+        log::warn!("cannot report error {hash} of event handler {handler}: {error:?}");
+    }
+}
+
+/// Generate a unique error code from the error message, the time at which the error occurred and
+/// a random component, such that errors with the same message occurring at the same time are not
+/// given the same code.
+fn error_code(message: &str, timestamp: OffsetDateTime) -> String {
     let mut hasher = Sha224::new();
+    hasher.update(message.as_bytes());
+    hasher.update(timestamp.unix_timestamp().to_string().as_bytes());
+    hasher.update(rand::random::<u64>().to_le_bytes());
+
+    let output = hasher.finalize();
+    output.map(|int| format!("{int:x}")).join("")
+}
+
+/// Localize the error message responded to the user encountering an error, falling back to the
+/// message in English if it cannot be localized.
+fn error_message(localizer: &Localizer<'_>, error_kind: &str, hash: &str) -> String {
+    let lines = localizer
+        .error_error_line_one(error_kind.to_string())
+        .and_then(|line_one| Ok((line_one, localizer.error_error_line_two()?)));
 
-    let channel_id_str = env::var("ERROR_CHANNEL_ID").unwrap();
-    let channel_id = Id::<ChannelMarker>::from_str(channel_id_str.as_str()).unwrap();
-
-    match payload {
-        ErrorPayload::Miette(report) => {
-            let report = strip_ansi_escapes::strip_str(report.to_string());
-
-            hasher.update(report.as_bytes());
-            hasher.update(Utc::now().timestamp().to_string().as_bytes());
-
-            let output = hasher.finalize();
-            let hash = output.map(|int| format!("{int:x}")).join("");
-            interaction_client
-                .create_response(
-                    interaction_create.id,
-                    &interaction_create.token,
-                    &ephemeral_error_response(format!(
-                        ":x: This command encountered an unexpected error. Please provide the following error code for support.\n\nError code: {}", hash.clone().discord_inline_code()
-                    ))
+    let (line_one, line_two) =
+        match lines {
+            Ok(lines) => lines,
+            Err(error) => {
+                log::warn!("cannot localize error message: {error:?}");
+
+                (
+                    format!(
+                    ":x: This command encountered {} error. Please provide the following error \
+                     code for support.",
+                    if error_kind == "critical" { "a critical" } else { "an unexpected" }
+                ),
+                    String::from("Error code:"),
                 )
-                .await
-                .unwrap();
-
-            let embed = EmbedBuilder::new()
-                .color(0xFF_99_33)
-                .title("Unexpected Error")
-                .field(EmbedFieldBuilder::new(
-                    "Error Hash",
-                    hash.clone().discord_inline_code(),
-                ))
-                .field(EmbedFieldBuilder::new(
-                    "Error",
-                    report.clone().discord_codeblock(),
-                ))
-                .validate()
-                .unwrap()
-                .build();
-
-            CLIENT
-                .create_message(channel_id)
-                .embeds(&[embed])
-                .await
-                .unwrap();
-
-            log::warn!("command errorred: {report:?}; error hash: {hash}");
+            }
+        };
+
+    format!(
+        "{line_one}\n\n{line_two} {}",
+        hash.to_string().discord_inline_code()
+    )
+}
+
+/// Send a message with the error report of an interaction to a designated channel for error logs
+/// in the support server.
+#[allow(clippy::too_many_arguments)]
+async fn report_interaction_error(
+    hash: &str,
+    title: &str,
+    color: u32,
+    message: &str,
+    backtrace: Option<String>,
+    context: &InteractionContext,
+    interaction: &Interaction,
+    shard: ShardId,
+) -> miette::Result<()> {
+    let or_none = |value: Option<String>| {
+        value.map_or_else(|| String::from("None"), MarkdownStyle::discord_inline_code)
+    };
+    let options = if context.options.is_empty() {
+        String::from("None")
+    } else {
+        context
+            .options
+            .join("\n")
+            .chars()
+            .take(OPTIONS_MAX_LENGTH)
+            .collect::<String>()
+            .discord_codeblock()
+    };
+
+    let fields = vec![
+        EmbedFieldBuilder::new("Command", or_none(context.command.clone())).inline(),
+        EmbedFieldBuilder::new("Shard", shard.to_string().discord_inline_code()).inline(),
+        EmbedFieldBuilder::new(
+            "Server",
+            or_none(interaction.guild_id.map(|id| id.to_string())),
+        )
+        .inline(),
+        EmbedFieldBuilder::new(
+            "Channel",
+            or_none(
+                interaction
+                    .channel
+                    .as_ref()
+                    .map(|channel| channel.id.to_string()),
+            ),
+        )
+        .inline(),
+        EmbedFieldBuilder::new(
+            "User",
+            or_none(interaction.author_id().map(|id| id.to_string())),
+        )
+        .inline(),
+        EmbedFieldBuilder::new("Options", options),
+    ];
+
+    report_error(hash, title, color, message, backtrace, fields).await
+}
+
+/// Send a message with an error report to a designated channel for error logs in the support
+/// server, with the error code and the given fields describing where the error occurred.
+///
+/// The full error and the backtrace are attached as a file, as they may not fit in an embed.
+async fn report_error(
+    hash: &str,
+    title: &str,
+    color: u32,
+    message: &str,
+    backtrace: Option<String>,
+    fields: Vec<EmbedFieldBuilder>,
+) -> miette::Result<()> {
+    let channel_id = env::var("ERROR_CHANNEL_ID").into_diagnostic()?;
+    let channel_id = Id::<ChannelMarker>::from_str(&channel_id).into_diagnostic()?;
+
+    let mut embed = EmbedBuilder::new()
+        .color(color)
+        .title(title)
+        .description(
+            message
+                .chars()
+                .take(ERROR_MAX_LENGTH)
+                .collect::<String>()
+                .discord_codeblock(),
+        )
+        .field(EmbedFieldBuilder::new(
+            "Error Hash",
+            hash.to_string().discord_inline_code(),
+        ));
+    for field in fields {
+        embed = embed.field(field);
+    }
+    let embed = embed.validate().into_diagnostic()?.build();
+
+    let mut file = message.to_string();
+    if let Some(backtrace) = backtrace {
+        file.push_str("\n\nBacktrace:\n");
+        file.push_str(&backtrace);
+    }
+    let attachment = Attachment::from_bytes(format!("{hash}.txt"), file.into_bytes(), 0);
+
+    CLIENT
+        .create_message(channel_id)
+        .embeds(&[embed])
+        .attachments(&[attachment])
+        .await
+        .into_diagnostic()?;
+
+    Ok(())
+}
+
+/// Store an error report of an interaction in the database, along with the command and the
+/// options that the interaction was invoked with.
+async fn store_report(
+    code: &str,
+    kind: &str,
+    report: &str,
+    context: &InteractionContext,
+    interaction: &Interaction,
+    shard: ShardId,
+    timestamp: OffsetDateTime,
+) -> miette::Result<()> {
+    let options = match &interaction.data {
+        Some(InteractionData::ApplicationCommand(command)) => {
+            Some(serde_json::to_string(&command.options).into_diagnostic()?)
         }
-        ErrorPayload::Panic(message) => {
-            let message = strip_ansi_escapes::strip_str(message);
-
-            hasher.update(message.as_bytes());
-            hasher.update(Utc::now().timestamp().to_string().as_bytes());
-
-            let output = hasher.finalize();
-            let hash = output.map(|int| format!("{int:x}")).join("");
-
-            interaction_client
-                .create_response(
-                    interaction_create.id,
-                    &interaction_create.token,
-                    &ephemeral_error_response(format!(
-                        ":x: This command encountered an critical error. Please provide the following error code for support.\n\nError code: {}", hash.clone().discord_inline_code()
-                    )),
-                )
-                .await
-                .unwrap();
-
-            let embed = EmbedBuilder::new()
-                .color(0xFF_33_33)
-                .title("Critical Error")
-                .field(EmbedFieldBuilder::new(
-                    "Error Hash",
-                    hash.clone().discord_inline_code(),
-                ))
-                .field(EmbedFieldBuilder::new(
-                    "Error",
-                    message.clone().discord_codeblock(),
-                ))
-                .validate()
-                .unwrap()
-                .build();
-
-            CLIENT
-                .create_message(channel_id)
-                .embeds(&[embed])
-                .await
-                .unwrap();
-
-            log::error!("interaction command panicked: {message:?}; error hash: {hash}");
+        Some(InteractionData::MessageComponent(component)) => {
+            Some(serde_json::to_string(&component.values).into_diagnostic()?)
         }
+        Some(InteractionData::ModalSubmit(modal)) => {
+            Some(serde_json::to_string(&modal.components).into_diagnostic()?)
+        }
+        _ => None,
+    };
+    let user_id = interaction.author_id().map(|id| id.to_string());
+
+    let pinned = Pin::static_ref(&DATABASE_POOL).await;
+    let pooled = pinned.get().await.into_diagnostic()?;
+    let client = pooled.client();
+
+    error_report_insert()
+        .bind(
+            client,
+            &code,
+            &kind,
+            &report,
+            &context.command,
+            &options,
+            &interaction.guild_id.map(|id| id.to_string()),
+            &user_id,
+            &i64::from(shard.number()),
+            &i64::from(shard.total()),
+            &timestamp,
+        )
+        .await
+        .into_diagnostic()?;
+
+    Ok(())
+}
+
+/// The command and the options an interaction was invoked with, described for error reports.
+struct InteractionContext {
+    /// The path of the command, including its subcommand group and subcommand, or the custom ID of
+    /// the component or modal.
+    command: Option<String>,
+    /// The options of the command, with the names of the entities they resolve to, or the values of
+    /// the component or the fields of the modal.
+    options: Vec<String>,
+}
+
+impl From<&Interaction> for InteractionContext {
+    fn from(interaction: &Interaction) -> Self {
+        match &interaction.data {
+            Some(InteractionData::ApplicationCommand(command)) => {
+                let mut path = vec![command.name.clone()];
+                let mut options = Vec::new();
+                describe_options(command, &command.options, &mut path, &mut options);
+
+                Self {
+                    command: Some(path.join(" ")),
+                    options,
+                }
+            }
+            Some(InteractionData::MessageComponent(component)) => Self {
+                command: Some(component.custom_id.clone()),
+                options: component.values.clone(),
+            },
+            Some(InteractionData::ModalSubmit(modal)) => Self {
+                command: Some(modal.custom_id.clone()),
+                options: modal
+                    .components
+                    .iter()
+                    .flat_map(|row| &row.components)
+                    .map(|field| {
+                        format!(
+                            "{}: {}",
+                            field.custom_id,
+                            field.value.as_deref().unwrap_or_default()
+                        )
+                    })
+                    .collect(),
+            },
+            _ => Self {
+                command: None,
+                options: Vec::new(),
+            },
+        }
+    }
+}
+
+/// Describe the options of a command, appending subcommand groups and subcommands to the path of
+/// the command and resolving the entities the other options refer to.
+fn describe_options(
+    command: &CommandData,
+    options: &[CommandDataOption],
+    path: &mut Vec<String>,
+    described: &mut Vec<String>,
+) {
+    let resolved = command.resolved.as_ref();
+
+    for option in options {
+        let value = match &option.value {
+            CommandOptionValue::SubCommand(options)
+            | CommandOptionValue::SubCommandGroup(options) => {
+                path.push(option.name.clone());
+                describe_options(command, options, path, described);
+
+                continue;
+            }
+            CommandOptionValue::Attachment(id) => resolved
+                .and_then(|resolved| resolved.attachments.get(id))
+                .map_or_else(
+                    || id.to_string(),
+                    |attachment| format!("{} ({id})", attachment.filename),
+                ),
+            CommandOptionValue::Boolean(value) => value.to_string(),
+            CommandOptionValue::Channel(id) => resolved
+                .and_then(|resolved| resolved.channels.get(id))
+                .map_or_else(
+                    || id.to_string(),
+                    |channel| format!("#{} ({id})", channel.name),
+                ),
+            CommandOptionValue::Focused(value, _) | CommandOptionValue::String(value) => {
+                value.clone()
+            }
+            CommandOptionValue::Integer(value) => value.to_string(),
+            CommandOptionValue::Mentionable(id) => id.to_string(),
+            CommandOptionValue::Number(value) => value.to_string(),
+            CommandOptionValue::Role(id) => resolved
+                .and_then(|resolved| resolved.roles.get(id))
+                .map_or_else(|| id.to_string(), |role| format!("@{} ({id})", role.name)),
+            CommandOptionValue::User(id) => resolved
+                .and_then(|resolved| resolved.users.get(id))
+                .map_or_else(|| id.to_string(), |user| format!("@{} ({id})", user.name)),
+        };
+
+        described.push(format!("{}: {value}", option.name));
     }
 }
 
@@ -151,5 +525,38 @@ pub enum ErrorPayload {
     /// A `miette` report payload.
     Miette(Report),
     /// A panic message payload.
-    Panic(String),
+    Panic {
+        /// The panic message.
+        message: String,
+        /// The backtrace captured when the panic was raised, if the panic hook is installed.
+        backtrace: Option<Backtrace>,
+    },
+}
+
+impl ErrorPayload {
+    /// Constructs a panic message payload from a caught panic, along with the backtrace captured
+    /// when it was raised.
+    ///
+    /// This must be called on the thread catching the panic.
+    pub fn from_panic(error: &(dyn Any + Send)) -> Self {
+        let message = error
+            .downcast_ref::<String>()
+            .cloned()
+            .or_else(|| error.downcast_ref::<&str>().map(ToString::to_string))
+            .unwrap_or_default();
+
+        Self::Panic {
+            message,
+            backtrace: PANIC_BACKTRACE.with(RefCell::take),
+        }
+    }
+}
+
+impl Display for ErrorPayload {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Miette(report) => write!(f, "{report}"),
+            Self::Panic { message, .. } => write!(f, "panicked: {message}"),
+        }
+    }
 }
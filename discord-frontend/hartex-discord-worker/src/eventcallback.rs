@@ -20,155 +20,171 @@
  * with HarTex. If not, see <https://www.gnu.org/licenses/>.
  */
 
-use std::env;
+use std::collections::HashMap;
 use std::panic::AssertUnwindSafe;
-use std::time::Duration;
-use std::time::SystemTime;
+use std::sync::Arc;
 
 use futures_util::FutureExt;
-use hartex_backend_models::uptime::UptimeUpdate;
-use hartex_discord_core::discord::model::application::interaction::InteractionType;
-use hartex_discord_core::discord::model::gateway::event::DispatchEvent;
+use hartex_discord_commands_core::traits::EventHandler;
+use hartex_discord_core::discord::model::gateway::event::EventType;
 use hartex_discord_core::discord::model::gateway::event::GatewayEvent;
-use hartex_discord_core::discord::model::gateway::payload::outgoing::request_guild_members::RequestGuildMembersInfo;
-use hartex_discord_core::discord::model::gateway::payload::outgoing::RequestGuildMembers;
-use hartex_discord_core::discord::model::gateway::OpCode;
-use hartex_discord_core::tokio::net::TcpStream;
-use hartex_discord_core::tokio::spawn;
-use hartex_discord_utils::CLIENT;
+use hartex_discord_core::discord::model::gateway::ShardId;
+use hartex_kafka_utils::envelope::WORKER_EVENT_TYPES;
+use hartex_kafka_utils::lifecycle::ShardDisconnectKind;
+use hartex_kafka_utils::lifecycle::ShardLifecycleEvent;
+use hartex_kafka_utils::lifecycle::ShardLifecycleEventKind;
 use hartex_log::log;
-use hyper::client::conn::http1::handshake;
-use hyper::header::ACCEPT;
-use hyper::header::CONTENT_TYPE;
-use hyper::Method;
-use hyper::Request;
-use hyper_util::rt::TokioIo;
-use miette::IntoDiagnostic;
-use rdkafka::error::KafkaError;
+use miette::Report;
 use rdkafka::producer::FutureProducer;
-use rdkafka::producer::FutureRecord;
-use rdkafka::util::Timeout;
 
 use crate::errorhandler::ErrorPayload;
+use crate::handlers::guild_create::GuildCreateHandler;
+use crate::handlers::interaction_create::InteractionCreateHandler;
+use crate::handlers::ready::ReadyHandler;
+
+/// Lookup table for event handlers, keyed by the types of the events they subscribe to.
+///
+/// This contains the event handlers built into the worker process, as well as those provided by
+/// every plugin.
+#[allow(clippy::module_name_repetitions)]
+pub struct EventHandlerRegistry(HashMap<EventType, Vec<Arc<dyn EventHandler + Send + Sync>>>);
+
+impl EventHandlerRegistry {
+    /// Construct the registry.
+    pub fn new(producer: FutureProducer) -> miette::Result<Self> {
+        let mut handlers: Vec<Box<dyn EventHandler + Send + Sync>> = vec![
+            Box::new(GuildCreateHandler::new(producer)?),
+            Box::new(InteractionCreateHandler),
+            Box::new(ReadyHandler),
+        ];
+        if let Some(event_type) = handlers
+            .iter()
+            .flat_map(|handler| handler.event_types())
+            .find(|event_type| !WORKER_EVENT_TYPES.contains(event_type))
+        {
+            return Err(Report::msg(format!(
+                "built-in event handlers subscribe to {event_type:?} events not routed to the worker"
+            )));
+        }
+
+        handlers.extend(
+            hartex_discord_commands::plugins()
+                .iter()
+                .flat_map(|plugin| plugin.event_handlers()),
+        );
+
+        let mut map = HashMap::<EventType, Vec<Arc<dyn EventHandler + Send + Sync>>>::new();
+        for handler in handlers {
+            let handler = Arc::<dyn EventHandler + Send + Sync>::from(handler);
+            for event_type in handler.event_types() {
+                map.entry(event_type).or_default().push(handler.clone());
+            }
+        }
 
-/// Invoke a corresponding event callback for an event.
-#[allow(clippy::cast_lossless)]
+        Ok(Self(map))
+    }
+}
+
+/// Invoke the event handlers subscribing to an event.
+///
+/// Each event handler is isolated from the others; errors returned and panics raised by an event
+/// handler are reported without affecting any other event handler. The failures of the event
+/// handlers are returned, each prefixed with the name of the event handler.
+///
+/// Event handlers with side effects that must not be repeated are skipped if they have already
+/// handled the event; failing to handle the event does not count as having handled it.
 #[allow(clippy::large_futures)]
-#[allow(clippy::too_many_lines)]
 pub async fn invoke(
     event: GatewayEvent,
-    shard: u8,
-    producer: FutureProducer,
-) -> miette::Result<()> {
-    let topic = env::var("KAFKA_TOPIC_OUTBOUND_COMMUNICATION").into_diagnostic()?;
-
-    #[allow(clippy::collapsible_match)]
-    match event {
-        GatewayEvent::Dispatch(seq, dispatch) => match dispatch {
-            DispatchEvent::GuildCreate(guild_create) => {
-                log::trace!(
-                    "shard {shard} has received GUILD_CREATE payload from Discord (sequence {seq})"
-                );
-
-                let request = RequestGuildMembers {
-                    d: RequestGuildMembersInfo {
-                        guild_id: guild_create.id,
-                        limit: Some(0),
-                        nonce: None,
-                        presences: Some(true),
-                        query: Some(String::new()),
-                        user_ids: None,
-                    },
-                    op: OpCode::RequestGuildMembers,
-                };
-                let string = serde_json::to_string(&request).into_diagnostic()?;
-                if let Err((error, _)) = producer
-                    .send(
-                        FutureRecord::to(&topic)
-                            .key(&format!("OUTBOUND_REQUEST_GUILD_MEMBERS_{shard}"))
-                            .payload(&string),
-                        Timeout::After(Duration::from_secs(0)),
-                    )
+    shard: ShardId,
+    registry: &EventHandlerRegistry,
+) -> Vec<String> {
+    let mut failures = Vec::new();
+
+    let GatewayEvent::Dispatch(seq, dispatch) = event else {
+        return failures;
+    };
+
+    let event_type = dispatch.kind();
+    let Some(handlers) = registry.0.get(&event_type) else {
+        return failures;
+    };
+
+    log::trace!(
+        "shard {} has received {} payload from Discord (sequence {seq})",
+        shard.number(),
+        event_type.name().unwrap_or("UNKNOWN")
+    );
+
+    for handler in handlers {
+        let result = match crate::idempotency::claim(handler.as_ref(), &dispatch).await {
+            Ok(true) => {
+                AssertUnwindSafe(handler.handle(dispatch.clone(), shard))
+                    .catch_unwind()
                     .await
-                {
-                    println!("{:?}", Err::<(), KafkaError>(error).into_diagnostic());
-                }
-
-                Ok(())
             }
-            DispatchEvent::InteractionCreate(interaction_create)
-                if interaction_create.kind == InteractionType::ApplicationCommand =>
-            {
-                log::trace!(
-                    "shard {shard} has received INTERACTION_CREATE payload from Discord (sequence {seq})"
-                );
+            Ok(false) => continue,
+            Err(report) => Ok(Err(report)),
+        };
 
-                let interaction_client = CLIENT.interaction(interaction_create.application_id);
-
-                if let Err(error) = AssertUnwindSafe(crate::interaction::application_command(
-                    interaction_create.clone(),
-                    &interaction_client,
-                ))
-                .catch_unwind()
-                .await
-                {
-                    crate::errorhandler::handle_interaction_error(
-                        ErrorPayload::Panic(
-                            error
-                                .downcast_ref::<String>()
-                                .unwrap_or(&String::new())
-                                .to_string(),
-                        ),
-                        interaction_create,
-                        &interaction_client,
-                    )
-                    .await;
-                }
-
-                Ok(())
-            }
-            DispatchEvent::Ready(ready) => {
-                log::info!(
-                    "{}#{} (shard {shard}) has received READY payload from Discord (gateway v{}) (sequence {seq})",
-                    ready.user.name,
-                    ready.user.discriminator,
-                    ready.version
-                );
+        let payload = match result {
+            Ok(Ok(())) => continue,
+            Ok(Err(report)) => ErrorPayload::Miette(report),
+            Err(error) => ErrorPayload::from_panic(&*error),
+        };
+
+        if let Err(error) = crate::idempotency::release(handler.as_ref(), &dispatch).await {
+            println!("{error:?}");
+        }
+
+        failures.push(format!("{}: {payload}", handler.name()));
+
+        crate::errorhandler::handle_event_error(payload, handler.name(), event_type, shard).await;
+    }
 
-                let api_domain = env::var("API_DOMAIN").into_diagnostic()?;
-                let uri = format!("http://{}/api/v0110/stats/uptime", api_domain.clone());
-                let now = SystemTime::now();
-                let duration = now
-                    .duration_since(SystemTime::UNIX_EPOCH)
-                    .into_diagnostic()?;
-
-                let stream = TcpStream::connect(api_domain).await.into_diagnostic()?;
-                let (mut sender, connection) =
-                    handshake(TokioIo::new(stream)).await.into_diagnostic()?;
-
-                spawn(async move {
-                    if let Err(err) = connection.await {
-                        log::error!("TCP connection failed: {:?}", err);
-                    }
-                });
-
-                log::debug!("sending a request to {}", &uri);
-
-                let query = UptimeUpdate::new("HarTex Nightly", duration.as_secs() as u128);
-                let request = Request::builder()
-                    .uri(uri)
-                    .method(Method::PATCH)
-                    .header(ACCEPT, "application/json")
-                    .header(CONTENT_TYPE, "application/json")
-                    .body(serde_json::to_string(&query).into_diagnostic()?)
-                    .into_diagnostic()?;
-
-                sender.send_request(request).await.into_diagnostic()?;
-
-                Ok(())
+    failures
+}
+
+/// Invoke a corresponding callback for a shard lifecycle event.
+pub async fn invoke_lifecycle(event: &ShardLifecycleEvent) {
+    match &event.kind {
+        ShardLifecycleEventKind::Disconnected {
+            close_code,
+            disconnect_kind: ShardDisconnectKind::Fatal,
+            ..
+        } => log::error!(
+            "shard {}/{} went down and cannot reconnect (close code {close_code:?})",
+            event.shard_id,
+            event.shard_count
+        ),
+        ShardLifecycleEventKind::Disconnected {
+            close_code,
+            disconnect_kind,
+            ..
+        } => log::warn!(
+            "shard {}/{} was disconnected (close code {close_code:?}); {disconnect_kind:?}",
+            event.shard_id,
+            event.shard_count
+        ),
+        ShardLifecycleEventKind::HeartbeatAcknowledged {
+            average_latency,
+            latest_latency,
+        } => {
+            // latencies are reported again on the next heartbeat
+            if let Err(error) = crate::latency::record(
+                event.shard_id,
+                event.shard_count,
+                *average_latency,
+                *latest_latency,
+            )
+            .await
+            {
+                log::warn!(
+                    "failed to record latency of shard {}/{}: {error:?}",
+                    event.shard_id,
+                    event.shard_count
+                );
             }
-            _ => Ok(()),
-        },
-        _ => Ok(()),
+        }
     }
 }
@@ -0,0 +1,109 @@
+/*
+ * SPDX-License-Identifier: AGPL-3.0-only
+ *
+ * This file is part of HarTex.
+ *
+ * HarTex
+ * Copyright (c) 2021-2024 HarTex Project Developers
+ *
+ * HarTex is free software; you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation; either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * HarTex is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License along
+ * with HarTex. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::env;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use hartex_discord_commands_core::traits::EventHandler;
+use hartex_discord_core::discord::model::gateway::event::DispatchEvent;
+use hartex_discord_core::discord::model::gateway::event::EventType;
+use hartex_discord_core::discord::model::gateway::payload::outgoing::request_guild_members::RequestGuildMembersInfo;
+use hartex_discord_core::discord::model::gateway::payload::outgoing::RequestGuildMembers;
+use hartex_discord_core::discord::model::gateway::OpCode;
+use hartex_discord_core::discord::model::gateway::ShardId;
+use hartex_kafka_utils::keys;
+use hartex_kafka_utils::outbound::OutboundCommand;
+use hartex_kafka_utils::outbound::OutboundEnvelope;
+use hartex_kafka_utils::outbound::ShardTarget;
+use miette::IntoDiagnostic;
+use rdkafka::producer::FutureProducer;
+use rdkafka::producer::FutureRecord;
+use rdkafka::util::Timeout;
+
+/// Requests the members of every guild the bot joins or becomes available in, such that they are
+/// cached.
+pub struct GuildCreateHandler {
+    producer: FutureProducer,
+    topic: String,
+}
+
+impl GuildCreateHandler {
+    /// Construct the event handler.
+    pub fn new(producer: FutureProducer) -> miette::Result<Self> {
+        Ok(Self {
+            producer,
+            topic: env::var("KAFKA_TOPIC_OUTBOUND_COMMUNICATION").into_diagnostic()?,
+        })
+    }
+}
+
+#[async_trait]
+impl EventHandler for GuildCreateHandler {
+    fn name(&self) -> String {
+        String::from("guild_create")
+    }
+
+    fn event_types(&self) -> Vec<EventType> {
+        vec![EventType::GuildCreate]
+    }
+
+    async fn handle(&self, event: DispatchEvent, shard: ShardId) -> miette::Result<()> {
+        let DispatchEvent::GuildCreate(guild_create) = event else {
+            return Ok(());
+        };
+
+        let envelope = OutboundEnvelope {
+            target: ShardTarget::Shard {
+                shard_id: shard.number(),
+                shard_count: shard.total(),
+            },
+            command: OutboundCommand::RequestGuildMembers(RequestGuildMembers {
+                d: RequestGuildMembersInfo {
+                    guild_id: guild_create.id,
+                    limit: Some(0),
+                    nonce: None,
+                    // presences are not cached; requesting them requires the privileged
+                    // guild presences intent
+                    presences: None,
+                    query: Some(String::new()),
+                    user_ids: None,
+                },
+                op: OpCode::RequestGuildMembers,
+            }),
+        };
+        let string = serde_json::to_string(&envelope).into_diagnostic()?;
+
+        self.producer
+            .send(
+                FutureRecord::to(&self.topic)
+                    .key(keys::OUTBOUND_GATEWAY_COMMAND)
+                    .payload(&string),
+                Timeout::After(Duration::from_secs(0)),
+            )
+            .await
+            .map_err(|(error, _)| error)
+            .into_diagnostic()?;
+
+        Ok(())
+    }
+}
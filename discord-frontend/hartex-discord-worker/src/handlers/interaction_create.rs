@@ -0,0 +1,127 @@
+/*
+ * SPDX-License-Identifier: AGPL-3.0-only
+ *
+ * This file is part of HarTex.
+ *
+ * HarTex
+ * Copyright (c) 2021-2024 HarTex Project Developers
+ *
+ * HarTex is free software; you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation; either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * HarTex is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License along
+ * with HarTex. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::panic::AssertUnwindSafe;
+
+use async_trait::async_trait;
+use futures_util::FutureExt;
+use hartex_discord_commands_core::response::ResponseContext;
+use hartex_discord_commands_core::traits::EventHandler;
+use hartex_discord_core::discord::model::application::interaction::InteractionType;
+use hartex_discord_core::discord::model::gateway::event::DispatchEvent;
+use hartex_discord_core::discord::model::gateway::event::EventType;
+use hartex_discord_core::discord::model::gateway::ShardId;
+use hartex_discord_utils::CLIENT;
+
+use crate::errorhandler::ErrorPayload;
+
+/// Handles application command, autocomplete, message component and modal submit interactions,
+/// reporting errors and panics to the user invoking them.
+pub struct InteractionCreateHandler;
+
+#[async_trait]
+impl EventHandler for InteractionCreateHandler {
+    fn name(&self) -> String {
+        String::from("interaction_create")
+    }
+
+    fn event_types(&self) -> Vec<EventType> {
+        vec![EventType::InteractionCreate]
+    }
+
+    // an interaction can only be responded to once, so it is not handled again when consumed again
+    fn idempotency_key(&self, event: &DispatchEvent) -> Option<String> {
+        let DispatchEvent::InteractionCreate(interaction_create) = event else {
+            return None;
+        };
+
+        Some(interaction_create.id.to_string())
+    }
+
+    #[allow(clippy::large_futures)]
+    async fn handle(&self, event: DispatchEvent, shard: ShardId) -> miette::Result<()> {
+        let DispatchEvent::InteractionCreate(interaction_create) = event else {
+            return Ok(());
+        };
+
+        let response = ResponseContext::new(&CLIENT, &interaction_create);
+
+        // autocomplete interactions must be responded to directly and cannot be deferred
+        let _deferral = (interaction_create.kind
+            != InteractionType::ApplicationCommandAutocomplete)
+            .then(|| response.defer_before_deadline());
+
+        let result = match interaction_create.kind {
+            InteractionType::ApplicationCommand => {
+                AssertUnwindSafe(crate::interaction::application_command(
+                    interaction_create.clone(),
+                    shard,
+                    &response,
+                ))
+                .catch_unwind()
+                .await
+            }
+            InteractionType::ApplicationCommandAutocomplete => {
+                AssertUnwindSafe(crate::interaction::autocomplete(
+                    interaction_create.clone(),
+                    &response,
+                ))
+                .catch_unwind()
+                .await
+            }
+            InteractionType::MessageComponent => {
+                AssertUnwindSafe(crate::interaction::message_component(
+                    interaction_create.clone(),
+                    shard,
+                    &response,
+                ))
+                .catch_unwind()
+                .await
+            }
+            InteractionType::ModalSubmit => {
+                AssertUnwindSafe(crate::interaction::modal_submit(
+                    interaction_create.clone(),
+                    shard,
+                    &response,
+                ))
+                .catch_unwind()
+                .await
+            }
+            _ => return Ok(()),
+        };
+
+        match result {
+            Ok(result) => result,
+            Err(error) => {
+                crate::errorhandler::handle_interaction_error(
+                    ErrorPayload::from_panic(&*error),
+                    &interaction_create,
+                    shard,
+                    &response,
+                )
+                .await;
+
+                Ok(())
+            }
+        }
+    }
+}
@@ -0,0 +1,101 @@
+/*
+ * SPDX-License-Identifier: AGPL-3.0-only
+ *
+ * This file is part of HarTex.
+ *
+ * HarTex
+ * Copyright (c) 2021-2024 HarTex Project Developers
+ *
+ * HarTex is free software; you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation; either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * HarTex is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License along
+ * with HarTex. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::env;
+use std::time::SystemTime;
+
+use async_trait::async_trait;
+use hartex_backend_models::uptime::UptimeUpdate;
+use hartex_discord_commands_core::traits::EventHandler;
+use hartex_discord_core::discord::model::gateway::event::DispatchEvent;
+use hartex_discord_core::discord::model::gateway::event::EventType;
+use hartex_discord_core::discord::model::gateway::ShardId;
+use hartex_discord_core::tokio::net::TcpStream;
+use hartex_discord_core::tokio::spawn;
+use hartex_log::log;
+use hyper::client::conn::http1::handshake;
+use hyper::header::ACCEPT;
+use hyper::header::CONTENT_TYPE;
+use hyper::Method;
+use hyper::Request;
+use hyper_util::rt::TokioIo;
+use miette::IntoDiagnostic;
+
+/// Reports the time the bot has become ready to the API backend.
+pub struct ReadyHandler;
+
+#[async_trait]
+impl EventHandler for ReadyHandler {
+    fn name(&self) -> String {
+        String::from("ready")
+    }
+
+    fn event_types(&self) -> Vec<EventType> {
+        vec![EventType::Ready]
+    }
+
+    #[allow(clippy::cast_lossless)]
+    async fn handle(&self, event: DispatchEvent, shard: ShardId) -> miette::Result<()> {
+        let DispatchEvent::Ready(ready) = event else {
+            return Ok(());
+        };
+
+        log::info!(
+            "{}#{} (shard {}) has received READY payload from Discord (gateway v{})",
+            ready.user.name,
+            ready.user.discriminator,
+            shard.number(),
+            ready.version
+        );
+
+        let api_domain = env::var("API_DOMAIN").into_diagnostic()?;
+        let uri = format!("http://{}/api/v0110/stats/uptime", api_domain.clone());
+        let now = SystemTime::now();
+        let duration = now
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .into_diagnostic()?;
+
+        let stream = TcpStream::connect(api_domain).await.into_diagnostic()?;
+        let (mut sender, connection) = handshake(TokioIo::new(stream)).await.into_diagnostic()?;
+
+        spawn(async move {
+            if let Err(err) = connection.await {
+                log::error!("TCP connection failed: {:?}", err);
+            }
+        });
+
+        log::debug!("sending a request to {}", &uri);
+
+        let query = UptimeUpdate::new("HarTex Nightly", duration.as_secs() as u128);
+        let request = Request::builder()
+            .uri(uri)
+            .method(Method::PATCH)
+            .header(ACCEPT, "application/json")
+            .header(CONTENT_TYPE, "application/json")
+            .body(serde_json::to_string(&query).into_diagnostic()?)
+            .into_diagnostic()?;
+
+        sender.send_request(request).await.into_diagnostic()?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,114 @@
+/*
+ * SPDX-License-Identifier: AGPL-3.0-only
+ *
+ * This file is part of HarTex.
+ *
+ * HarTex
+ * Copyright (c) 2021-2024 HarTex Project Developers
+ *
+ * HarTex is free software; you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation; either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * HarTex is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License along
+ * with HarTex. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! # Idempotency Guards
+//!
+//! Offsets are only committed after events are processed, so events may be consumed more than
+//! once. Event handlers with side effects that must not be repeated claim a key for each event
+//! before handling it, and skip events whose keys were already claimed. Keys are released when
+//! event handlers fail to handle their events, such that the events are handled when replayed.
+
+use std::pin::Pin;
+
+use hartex_database_queries::discord_frontend::queries::processed_event_delete::processed_event_delete;
+use hartex_database_queries::discord_frontend::queries::processed_event_delete_before::processed_event_delete_before;
+use hartex_database_queries::discord_frontend::queries::processed_event_insert::processed_event_insert;
+use hartex_discord_commands_core::traits::EventHandler;
+use hartex_discord_core::discord::model::gateway::event::DispatchEvent;
+use hartex_discord_utils::DATABASE_POOL;
+use hartex_log::log;
+use miette::IntoDiagnostic;
+use time::Duration;
+use time::OffsetDateTime;
+use tokio_postgres::GenericClient;
+
+/// How long claimed keys are kept for.
+///
+/// Events are only consumed again after a worker restarts or a consumer group rebalances, which
+/// happens well within this duration.
+const RETENTION: Duration = Duration::days(1);
+
+/// Claim the key of an event for an event handler, returning whether the event handler is to
+/// handle the event.
+///
+/// Events are always handled by event handlers that do not have a key for them.
+pub async fn claim(
+    handler: &(dyn EventHandler + Send + Sync),
+    event: &DispatchEvent,
+) -> miette::Result<bool> {
+    let Some(key) = handler.idempotency_key(event) else {
+        return Ok(true);
+    };
+
+    let pinned = Pin::static_ref(&DATABASE_POOL).await;
+    let pooled = pinned.get().await.into_diagnostic()?;
+    let client = pooled.client();
+
+    let inserted = processed_event_insert()
+        .bind(client, &format!("{}:{key}", handler.name()))
+        .await
+        .into_diagnostic()?;
+    if inserted == 0 {
+        log::debug!(
+            "event handler {} skipping event {key}: already handled",
+            handler.name()
+        );
+    }
+
+    Ok(inserted > 0)
+}
+
+/// Release the key of an event claimed by an event handler that failed to handle it.
+pub async fn release(
+    handler: &(dyn EventHandler + Send + Sync),
+    event: &DispatchEvent,
+) -> miette::Result<()> {
+    let Some(key) = handler.idempotency_key(event) else {
+        return Ok(());
+    };
+
+    let pinned = Pin::static_ref(&DATABASE_POOL).await;
+    let pooled = pinned.get().await.into_diagnostic()?;
+    let client = pooled.client();
+
+    processed_event_delete()
+        .bind(client, &format!("{}:{key}", handler.name()))
+        .await
+        .into_diagnostic()?;
+
+    Ok(())
+}
+
+/// Remove the keys that were claimed longer ago than the retention period.
+pub async fn prune() -> miette::Result<()> {
+    let pinned = Pin::static_ref(&DATABASE_POOL).await;
+    let pooled = pinned.get().await.into_diagnostic()?;
+    let client = pooled.client();
+
+    let deleted = processed_event_delete_before()
+        .bind(client, &(OffsetDateTime::now_utc() - RETENTION))
+        .await
+        .into_diagnostic()?;
+    log::debug!("pruned {deleted} claimed event keys");
+
+    Ok(())
+}
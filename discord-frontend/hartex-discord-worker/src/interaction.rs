@@ -21,22 +21,30 @@
  */
 
 use std::collections::HashMap;
+use std::str::FromStr;
 
-use hartex_discord_commands::general::about::About;
-use hartex_discord_commands::general::contributors::Contributors;
-use hartex_discord_commands::utilities::info::Info;
+use hartex_discord_commands_core::autocomplete::FocusedOption;
+use hartex_discord_commands_core::autocomplete::MAX_CHOICES;
+use hartex_discord_commands_core::customid::CustomId;
+use hartex_discord_commands_core::modalfields::ModalFields;
+use hartex_discord_commands_core::registry;
+use hartex_discord_commands_core::response::ResponseContext;
 use hartex_discord_commands_core::traits::Command;
 use hartex_discord_commands_core::traits::CommandMetadata;
-use hartex_discord_core::discord::http::client::InteractionClient;
+use hartex_discord_commands_core::traits::ComponentHandler;
+use hartex_discord_commands_core::traits::ModalHandler;
+use hartex_discord_core::discord::model::application::interaction::InteractionContextType;
 use hartex_discord_core::discord::model::application::interaction::InteractionData;
 use hartex_discord_core::discord::model::gateway::payload::incoming::InteractionCreate;
+use hartex_discord_core::discord::model::gateway::ShardId;
+use hartex_discord_core::discord::model::guild::Permissions;
 use hartex_discord_core::discord::model::http::interaction::InteractionResponse;
 use hartex_discord_core::discord::model::http::interaction::InteractionResponseType;
 use hartex_discord_core::discord::util::builder::InteractionResponseDataBuilder;
+use hartex_discord_utils::interaction::ephemeral_error_response;
 use hartex_localization_core::Localizer;
 use hartex_localization_core::LOCALIZATION_HOLDER;
 use hartex_log::log;
-use miette::IntoDiagnostic;
 use once_cell::sync::Lazy;
 
 use crate::errorhandler::ErrorPayload;
@@ -44,21 +52,43 @@ use crate::errorhandler::ErrorPayload;
 /// Lookup table for commands provided by the bot.
 ///
 /// This is used for retrieving the command instance by its name such that precommand checks
-/// can be executed via dynamic dispatch without the need of match arms and if guards.
+/// can be executed via dynamic dispatch without the need of match arms and if guards. Commands
+/// are registered by the `command` macro.
 pub static COMMAND_LOOKUP: Lazy<HashMap<String, Box<dyn Command + Send + Sync>>> =
     Lazy::new(|| {
-        let mut map = HashMap::<String, Box<dyn Command + Send + Sync>>::new();
-        map.insert(About.name(), Box::new(About));
-        map.insert(Contributors.name(), Box::new(Contributors));
-        map.insert(Info.name(), Box::new(Info));
-        map
+        registry::commands()
+            .map(|command| (command.name(), command))
+            .collect()
+    });
+
+/// Lookup table for message component handlers provided by the bot.
+///
+/// This is used for retrieving the component handler named by the custom ID of a message
+/// component. Component handlers are registered by the `component` macro.
+pub static COMPONENT_LOOKUP: Lazy<HashMap<String, Box<dyn ComponentHandler + Send + Sync>>> =
+    Lazy::new(|| {
+        registry::component_handlers()
+            .map(|handler| (handler.name(), handler))
+            .collect()
+    });
+
+/// Lookup table for modal handlers provided by the bot.
+///
+/// This is used for retrieving the modal handler named by the custom ID of a submitted modal.
+/// Modal handlers are registered by the `modal` macro.
+pub static MODAL_LOOKUP: Lazy<HashMap<String, Box<dyn ModalHandler + Send + Sync>>> =
+    Lazy::new(|| {
+        registry::modal_handlers()
+            .map(|handler| (handler.name(), handler))
+            .collect()
     });
 
 /// Handle an application command interaction.
 #[allow(clippy::large_futures)]
 pub async fn application_command(
     interaction_create: Box<InteractionCreate>,
-    interaction_client: &InteractionClient<'_>,
+    shard: ShardId,
+    response: &ResponseContext,
 ) -> miette::Result<()> {
     let InteractionData::ApplicationCommand(command) = interaction_create.data.clone().unwrap()
     else {
@@ -72,66 +102,349 @@ pub async fn application_command(
     let locale = interaction_create.locale.as_deref().unwrap_or("en-GB");
     let localizer = Localizer::new(&LOCALIZATION_HOLDER, locale);
 
-    let command = COMMAND_LOOKUP.get(&command.name).unwrap();
-    let plugin = command.plugin();
-    if !plugin.enabled(interaction_create.guild_id.unwrap()).await? {
-        interaction_client
-            .create_response(
-                interaction_create.id,
-                &interaction_create.token,
-                &InteractionResponse {
-                    kind: InteractionResponseType::ChannelMessageWithSource,
-                    data: Some(
-                        InteractionResponseDataBuilder::new()
-                            .content(localizer.error_error_plugin_disabled(plugin.name())?)
-                            .build(),
-                    ),
-                },
-            )
-            .await
-            .into_diagnostic()?;
+    let Some(command) = COMMAND_LOOKUP.get(&command.name) else {
+        log::warn!("received interaction of unknown command {}", command.name);
+
+        response
+            .respond(ephemeral_error_response(
+                localizer.error_error_unknown_command()?,
+            ))
+            .await?;
 
+        return Ok(());
+    };
+
+    if !checks(&**command, &interaction_create, response, &localizer).await? {
         return Ok(());
     }
 
-    let permissions = command.required_permissions();
-    let member_permissions = interaction_create
-        .member
-        .clone()
-        .unwrap()
-        .permissions
-        .unwrap();
-    if !member_permissions.contains(permissions) {
-        interaction_client
-            .create_response(
-                interaction_create.id,
-                &interaction_create.token,
-                &InteractionResponse {
-                    kind: InteractionResponseType::ChannelMessageWithSource,
-                    data: Some(
-                        InteractionResponseDataBuilder::new()
-                            .content(localizer.error_error_insufficient_permissions()?)
-                            .build(),
-                    ),
-                },
-            )
-            .await
-            .into_diagnostic()?;
+    if let Some(seconds) = crate::cooldown::hit(&**command, &interaction_create).await? {
+        response
+            .respond(ephemeral_error_response(
+                localizer.error_error_command_on_cooldown(seconds)?,
+            ))
+            .await?;
 
         return Ok(());
     }
 
-    if let Err(error) = command
-        .execute(cloned.0, interaction_client, localizer)
+    if let Err(error) = command.execute(cloned.0, response, localizer).await {
+        crate::errorhandler::handle_interaction_error(
+            ErrorPayload::Miette(error),
+            &interaction_create,
+            shard,
+            response,
+        )
+        .await;
+    }
+
+    Ok(())
+}
+
+/// Handle an application command autocomplete interaction.
+#[allow(clippy::large_futures)]
+pub async fn autocomplete(
+    interaction_create: Box<InteractionCreate>,
+    response: &ResponseContext,
+) -> miette::Result<()> {
+    let InteractionData::ApplicationCommand(command) = interaction_create.data.clone().unwrap()
+    else {
+        unreachable!("this should not be possible")
+    };
+
+    let Some(focused) = FocusedOption::find(&command.options) else {
+        log::warn!(
+            "received autocomplete interaction for command {} without a focused option",
+            command.name
+        );
+
+        return Ok(());
+    };
+
+    log::trace!(
+        "running autocomplete of command {} for option {}",
+        &command.name,
+        &focused.name
+    );
+
+    let locale = interaction_create.locale.as_deref().unwrap_or("en-GB");
+    let localizer = Localizer::new(&LOCALIZATION_HOLDER, locale);
+
+    // no choices are suggested for unknown commands
+    let Some(command) = COMMAND_LOOKUP.get(&command.name) else {
+        log::warn!(
+            "received autocomplete interaction of unknown command {}",
+            command.name
+        );
+
+        response
+            .respond(InteractionResponse {
+                kind: InteractionResponseType::ApplicationCommandAutocompleteResult,
+                data: Some(
+                    InteractionResponseDataBuilder::new()
+                        .choices(Vec::new())
+                        .build(),
+                ),
+            })
+            .await?;
+
+        return Ok(());
+    };
+
+    // choices are not suggested for commands that cannot be run
+    let mut choices = if command.contexts().contains(&context(&interaction_create))
+        && plugin_enabled(&**command, &interaction_create).await?
+        && permissions(&interaction_create).contains(command.required_permissions())
+    {
+        command
+            .autocomplete(interaction_create.0.clone(), focused, localizer)
+            .await?
+    } else {
+        Vec::new()
+    };
+    choices.truncate(MAX_CHOICES);
+
+    response
+        .respond(InteractionResponse {
+            kind: InteractionResponseType::ApplicationCommandAutocompleteResult,
+            data: Some(
+                InteractionResponseDataBuilder::new()
+                    .choices(choices)
+                    .build(),
+            ),
+        })
+        .await?;
+
+    Ok(())
+}
+
+/// Handle a message component interaction.
+#[allow(clippy::large_futures)]
+pub async fn message_component(
+    interaction_create: Box<InteractionCreate>,
+    shard: ShardId,
+    response: &ResponseContext,
+) -> miette::Result<()> {
+    let InteractionData::MessageComponent(component) = interaction_create.data.clone().unwrap()
+    else {
+        unreachable!("this should not be possible")
+    };
+
+    let cloned = interaction_create.clone();
+
+    let locale = interaction_create.locale.as_deref().unwrap_or("en-GB");
+    let localizer = Localizer::new(&LOCALIZATION_HOLDER, locale);
+
+    let Some((custom_id, handler)) =
+        CustomId::from_str(&component.custom_id)
+            .ok()
+            .and_then(|custom_id| {
+                let handler = COMPONENT_LOOKUP.get(custom_id.handler())?;
+
+                Some((custom_id, handler))
+            })
+    else {
+        log::warn!(
+            "received interaction with component of unknown custom id {}",
+            component.custom_id
+        );
+
+        response
+            .respond(ephemeral_error_response(
+                localizer.error_error_unknown_component()?,
+            ))
+            .await?;
+
+        return Ok(());
+    };
+
+    log::trace!("running component handler for custom id {custom_id}");
+
+    if !checks(&**handler, &interaction_create, response, &localizer).await? {
+        return Ok(());
+    }
+
+    if let Err(error) = handler
+        .handle(cloned.0, custom_id, response, localizer)
         .await
     {
         crate::errorhandler::handle_interaction_error(
             ErrorPayload::Miette(error),
-            interaction_create,
-            interaction_client,
+            &interaction_create,
+            shard,
+            response,
         )
         .await;
     }
 
     Ok(())
 }
+
+/// Handle a modal submit interaction.
+#[allow(clippy::large_futures)]
+pub async fn modal_submit(
+    interaction_create: Box<InteractionCreate>,
+    shard: ShardId,
+    response: &ResponseContext,
+) -> miette::Result<()> {
+    let InteractionData::ModalSubmit(modal) = interaction_create.data.clone().unwrap() else {
+        unreachable!("this should not be possible")
+    };
+
+    let cloned = interaction_create.clone();
+
+    let locale = interaction_create.locale.as_deref().unwrap_or("en-GB");
+    let localizer = Localizer::new(&LOCALIZATION_HOLDER, locale);
+
+    let Some((custom_id, handler)) =
+        CustomId::from_str(&modal.custom_id)
+            .ok()
+            .and_then(|custom_id| {
+                let handler = MODAL_LOOKUP.get(custom_id.handler())?;
+
+                Some((custom_id, handler))
+            })
+    else {
+        log::warn!(
+            "received interaction with modal of unknown custom id {}",
+            modal.custom_id
+        );
+
+        response
+            .respond(ephemeral_error_response(
+                localizer.error_error_unknown_modal()?,
+            ))
+            .await?;
+
+        return Ok(());
+    };
+
+    log::trace!("running modal handler for custom id {custom_id}");
+
+    if !checks(&**handler, &interaction_create, response, &localizer).await? {
+        return Ok(());
+    }
+
+    if let Err(error) = handler
+        .handle(
+            cloned.0,
+            custom_id,
+            ModalFields::from(modal),
+            response,
+            localizer,
+        )
+        .await
+    {
+        crate::errorhandler::handle_interaction_error(
+            ErrorPayload::Miette(error),
+            &interaction_create,
+            shard,
+            response,
+        )
+        .await;
+    }
+
+    Ok(())
+}
+
+/// Run the checks for an interaction handled by a command, a component handler or a modal
+/// handler, namely whether it can be run in the context of the interaction, whether its plugin is
+/// enabled and whether the invoking user has the required permissions.
+///
+/// Returns `false` after responding to the interaction if any of the checks fail.
+async fn checks<M>(
+    metadata: &M,
+    interaction_create: &InteractionCreate,
+    response: &ResponseContext,
+    localizer: &Localizer<'_>,
+) -> miette::Result<bool>
+where
+    M: CommandMetadata + Sync + ?Sized,
+{
+    if !metadata.contexts().contains(&context(interaction_create)) {
+        response
+            .respond(ephemeral_error_response(
+                localizer.error_error_unsupported_context()?,
+            ))
+            .await?;
+
+        return Ok(false);
+    }
+
+    if !plugin_enabled(metadata, interaction_create).await? {
+        response
+            .respond(InteractionResponse {
+                kind: InteractionResponseType::ChannelMessageWithSource,
+                data: Some(
+                    InteractionResponseDataBuilder::new()
+                        .content(localizer.error_error_plugin_disabled(metadata.plugin().name())?)
+                        .build(),
+                ),
+            })
+            .await?;
+
+        return Ok(false);
+    }
+
+    if !permissions(interaction_create).contains(metadata.required_permissions()) {
+        response
+            .respond(InteractionResponse {
+                kind: InteractionResponseType::ChannelMessageWithSource,
+                data: Some(
+                    InteractionResponseDataBuilder::new()
+                        .content(localizer.error_error_insufficient_permissions()?)
+                        .build(),
+                ),
+            })
+            .await?;
+
+        return Ok(false);
+    }
+
+    Ok(true)
+}
+
+/// The context an interaction was invoked in.
+///
+/// Interactions in guilds are assumed to be invoked in the guild context, and those outside of
+/// guilds in direct messages with the bot, if Discord does not specify the context.
+fn context(interaction_create: &InteractionCreate) -> InteractionContextType {
+    interaction_create.context.unwrap_or_else(|| {
+        if interaction_create.guild_id.is_some() {
+            InteractionContextType::Guild
+        } else {
+            InteractionContextType::BotDm
+        }
+    })
+}
+
+/// Whether the plugin of a command, a component handler or a modal handler is enabled for an
+/// interaction.
+///
+/// Plugins are only configured for guilds, and are hence always enabled outside of guilds.
+async fn plugin_enabled<M>(
+    metadata: &M,
+    interaction_create: &InteractionCreate,
+) -> miette::Result<bool>
+where
+    M: CommandMetadata + Sync + ?Sized,
+{
+    let Some(guild_id) = interaction_create.guild_id else {
+        return Ok(true);
+    };
+
+    metadata.plugin().enabled(guild_id).await
+}
+
+/// The permissions of the user invoking an interaction.
+///
+/// These are the permissions of the member in guilds, or the permissions of the app in the
+/// channel of the interaction outside of guilds.
+fn permissions(interaction_create: &InteractionCreate) -> Permissions {
+    interaction_create
+        .member
+        .as_ref()
+        .and_then(|member| member.permissions)
+        .or(interaction_create.app_permissions)
+        .unwrap_or_else(Permissions::empty)
+}
@@ -0,0 +1,60 @@
+/*
+ * SPDX-License-Identifier: AGPL-3.0-only
+ *
+ * This file is part of HarTex.
+ *
+ * HarTex
+ * Copyright (c) 2021-2024 HarTex Project Developers
+ *
+ * HarTex is free software; you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation; either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * HarTex is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License along
+ * with HarTex. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! # Shard Latencies
+//!
+//! The shards are held by the leader, which publishes their heartbeat latencies as lifecycle
+//! events. The latencies are stored in the database, such that they are available to every worker
+//! regardless of the partitions each of them consumes.
+
+use std::pin::Pin;
+
+use hartex_database_queries::discord_frontend::queries::shard_latency_upsert::shard_latency_upsert;
+use hartex_discord_utils::DATABASE_POOL;
+use miette::IntoDiagnostic;
+use tokio_postgres::GenericClient;
+
+/// Record the heartbeat latencies of a shard, in milliseconds.
+#[allow(clippy::cast_possible_wrap)]
+pub async fn record(
+    shard_id: u32,
+    shard_count: u32,
+    average_latency: Option<u64>,
+    latest_latency: Option<u64>,
+) -> miette::Result<()> {
+    let pinned = Pin::static_ref(&DATABASE_POOL).await;
+    let pooled = pinned.get().await.into_diagnostic()?;
+    let client = pooled.client();
+
+    shard_latency_upsert()
+        .bind(
+            client,
+            &i64::from(shard_id),
+            &i64::from(shard_count),
+            &average_latency.map(|latency| latency as i64),
+            &latest_latency.map(|latency| latency as i64),
+        )
+        .await
+        .into_diagnostic()?;
+
+    Ok(())
+}
@@ -30,15 +30,25 @@
 
 use std::env;
 use std::str;
-use std::str::Utf8Error;
+use std::sync::Arc;
+use std::time::Duration;
 
 use futures_util::StreamExt;
 use hartex_discord_core::discord::model::gateway::event::GatewayEventDeserializer;
+use hartex_discord_core::discord::model::gateway::ShardId;
 use hartex_discord_core::dotenvy;
+use hartex_discord_core::shutdown::ShutdownController;
+use hartex_discord_core::shutdown::DEFAULT_DRAIN_TIMEOUT;
 use hartex_discord_core::tokio;
-use hartex_discord_core::tokio::signal;
+use hartex_discord_core::tokio::time;
 use hartex_discord_utils::CLIENT;
 use hartex_discord_utils::TOKEN;
+use hartex_kafka_utils::deadletter::DeadLetterProducer;
+use hartex_kafka_utils::deadletter::DeadLetterStage;
+use hartex_kafka_utils::envelope::InboundGatewayEnvelope;
+use hartex_kafka_utils::lifecycle::ShardLifecycleEvent;
+use hartex_kafka_utils::offsets::OffsetCommitter;
+use hartex_kafka_utils::offsets::OffsetCommitterContext;
 use hartex_kafka_utils::traits::ClientConfigUtils;
 use hartex_kafka_utils::types::CompressionType;
 use hartex_log::log;
@@ -47,26 +57,38 @@ use once_cell::sync::Lazy;
 use rdkafka::consumer::Consumer;
 use rdkafka::consumer::StreamConsumer;
 use rdkafka::error::KafkaError;
+use rdkafka::message::BorrowedMessage;
 use rdkafka::message::Message;
 use rdkafka::producer::FutureProducer;
 use rdkafka::ClientConfig;
 use serde::de::DeserializeSeed;
-use serde_scan::scan;
 
+use crate::dispatcher::Dispatcher;
 use crate::error::ConsumerError;
 use crate::error::ConsumerErrorKind;
+use crate::eventcallback::EventHandlerRegistry;
 use crate::interaction::COMMAND_LOOKUP;
 
+mod cooldown;
+mod dispatcher;
 mod error;
 mod errorhandler;
 mod eventcallback;
+mod handlers;
+mod idempotency;
 mod interaction;
+mod latency;
+mod specification;
+
+/// The interval between prunes of claimed event keys and expired cooldown buckets.
+const PRUNE_INTERVAL: Duration = Duration::from_secs(600);
 
 /// Entry point.
 #[allow(clippy::large_futures)]
 #[tokio::main(flavor = "multi_thread")]
 pub async fn main() -> miette::Result<()> {
     hartex_log::initialize();
+    crate::errorhandler::install_panic_hook();
 
     log::trace!("loading environment variables");
     dotenvy::dotenv().into_diagnostic()?;
@@ -75,12 +97,16 @@ pub async fn main() -> miette::Result<()> {
     Lazy::force(&COMMAND_LOOKUP);
     Lazy::force(&TOKEN);
 
+    specification::check()?;
+
     let bootstrap_servers = env::var("KAFKA_BOOTSTRAP_SERVERS")
         .into_diagnostic()?
         .split(';')
         .map(String::from)
         .collect::<Vec<_>>();
     let topic = env::var("KAFKA_TOPIC_INBOUND_DISCORD_GATEWAY_PAYLOAD").into_diagnostic()?;
+    let lifecycle_topic =
+        env::var("KAFKA_TOPIC_INBOUND_DISCORD_GATEWAY_LIFECYCLE").into_diagnostic()?;
 
     let producer = ClientConfig::new()
         .bootstrap_servers(bootstrap_servers.clone().into_iter())
@@ -91,12 +117,51 @@ pub async fn main() -> miette::Result<()> {
     let consumer = ClientConfig::new()
         .bootstrap_servers(bootstrap_servers.into_iter())
         .group_id("com.github.teamhartex.hartex.inbound.gateway.payload.consumer")
-        .create::<StreamConsumer>()
+        .enable_auto_commit(false)
+        .enable_auto_offset_store(false)
+        .create_with_context::<_, StreamConsumer<OffsetCommitterContext>>(
+            OffsetCommitterContext::default(),
+        )
         .into_diagnostic()?;
+    let consumer = Arc::new(consumer);
+
+    // offsets are committed once events are processed, such that events being processed when the
+    // worker stops are consumed again when it restarts
+    let committer = Arc::new(OffsetCommitter::new(consumer.clone()));
+
+    let shutdown = ShutdownController::new();
+    shutdown.listen_for_signals();
+
+    let dead_letters = DeadLetterProducer::new(
+        producer.clone(),
+        env::var("KAFKA_TOPIC_DEAD_LETTER").into_diagnostic()?,
+        "worker",
+        shutdown.clone(),
+    );
+
+    let dispatcher = Dispatcher::new(
+        EventHandlerRegistry::new(producer)?,
+        dead_letters.clone(),
+        committer.clone(),
+        shutdown.clone(),
+    )?;
 
-    consumer.subscribe(&[&topic]).into_diagnostic()?;
+    tokio::spawn(prune(shutdown.clone()));
+
+    consumer
+        .subscribe(&[&topic, &lifecycle_topic])
+        .into_diagnostic()?;
+
+    let mut stream = consumer.stream();
+    loop {
+        let result = tokio::select! {
+            () = shutdown.requested() => break,
+            result = stream.next() => result,
+        };
+        let Some(result) = result else {
+            break;
+        };
 
-    while let Some(result) = consumer.stream().next().await {
         let Ok(message) = result else {
             let error = result.unwrap_err();
             println!("{:?}", Err::<(), KafkaError>(error).into_diagnostic());
@@ -104,12 +169,32 @@ pub async fn main() -> miette::Result<()> {
             continue;
         };
 
+        committer.track(&message);
+
         let bytes = message.payload().unwrap();
 
+        if message.topic() == lifecycle_topic {
+            match serde_json::from_slice::<ShardLifecycleEvent>(bytes) {
+                Ok(event) => {
+                    eventcallback::invoke_lifecycle(&event).await;
+                    complete(&committer, &message);
+                }
+                Err(error) => {
+                    dead_letters
+                        .dead_letter(&committer, &message, DeadLetterStage::Deserialize, error)
+                        .await;
+                }
+            }
+
+            continue;
+        }
+
         let (gateway_deserializer, mut json_deserializer) = {
             let result = str::from_utf8(bytes);
             if let Err(error) = result {
-                println!("{:?}", Err::<(), Utf8Error>(error).into_diagnostic());
+                dead_letters
+                    .dead_letter(&committer, &message, DeadLetterStage::Decode, error)
+                    .await;
 
                 continue;
             }
@@ -120,7 +205,9 @@ pub async fn main() -> miette::Result<()> {
                 });
 
             if let Err(error) = result {
-                println!("{:?}", Err::<(), ConsumerError>(error).into_diagnostic());
+                dead_letters
+                    .dead_letter(&committer, &message, DeadLetterStage::Decode, error)
+                    .await;
 
                 continue;
             }
@@ -130,38 +217,103 @@ pub async fn main() -> miette::Result<()> {
             (result.unwrap(), json_deserializer)
         };
 
-        let key_bytes = message.key().unwrap();
-        let result = str::from_utf8(key_bytes);
-        if let Err(error) = result {
-            println!("{:?}", Err::<(), Utf8Error>(error).into_diagnostic());
+        let Some(headers) = message.headers() else {
+            let error = ConsumerError {
+                kind: ConsumerErrorKind::MissingHeaders,
+            };
+            dead_letters
+                .dead_letter(&committer, &message, DeadLetterStage::Envelope, error)
+                .await;
 
             continue;
-        }
+        };
+        let envelope = match InboundGatewayEnvelope::from_headers(headers) {
+            Ok(envelope) => envelope,
+            Err(error) => {
+                dead_letters
+                    .dead_letter(&committer, &message, DeadLetterStage::Envelope, error)
+                    .await;
 
-        let key = result.unwrap();
-        let scanned: u8 = scan!("INBOUND_GATEWAY_PAYLOAD_SHARD_{}" <- key).into_diagnostic()?;
+                continue;
+            }
+        };
+        let Some(shard) = ShardId::new_checked(envelope.shard_id, envelope.shard_count) else {
+            let error = ConsumerError {
+                kind: ConsumerErrorKind::InvalidShard {
+                    shard_id: envelope.shard_id,
+                    shard_count: envelope.shard_count,
+                },
+            };
+            dead_letters
+                .dead_letter(&committer, &message, DeadLetterStage::Envelope, error)
+                .await;
+
+            continue;
+        };
 
         log::trace!(
-            "[shard {scanned}] received {} event; attempting to deserialize",
-            gateway_deserializer.event_type().unwrap_or("UNKNOWN")
+            "[shard {}] received {} event; attempting to deserialize",
+            shard.number(),
+            envelope.event_type.as_deref().unwrap_or("UNKNOWN")
         );
         let result = gateway_deserializer.deserialize(&mut json_deserializer);
         if let Err(error) = result {
-            println!(
-                "{:?}",
-                Err::<(), serde_json::Error>(error).into_diagnostic()
-            );
+            dead_letters
+                .dead_letter(&committer, &message, DeadLetterStage::Deserialize, error)
+                .await;
 
             continue;
         }
 
         let event = result.unwrap();
 
-        eventcallback::invoke(event, scanned, producer.clone()).await?;
+        dispatcher.dispatch(event, message.detach(), shard).await;
     }
 
-    signal::ctrl_c().await.into_diagnostic()?;
-    log::warn!("ctrl-c signal received, shutting down");
+    log::warn!("shutting down; waiting for events being processed");
+    let remaining = shutdown.drain(DEFAULT_DRAIN_TIMEOUT).await;
+    if remaining > 0 {
+        log::warn!("{remaining} events still being processed; they are consumed again on restart");
+    }
+
+    log::trace!("committing offsets");
+    if let Err(error) = committer.commit_all() {
+        println!("{:?}", Err::<(), KafkaError>(error).into_diagnostic());
+    }
+
+    log::trace!("waiting for database connections to be returned");
+    if !hartex_discord_utils::drain_database_pool(DEFAULT_DRAIN_TIMEOUT).await {
+        log::warn!("database connections are still in use");
+    }
 
     Ok(())
 }
+
+/// Periodically prune claimed event keys and expired cooldown buckets, until shutdown is
+/// requested.
+async fn prune(shutdown: ShutdownController) {
+    let mut interval = time::interval(PRUNE_INTERVAL);
+    loop {
+        tokio::select! {
+            () = shutdown.requested() => break,
+            _ = interval.tick() => {}
+        }
+
+        if let Err(error) = idempotency::prune().await {
+            println!("{error:?}");
+        }
+        if let Err(error) = cooldown::prune().await {
+            println!("{error:?}");
+        }
+    }
+}
+
+/// Mark a record as processed, committing its offset.
+fn complete(
+    committer: &OffsetCommitter<StreamConsumer<OffsetCommitterContext>>,
+    message: &BorrowedMessage<'_>,
+) {
+    if let Err(error) = committer.complete(message) {
+        println!("{:?}", Err::<(), KafkaError>(error).into_diagnostic());
+    }
+}
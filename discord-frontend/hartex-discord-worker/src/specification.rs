@@ -0,0 +1,173 @@
+/*
+ * SPDX-License-Identifier: AGPL-3.0-only
+ *
+ * This file is part of HarTex.
+ *
+ * HarTex
+ * Copyright (c) 2021-2024 HarTex Project Developers
+ *
+ * HarTex is free software; you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation; either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * HarTex is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License along
+ * with HarTex. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! # Command Specifications
+//!
+//! Commands are registered with Discord from their specifications by the commands manager, while
+//! the worker handles the commands registered by the `command` macro. Every registered command is
+//! checked to have a specification at startup, such that commands are not left unregistered with
+//! Discord.
+//!
+//! The specifications are embedded into the worker, as they are not deployed alongside it.
+
+use std::collections::HashSet;
+
+use hartex_discord_commands_core::registry::COMMANDS;
+use hartex_log::log;
+use miette::IntoDiagnostic;
+use miette::Report;
+
+use crate::interaction::COMMAND_LOOKUP;
+
+/// The specifications of the commands, keyed by their paths relative to the specification
+/// directory.
+const SPECIFICATIONS: [(&str, &str); 7] = [
+    (
+        "general-plugin/about.json",
+        include_str!("../../hartex-discord-commands-spec/general-plugin/about.json"),
+    ),
+    (
+        "general-plugin/contributors.json",
+        include_str!("../../hartex-discord-commands-spec/general-plugin/contributors.json"),
+    ),
+    (
+        "general-plugin/feedback.json",
+        include_str!("../../hartex-discord-commands-spec/general-plugin/feedback.json"),
+    ),
+    (
+        "general-plugin/latency.json",
+        include_str!("../../hartex-discord-commands-spec/general-plugin/latency.json"),
+    ),
+    (
+        "general-plugin/uptime.json",
+        include_str!("../../hartex-discord-commands-spec/general-plugin/uptime.json"),
+    ),
+    (
+        "staff-plugin/error.json",
+        include_str!("../../hartex-discord-commands-spec/staff-plugin/error.json"),
+    ),
+    (
+        "utilities-plugin/info.json",
+        include_str!("../../hartex-discord-commands-spec/utilities-plugin/info.json"),
+    ),
+];
+
+/// Check that every registered command has a specification, and that no two registered commands
+/// share the same name.
+pub fn check() -> miette::Result<()> {
+    log::trace!("checking specifications of registered commands");
+
+    if COMMAND_LOOKUP.len() != COMMANDS.len() {
+        return Err(Report::msg(format!(
+            "{} commands are registered under {} distinct names",
+            COMMANDS.len(),
+            COMMAND_LOOKUP.len()
+        )));
+    }
+
+    let mut names = HashSet::new();
+    for (path, specification) in SPECIFICATIONS {
+        let specification =
+            serde_json::from_str::<serde_json::Value>(specification).into_diagnostic()?;
+        let Some(name) = specification.get("name").and_then(|name| name.as_str()) else {
+            log::warn!("specification {path} has no name");
+
+            continue;
+        };
+
+        names.insert(name.to_string());
+    }
+
+    let mut missing = COMMAND_LOOKUP
+        .keys()
+        .filter(|name| !names.contains(*name))
+        .cloned()
+        .collect::<Vec<_>>();
+    if !missing.is_empty() {
+        missing.sort_unstable();
+
+        return Err(Report::msg(format!(
+            "registered commands without specifications: {}",
+            missing.join(", ")
+        )));
+    }
+
+    log::debug!(
+        "all {} registered commands have specifications",
+        COMMAND_LOOKUP.len()
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+
+    use super::SPECIFICATIONS;
+
+    /// The directory containing the specifications of the commands.
+    const SPECIFICATION_DIRECTORY: &str = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../hartex-discord-commands-spec"
+    );
+
+    #[test]
+    fn every_specification_is_embedded() {
+        let mut paths = Vec::new();
+        collect_paths(Path::new(SPECIFICATION_DIRECTORY), &mut paths);
+        paths.sort_unstable();
+
+        let mut embedded = SPECIFICATIONS
+            .iter()
+            .map(|(path, _)| (*path).to_string())
+            .collect::<Vec<_>>();
+        embedded.sort_unstable();
+
+        assert_eq!(paths, embedded);
+    }
+
+    #[test]
+    fn registered_commands_have_specifications() {
+        super::check().unwrap();
+    }
+
+    /// Collect the paths of the specifications in a directory and its subdirectories, relative to
+    /// the specification directory.
+    fn collect_paths(directory: &Path, paths: &mut Vec<String>) {
+        for entry in fs::read_dir(directory).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                collect_paths(&path, paths);
+                continue;
+            }
+
+            if path.extension().and_then(|extension| extension.to_str()) != Some("json") {
+                continue;
+            }
+
+            let relative = path.strip_prefix(SPECIFICATION_DIRECTORY).unwrap();
+            paths.push(relative.to_string_lossy().into_owned());
+        }
+    }
+}
@@ -30,3 +30,17 @@ contributors-embed-global-admin-field-name=Global Administrator & Lead Developer
 contributors-embed-front-dev-field-name=Frontend Developer
 contributors-embed-translation-team-field-name=Translation Team
 contributors-embed-footer=This is not a complete list (preserve more screen real estate) - but thanks to all who contributed!
+feedback-modal-title=Send Feedback
+feedback-modal-feedback-label=What would you like to tell us?
+feedback-submitted=Thank you for your feedback!
+latency-embed-title=Latency
+latency-embed-shards-field-name=Gateway Heartbeat (Milliseconds)
+latency-embed-shard=Shard {$shardId}/{$shardCount}: {$average} average, {$latest} latest
+latency-embed-more-shards=...and {$count ->
+  [one] {$count} more shard
+  *[other] {$count} more shards
+}
+latency-embed-no-shards=No shard has reported its latency recently.
+latency-embed-unmeasured=unmeasured
+latency-embed-rest-field-name=REST Round Trip (Milliseconds)
+latency-embed-database-field-name=Database Ping (Milliseconds)
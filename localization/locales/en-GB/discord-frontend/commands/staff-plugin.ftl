@@ -0,0 +1,33 @@
+#
+# SPDX-License-Identifier: AGPL-3.0-only
+#
+# This file is part of HarTex.
+#
+# HarTex
+# Copyright (c) 2021-2024 HarTex Project Developers
+#
+# HarTex is free software; you can redistribute it and/or modify
+# it under the terms of the GNU Affero General Public License as published by
+# the Free Software Foundation; either version 3 of the License, or
+# (at your option) any later version.
+#
+# HarTex is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+# GNU Affero General Public License for more details.
+#
+# You should have received a copy of the GNU Affero General Public License along
+# with HarTex. If not, see <https://www.gnu.org/licenses/>.
+#
+
+error-embed-title=Error Report
+error-embed-code-field-name=Error Code
+error-embed-kind-field-name=Kind
+error-embed-command-field-name=Command
+error-embed-options-field-name=Options
+error-embed-guild-field-name=Server
+error-embed-user-field-name=User
+error-embed-shard-field-name=Shard
+error-embed-timestamp-field-name=Occurred
+error-embed-none=None
+error-error-unknown-code=There is no error report with the error code `{$code}`.
@@ -42,6 +42,7 @@ roleinfo-embed-attributes-hoist-subfield-name=Hoist:
 roleinfo-embed-attributes-managed-subfield-name=Managed:
 roleinfo-embed-attributes-mentionable-subfield-name=Mentionable:
 roleinfo-embed-attributes-position-subfield-name=Position:
+roleinfo-error-unknown-role=Unknown role. Perhaps it is not in this server?
 serverinfo-embed-generalinfo-field-name=General Information
 serverinfo-embed-generalinfo-id-subfield-name=ID:
 serverinfo-embed-generalinfo-created-subfield-name=Created:
@@ -69,6 +70,8 @@ serverinfo-embed-flags-default-message-notifications-subfield-name=Default Messa
 serverinfo-embed-flags-explicit-content-filter-subfield-name=Explicit Content Filter:
 serverinfo-embed-flags-mfa-level-subfield-name=MFA Level:
 serverinfo-embed-flags-verification-level-subfield-name=Verification Level:
+serverinfo-button-show-features=Show Server Features
+serverinfo-button-hide-features=Hide Server Features
 userinfo-embed-generalinfo-field-name=General Information
 userinfo-embed-generalinfo-id-subfield-name=ID:
 userinfo-embed-generalinfo-name-subfield-name=Global Name:
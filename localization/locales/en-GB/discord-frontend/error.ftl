@@ -27,4 +27,12 @@ error-line-one=:x: This command encountered{ $errorKind ->
 } error. Please provide the following error code for support.
 error-line-two=Error code:
 error-plugin-disabled=The `{$plugin}` plugin is not enabled. Please enable it in the guild configuration.
-error-insufficient-permissions=Invoking user has insufficient permissions.
\ No newline at end of file
+error-insufficient-permissions=Invoking user has insufficient permissions.
+error-unsupported-context=This command cannot be used here.
+error-unknown-component=This component is no longer available.
+error-unknown-modal=This form is no longer available.
+error-command-on-cooldown=This command is on cooldown. Please try again in { $seconds ->
+  [one] {$seconds} second
+  *[other] {$seconds} seconds
+}.
+error-unknown-command=This command is no longer available.
\ No newline at end of file
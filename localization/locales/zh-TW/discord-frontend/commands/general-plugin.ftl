@@ -30,3 +30,14 @@ contributors-embed-global-admin-field-name=全域管理員 & 首席開發人員
 contributors-embed-front-dev-field-name=前端開發人員
 contributors-embed-translation-team-field-name=翻譯團隊
 contributors-embed-footer=這不是完整的名單（以保留更多螢幕空間）－但感謝所有做出貢獻的人！
+feedback-modal-title=傳送意見回饋
+feedback-modal-feedback-label=您想告訴我們什麼？
+feedback-submitted=感謝您的意見回饋！
+latency-embed-title=延遲
+latency-embed-shards-field-name=閘道心跳（毫秒）
+latency-embed-shard=分片 {$shardId}/{$shardCount}：平均 {$average}，最近 {$latest}
+latency-embed-more-shards=⋯⋯以及其他 {$count} 個分片
+latency-embed-no-shards=最近沒有分片回報其延遲。
+latency-embed-unmeasured=未測量
+latency-embed-rest-field-name=REST 往返時間（毫秒）
+latency-embed-database-field-name=資料庫延遲（毫秒）
@@ -42,6 +42,7 @@ roleinfo-embed-attributes-hoist-subfield-name=與線上成員分開顯示：
 roleinfo-embed-attributes-managed-subfield-name=受管理：
 roleinfo-embed-attributes-mentionable-subfield-name=可提及：
 roleinfo-embed-attributes-position-subfield-name=位置：
+roleinfo-error-unknown-role=未知身分組。此身分組可能不在此伺服器中？
 serverinfo-embed-generalinfo-field-name=一般資訊
 serverinfo-embed-generalinfo-id-subfield-name=ID：
 serverinfo-embed-generalinfo-created-subfield-name=創建於：
@@ -69,6 +70,8 @@ serverinfo-embed-flags-default-message-notifications-subfield-name=預設訊息
 serverinfo-embed-flags-explicit-content-filter-subfield-name=內容過濾器：
 serverinfo-embed-flags-mfa-level-subfield-name=多重認證等級：
 serverinfo-embed-flags-verification-level-subfield-name=驗證等級：
+serverinfo-button-show-features=顯示伺服器功能
+serverinfo-button-hide-features=隱藏伺服器功能
 userinfo-embed-generalinfo-field-name=一般資訊
 userinfo-embed-generalinfo-id-subfield-name=ID：
 userinfo-embed-generalinfo-name-subfield-name=名稱：
@@ -28,3 +28,8 @@ error-line-one=:x: 這個指令發生了{ $errorKind ->
 error-line-two=錯誤代碼：
 error-plugin-disabled=插件 `{$plugin}` 未啟用。請在伺服器設定中啟用。
 error-insufficient-permissions=使用者權限不足。
+error-unsupported-context=此指令無法在此處使用。
+error-unknown-component=此元件已無法使用。
+error-unknown-modal=此表單已無法使用。
+error-command-on-cooldown=此指令正在冷卻中。請在 {$seconds} 秒後再試。
+error-unknown-command=此指令已無法使用。
\ No newline at end of file
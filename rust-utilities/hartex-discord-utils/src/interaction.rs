@@ -22,11 +22,22 @@
 
 //! # Utilities for Interaction Handling
 
+use hartex_discord_core::discord::model::channel::message::component::ActionRow;
+use hartex_discord_core::discord::model::channel::message::component::TextInput;
+use hartex_discord_core::discord::model::channel::message::component::TextInputStyle;
+use hartex_discord_core::discord::model::channel::message::Component;
 use hartex_discord_core::discord::model::channel::message::Embed;
 use hartex_discord_core::discord::model::channel::message::MessageFlags;
 use hartex_discord_core::discord::model::http::interaction::InteractionResponse;
 use hartex_discord_core::discord::model::http::interaction::InteractionResponseType;
 use hartex_discord_core::discord::util::builder::InteractionResponseDataBuilder;
+use miette::Report;
+
+/// The maximum length of the title of a modal allowed by Discord.
+pub const MODAL_TITLE_MAX_LENGTH: usize = 45;
+
+/// The maximum number of text inputs in a modal allowed by Discord.
+pub const MODAL_TEXT_INPUTS_MAX: usize = 5;
 
 /// Constructs an embed response.
 #[must_use]
@@ -41,6 +52,20 @@ pub fn embed_response(embeds: Vec<Embed>) -> InteractionResponse {
     }
 }
 
+/// Constructs an ephemeral embed response.
+#[must_use]
+pub fn ephemeral_embed_response(embeds: Vec<Embed>) -> InteractionResponse {
+    InteractionResponse {
+        kind: InteractionResponseType::ChannelMessageWithSource,
+        data: Some(
+            InteractionResponseDataBuilder::new()
+                .embeds(embeds)
+                .flags(MessageFlags::EPHEMERAL)
+                .build(),
+        ),
+    }
+}
+
 /// Constructs an ephemeral text response, used for error display.
 #[must_use]
 pub fn ephemeral_error_response(message: impl Into<String>) -> InteractionResponse {
@@ -54,3 +79,123 @@ pub fn ephemeral_error_response(message: impl Into<String>) -> InteractionRespon
         ),
     }
 }
+
+/// A builder for a modal response, opening a form for the user to fill in.
+///
+/// Each text input is placed in its own row, as Discord requires.
+#[derive(Clone, Debug)]
+#[must_use]
+pub struct Modal {
+    custom_id: String,
+    title: String,
+    text_inputs: Vec<TextInput>,
+}
+
+impl Modal {
+    /// Construct a modal with a custom ID and a title, without any text inputs.
+    pub fn new(custom_id: impl Into<String>, title: impl Into<String>) -> Self {
+        Self {
+            custom_id: custom_id.into(),
+            title: title.into(),
+            text_inputs: Vec::new(),
+        }
+    }
+
+    /// Add a single-line text input to the modal.
+    pub fn short(
+        self,
+        custom_id: impl Into<String>,
+        label: impl Into<String>,
+        required: bool,
+    ) -> Self {
+        self.text_input(Self::simple_text_input(
+            custom_id,
+            label,
+            required,
+            TextInputStyle::Short,
+        ))
+    }
+
+    /// Add a multi-line text input to the modal.
+    pub fn paragraph(
+        self,
+        custom_id: impl Into<String>,
+        label: impl Into<String>,
+        required: bool,
+    ) -> Self {
+        self.text_input(Self::simple_text_input(
+            custom_id,
+            label,
+            required,
+            TextInputStyle::Paragraph,
+        ))
+    }
+
+    /// Add a text input to the modal, for when lengths, placeholders or prefilled values are
+    /// needed.
+    pub fn text_input(mut self, text_input: TextInput) -> Self {
+        self.text_inputs.push(text_input);
+        self
+    }
+
+    /// Build the modal into an interaction response.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the title of the modal is longer than Discord allows, or if the modal
+    /// has no text inputs or more text inputs than Discord allows.
+    pub fn build(self) -> miette::Result<InteractionResponse> {
+        if self.title.chars().count() > MODAL_TITLE_MAX_LENGTH {
+            return Err(Report::msg(format!(
+                "title of modal {} is longer than {MODAL_TITLE_MAX_LENGTH} characters",
+                self.custom_id
+            )));
+        }
+
+        if self.text_inputs.is_empty() || self.text_inputs.len() > MODAL_TEXT_INPUTS_MAX {
+            return Err(Report::msg(format!(
+                "modal {} must have between 1 and {MODAL_TEXT_INPUTS_MAX} text inputs",
+                self.custom_id
+            )));
+        }
+
+        let components = self
+            .text_inputs
+            .into_iter()
+            .map(|text_input| {
+                Component::ActionRow(ActionRow {
+                    components: vec![Component::TextInput(text_input)],
+                })
+            })
+            .collect::<Vec<_>>();
+
+        Ok(InteractionResponse {
+            kind: InteractionResponseType::Modal,
+            data: Some(
+                InteractionResponseDataBuilder::new()
+                    .custom_id(self.custom_id)
+                    .title(self.title)
+                    .components(components)
+                    .build(),
+            ),
+        })
+    }
+
+    fn simple_text_input(
+        custom_id: impl Into<String>,
+        label: impl Into<String>,
+        required: bool,
+        style: TextInputStyle,
+    ) -> TextInput {
+        TextInput {
+            custom_id: custom_id.into(),
+            label: label.into(),
+            max_length: None,
+            min_length: None,
+            placeholder: None,
+            required: Some(required),
+            style,
+            value: None,
+        }
+    }
+}
@@ -33,11 +33,14 @@
 use std::env;
 use std::future::Future;
 use std::ops::Deref;
+use std::pin::Pin;
+use std::time::Duration;
 
 use async_once_cell::Lazy as AsyncLazy;
 use bb8_postgres::bb8::Pool;
 use bb8_postgres::PostgresConnectionManager;
 use hartex_discord_core::discord::http::Client;
+use hartex_discord_core::tokio::time;
 use once_cell::sync::Lazy;
 use tokio_postgres::NoTls;
 
@@ -72,5 +75,27 @@ pub static DATABASE_POOL: AsyncLazy<PostgresPool, DatabasePoolFuture> = AsyncLaz
     Pool::builder().build(manager).await.unwrap()
 });
 
+/// Wait for the connections of the database pool that are in use to be returned, up to a timeout,
+/// returning whether every connection was returned.
+///
+/// The connections of the pool are closed when the process exits; waiting for them beforehand
+/// ensures that queries in flight are not interrupted.
+pub async fn drain_database_pool(timeout: Duration) -> bool {
+    let pool = Pin::static_ref(&DATABASE_POOL).await;
+
+    let drained = async {
+        loop {
+            let state = pool.state();
+            if state.idle_connections == state.connections {
+                break;
+            }
+
+            time::sleep(Duration::from_millis(100)).await;
+        }
+    };
+
+    time::timeout(timeout, drained).await.is_ok()
+}
+
 /// The bot token used for logging in to the Discord gateway and sending HTTP requests.
 pub static TOKEN: Lazy<String> = Lazy::new(|| env::var("BOT_TOKEN").unwrap());
@@ -0,0 +1,369 @@
+/*
+ * SPDX-License-Identifier: AGPL-3.0-only
+ *
+ * This file is part of HarTex.
+ *
+ * HarTex
+ * Copyright (c) 2021-2024 HarTex Project Developers
+ *
+ * HarTex is free software; you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation; either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * HarTex is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License along
+ * with HarTex. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! # Dead Letters
+//!
+//! Records that cannot be processed are published to a dead-letter topic instead of being lost,
+//! such that they can be inspected and replayed once the cause of the failure is fixed. A dead
+//! letter keeps the key, payload and headers of the original record, and carries metadata about
+//! the failure in additional headers.
+
+use std::error::Error;
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::str::FromStr;
+use std::time::Duration;
+use std::time::SystemTime;
+
+use hartex_discord_core::shutdown::ShutdownController;
+use hartex_discord_core::tokio;
+use hartex_discord_core::tokio::time;
+use miette::IntoDiagnostic;
+use rdkafka::consumer::Consumer;
+use rdkafka::error::KafkaError;
+use rdkafka::message::Header;
+use rdkafka::message::Headers;
+use rdkafka::message::OwnedHeaders;
+use rdkafka::producer::FutureProducer;
+use rdkafka::producer::FutureRecord;
+use rdkafka::util::Timeout;
+use rdkafka::Message;
+
+use crate::envelope::optional;
+use crate::envelope::required;
+use crate::envelope::EnvelopeError;
+use crate::offsets::OffsetCommitter;
+use crate::offsets::OffsetCommitterContext;
+
+/// The prefix of the headers carrying the metadata of a dead letter.
+pub const HEADER_PREFIX: &str = "hartex-dead-letter-";
+
+/// Header carrying the topic of the original record.
+pub const HEADER_SOURCE_TOPIC: &str = "hartex-dead-letter-source-topic";
+
+/// Header carrying the partition of the original record.
+pub const HEADER_SOURCE_PARTITION: &str = "hartex-dead-letter-source-partition";
+
+/// Header carrying the offset of the original record.
+pub const HEADER_SOURCE_OFFSET: &str = "hartex-dead-letter-source-offset";
+
+/// Header carrying the name of the service that failed to process the original record.
+pub const HEADER_SERVICE: &str = "hartex-dead-letter-service";
+
+/// Header carrying the stage of processing at which the original record failed.
+pub const HEADER_STAGE: &str = "hartex-dead-letter-stage";
+
+/// Header carrying the error the original record failed with.
+pub const HEADER_ERROR: &str = "hartex-dead-letter-error";
+
+/// Header carrying the time the original record failed, in milliseconds since the Unix epoch.
+pub const HEADER_FAILED_AT: &str = "hartex-dead-letter-failed-at";
+
+/// The delay before publishing a dead letter is retried for the first time, which is doubled
+/// after every failed attempt.
+const RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// The maximum delay before publishing a dead letter is retried.
+const RETRY_MAX_DELAY: Duration = Duration::from_secs(60);
+
+/// The stage of processing at which a record failed.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DeadLetterStage {
+    /// The payload is not a valid gateway payload.
+    Decode,
+    /// The headers of the record are missing or malformed.
+    Envelope,
+    /// The gateway event cannot be deserialized.
+    Deserialize,
+    /// The gateway event failed to be handled.
+    Handle,
+}
+
+impl Display for DeadLetterStage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Decode => "decode",
+            Self::Envelope => "envelope",
+            Self::Deserialize => "deserialize",
+            Self::Handle => "handle",
+        })
+    }
+}
+
+impl FromStr for DeadLetterStage {
+    type Err = ();
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        match string {
+            "decode" => Ok(Self::Decode),
+            "envelope" => Ok(Self::Envelope),
+            "deserialize" => Ok(Self::Deserialize),
+            "handle" => Ok(Self::Handle),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Metadata of a dead letter.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DeadLetter {
+    /// The topic of the original record.
+    pub source_topic: String,
+    /// The partition of the original record.
+    pub source_partition: i32,
+    /// The offset of the original record.
+    pub source_offset: i64,
+    /// The name of the service that failed to process the original record.
+    pub service: String,
+    /// The stage of processing at which the original record failed.
+    pub stage: DeadLetterStage,
+    /// The error the original record failed with.
+    pub error: String,
+    /// The time the original record failed, in milliseconds since the Unix epoch.
+    pub failed_at: u64,
+}
+
+impl DeadLetter {
+    /// Construct the metadata of a record that just failed.
+    #[must_use]
+    pub fn new<M>(message: &M, service: &str, stage: DeadLetterStage, error: impl Display) -> Self
+    where
+        M: Message,
+    {
+        #[allow(clippy::cast_possible_truncation)]
+        let failed_at = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_millis() as u64);
+
+        Self {
+            source_topic: message.topic().to_string(),
+            source_partition: message.partition(),
+            source_offset: message.offset(),
+            service: service.to_string(),
+            stage,
+            error: error.to_string(),
+            failed_at,
+        }
+    }
+
+    /// Read the metadata of a dead letter from the headers of a record.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a header is missing or malformed.
+    pub fn from_headers<H>(headers: &H) -> Result<Self, EnvelopeError>
+    where
+        H: Headers,
+    {
+        let error = headers
+            .iter()
+            .find(|header| header.key == HEADER_ERROR)
+            .and_then(|header| header.value)
+            .map(|value| String::from_utf8_lossy(value).into_owned())
+            .unwrap_or_default();
+
+        Ok(Self {
+            source_topic: required(headers, HEADER_SOURCE_TOPIC)?,
+            source_partition: required(headers, HEADER_SOURCE_PARTITION)?,
+            source_offset: required(headers, HEADER_SOURCE_OFFSET)?,
+            service: required(headers, HEADER_SERVICE)?,
+            stage: required(headers, HEADER_STAGE)?,
+            error,
+            failed_at: optional(headers, HEADER_FAILED_AT)?.unwrap_or_default(),
+        })
+    }
+
+    /// Write the metadata into headers to attach to a dead letter, after the headers of the
+    /// original record.
+    #[must_use]
+    pub fn to_headers<H>(&self, original: Option<&H>) -> OwnedHeaders
+    where
+        H: Headers,
+    {
+        original
+            .map_or_else(OwnedHeaders::new, original_headers)
+            .insert(Header {
+                key: HEADER_SOURCE_TOPIC,
+                value: Some(&self.source_topic),
+            })
+            .insert(Header {
+                key: HEADER_SOURCE_PARTITION,
+                value: Some(&self.source_partition.to_string()),
+            })
+            .insert(Header {
+                key: HEADER_SOURCE_OFFSET,
+                value: Some(&self.source_offset.to_string()),
+            })
+            .insert(Header {
+                key: HEADER_SERVICE,
+                value: Some(&self.service),
+            })
+            .insert(Header {
+                key: HEADER_STAGE,
+                value: Some(&self.stage.to_string()),
+            })
+            .insert(Header {
+                key: HEADER_ERROR,
+                value: Some(&self.error),
+            })
+            .insert(Header {
+                key: HEADER_FAILED_AT,
+                value: Some(&self.failed_at.to_string()),
+            })
+    }
+}
+
+/// Copy the headers of a record, leaving out the metadata of a dead letter.
+///
+/// This restores the headers of the original record from those of its dead letter.
+#[must_use]
+pub fn original_headers<H>(headers: &H) -> OwnedHeaders
+where
+    H: Headers,
+{
+    headers
+        .iter()
+        .filter(|header| !header.key.starts_with(HEADER_PREFIX))
+        .fold(OwnedHeaders::new(), |headers, header| {
+            headers.insert(header)
+        })
+}
+
+/// Publishes records that failed to be processed to a dead-letter topic.
+///
+/// As the offset of a record that failed can only be committed once it is published, and holds
+/// back the commits of its partition until then, publishing is retried until it succeeds or
+/// shutdown is requested.
+#[derive(Clone)]
+pub struct DeadLetterProducer {
+    producer: FutureProducer,
+    service: String,
+    shutdown: ShutdownController,
+    topic: String,
+}
+
+impl DeadLetterProducer {
+    /// Construct a producer of dead letters for a service, which stops retrying to publish dead
+    /// letters once shutdown is requested with the controller.
+    #[must_use]
+    pub fn new(
+        producer: FutureProducer,
+        topic: String,
+        service: &str,
+        shutdown: ShutdownController,
+    ) -> Self {
+        Self {
+            producer,
+            service: service.to_string(),
+            shutdown,
+            topic,
+        }
+    }
+
+    /// Publish a record that failed at a given stage of processing, retrying with an increasing
+    /// delay until it is published.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the last attempt if shutdown is requested before the dead letter is
+    /// published.
+    pub async fn publish<M>(
+        &self,
+        message: &M,
+        stage: DeadLetterStage,
+        error: impl Display,
+    ) -> Result<(), KafkaError>
+    where
+        M: Message,
+    {
+        let dead_letter = DeadLetter::new(message, &self.service, stage, error);
+        let mut delay = RETRY_DELAY;
+
+        loop {
+            let Err(error) = self.send(message, &dead_letter).await else {
+                return Ok(());
+            };
+            println!(
+                "{:?}",
+                Err::<(), KafkaError>(error.clone()).into_diagnostic()
+            );
+
+            tokio::select! {
+                () = self.shutdown.requested() => return Err(error),
+                () = time::sleep(delay) => {}
+            }
+            delay = (delay * 2).min(RETRY_MAX_DELAY);
+        }
+    }
+
+    /// Report a record that failed to be processed at a given stage, and publish it to the
+    /// dead-letter topic.
+    ///
+    /// The record is marked as processed with the committer once it is published, as it is kept
+    /// in the dead-letter topic from then on. If shutdown is requested before it is published, it
+    /// is left unprocessed, such that it is consumed again after a restart.
+    pub async fn dead_letter<C, M, E>(
+        &self,
+        committer: &OffsetCommitter<C>,
+        message: &M,
+        stage: DeadLetterStage,
+        error: E,
+    ) where
+        C: Consumer<OffsetCommitterContext>,
+        M: Message,
+        E: Error + Send + Sync + 'static,
+    {
+        let description = error.to_string();
+        println!("{:?}", Err::<(), E>(error).into_diagnostic());
+
+        if let Err(error) = self.publish(message, stage, description).await {
+            println!("{:?}", Err::<(), KafkaError>(error).into_diagnostic());
+
+            return;
+        }
+
+        if let Err(error) = committer.complete(message) {
+            println!("{:?}", Err::<(), KafkaError>(error).into_diagnostic());
+        }
+    }
+
+    async fn send<M>(&self, message: &M, dead_letter: &DeadLetter) -> Result<(), KafkaError>
+    where
+        M: Message,
+    {
+        let headers = dead_letter.to_headers(message.headers());
+
+        let mut record = FutureRecord::<[u8], [u8]>::to(&self.topic)
+            .payload(message.payload().unwrap_or_default())
+            .headers(headers);
+        if let Some(key) = message.key() {
+            record = record.key(key);
+        }
+
+        self.producer
+            .send(record, Timeout::Never)
+            .await
+            .map(|_| ())
+            .map_err(|(error, _)| error)
+    }
+}
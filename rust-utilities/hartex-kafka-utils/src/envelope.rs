@@ -0,0 +1,363 @@
+/*
+ * SPDX-License-Identifier: AGPL-3.0-only
+ *
+ * This file is part of HarTex.
+ *
+ * HarTex
+ * Copyright (c) 2021-2024 HarTex Project Developers
+ *
+ * HarTex is free software; you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation; either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * HarTex is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License along
+ * with HarTex. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! # Kafka Record Envelope
+//!
+//! Inbound gateway payloads are forwarded as-is in the payload of Kafka records, while metadata
+//! about them (the shard that received them, their event type and sequence, the time they were
+//! received and the schema version of the metadata itself) are carried in the headers of the
+//! records.
+
+use std::error::Error;
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::str;
+use std::str::FromStr;
+use std::time::SystemTime;
+
+use hartex_discord_core::discord::model::gateway::event::EventType;
+use rdkafka::message::Header;
+use rdkafka::message::Headers;
+use rdkafka::message::OwnedHeaders;
+
+/// The current schema version of the envelope.
+pub const SCHEMA_VERSION: u16 = 1;
+
+/// Header carrying the shard number.
+pub const HEADER_SHARD_ID: &str = "hartex-shard-id";
+
+/// Header carrying the total number of shards in the set of the shard.
+pub const HEADER_SHARD_COUNT: &str = "hartex-shard-count";
+
+/// Header carrying the event type of the gateway payload, if any.
+pub const HEADER_EVENT_TYPE: &str = "hartex-event-type";
+
+/// Header carrying the sequence number of the gateway payload, if any.
+pub const HEADER_SEQUENCE: &str = "hartex-sequence";
+
+/// Header carrying the time the gateway payload was received, in milliseconds since the Unix
+/// epoch.
+pub const HEADER_RECEIVED_AT: &str = "hartex-received-at";
+
+/// Header carrying the schema version of the envelope.
+pub const HEADER_SCHEMA_VERSION: &str = "hartex-schema-version";
+
+/// The event types of the inbound gateway payloads consumed by the event handlers built into the
+/// worker process.
+///
+/// The leader routes payloads of these event types to the worker process, in addition to those
+/// consumed by the event handlers of plugins. The worker process refuses to start if any of its
+/// built-in event handlers subscribes to an event type not listed here.
+pub const WORKER_EVENT_TYPES: [EventType; 3] = [
+    EventType::GuildCreate,
+    EventType::InteractionCreate,
+    EventType::Ready,
+];
+
+/// Metadata of an inbound gateway payload.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InboundGatewayEnvelope {
+    /// The shard number.
+    pub shard_id: u32,
+    /// The total number of shards in the set of the shard.
+    pub shard_count: u32,
+    /// The event type of the payload, if any.
+    pub event_type: Option<String>,
+    /// The sequence number of the payload, if any.
+    pub sequence: Option<u64>,
+    /// The time the payload was received, in milliseconds since the Unix epoch.
+    pub received_at: u64,
+    /// The schema version of the envelope.
+    pub schema_version: u16,
+}
+
+impl InboundGatewayEnvelope {
+    /// Construct the envelope of a payload that was just received.
+    #[must_use]
+    pub fn new(
+        shard_id: u32,
+        shard_count: u32,
+        event_type: Option<&str>,
+        sequence: Option<u64>,
+    ) -> Self {
+        #[allow(clippy::cast_possible_truncation)]
+        let received_at = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_millis() as u64);
+
+        Self {
+            shard_id,
+            shard_count,
+            event_type: event_type.map(String::from),
+            sequence,
+            received_at,
+            schema_version: SCHEMA_VERSION,
+        }
+    }
+
+    /// Read the envelope from the headers of a record.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a required header is missing or malformed, or if the envelope was
+    /// produced with an unsupported schema version.
+    pub fn from_headers<H>(headers: &H) -> Result<Self, EnvelopeError>
+    where
+        H: Headers,
+    {
+        let schema_version = required::<H, u16>(headers, HEADER_SCHEMA_VERSION)?;
+        if schema_version != SCHEMA_VERSION {
+            return Err(EnvelopeError {
+                kind: EnvelopeErrorKind::UnsupportedSchemaVersion(schema_version),
+            });
+        }
+
+        Ok(Self {
+            shard_id: required(headers, HEADER_SHARD_ID)?,
+            shard_count: required(headers, HEADER_SHARD_COUNT)?,
+            event_type: optional(headers, HEADER_EVENT_TYPE)?,
+            sequence: optional(headers, HEADER_SEQUENCE)?,
+            received_at: required(headers, HEADER_RECEIVED_AT)?,
+            schema_version,
+        })
+    }
+
+    /// Write the envelope into headers to attach to a record.
+    #[must_use]
+    pub fn to_headers(&self) -> OwnedHeaders {
+        let mut headers = OwnedHeaders::new_with_capacity(6)
+            .insert(Header {
+                key: HEADER_SCHEMA_VERSION,
+                value: Some(&self.schema_version.to_string()),
+            })
+            .insert(Header {
+                key: HEADER_SHARD_ID,
+                value: Some(&self.shard_id.to_string()),
+            })
+            .insert(Header {
+                key: HEADER_SHARD_COUNT,
+                value: Some(&self.shard_count.to_string()),
+            })
+            .insert(Header {
+                key: HEADER_RECEIVED_AT,
+                value: Some(&self.received_at.to_string()),
+            });
+
+        if let Some(event_type) = &self.event_type {
+            headers = headers.insert(Header {
+                key: HEADER_EVENT_TYPE,
+                value: Some(event_type),
+            });
+        }
+
+        if let Some(sequence) = self.sequence {
+            headers = headers.insert(Header {
+                key: HEADER_SEQUENCE,
+                value: Some(&sequence.to_string()),
+            });
+        }
+
+        headers
+    }
+}
+
+/// Find the value of a header and parse it, if present.
+pub(crate) fn optional<H, T>(headers: &H, key: &'static str) -> Result<Option<T>, EnvelopeError>
+where
+    H: Headers,
+    T: FromStr,
+{
+    let Some(value) = headers
+        .iter()
+        .find(|header| header.key == key)
+        .and_then(|header| header.value)
+    else {
+        return Ok(None);
+    };
+
+    str::from_utf8(value)
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .map(Some)
+        .ok_or(EnvelopeError {
+            kind: EnvelopeErrorKind::MalformedHeader(key),
+        })
+}
+
+/// Find the value of a header and parse it, returning an error if it is absent.
+pub(crate) fn required<H, T>(headers: &H, key: &'static str) -> Result<T, EnvelopeError>
+where
+    H: Headers,
+    T: FromStr,
+{
+    optional(headers, key)?.ok_or(EnvelopeError {
+        kind: EnvelopeErrorKind::MissingHeader(key),
+    })
+}
+
+/// Envelope error.
+#[allow(clippy::module_name_repetitions)]
+#[derive(Clone, Debug)]
+pub struct EnvelopeError {
+    /// The error type.
+    pub kind: EnvelopeErrorKind,
+}
+
+impl Display for EnvelopeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            EnvelopeErrorKind::MalformedHeader(key) => write!(f, "malformed header {key}"),
+            EnvelopeErrorKind::MissingHeader(key) => write!(f, "missing header {key}"),
+            EnvelopeErrorKind::UnsupportedSchemaVersion(version) => {
+                write!(f, "unsupported envelope schema version {version}")
+            }
+        }
+    }
+}
+
+impl Error for EnvelopeError {}
+
+/// The type of envelope error that has occurred.
+#[allow(clippy::module_name_repetitions)]
+#[derive(Clone, Debug)]
+pub enum EnvelopeErrorKind {
+    /// A header could not be parsed.
+    MalformedHeader(&'static str),
+    /// A required header is absent.
+    MissingHeader(&'static str),
+    /// The envelope was produced with a schema version that is not supported.
+    UnsupportedSchemaVersion(u16),
+}
+
+#[cfg(test)]
+mod tests {
+    use rdkafka::message::Header;
+    use rdkafka::message::OwnedHeaders;
+
+    use super::EnvelopeErrorKind;
+    use super::InboundGatewayEnvelope;
+    use super::HEADER_SCHEMA_VERSION;
+    use super::HEADER_SEQUENCE;
+    use super::HEADER_SHARD_COUNT;
+    use super::HEADER_SHARD_ID;
+    use super::SCHEMA_VERSION;
+
+    fn headers(entries: &[(&str, &str)]) -> OwnedHeaders {
+        entries
+            .iter()
+            .fold(OwnedHeaders::new(), |headers, &(key, value)| {
+                headers.insert(Header {
+                    key,
+                    value: Some(value),
+                })
+            })
+    }
+
+    #[test]
+    fn envelope_round_trips_through_headers() {
+        let envelope = InboundGatewayEnvelope::new(300, 512, Some("GUILD_CREATE"), Some(42));
+
+        let read = InboundGatewayEnvelope::from_headers(&envelope.to_headers()).unwrap();
+
+        assert_eq!(read, envelope);
+    }
+
+    #[test]
+    fn envelope_without_event_type_or_sequence_round_trips_through_headers() {
+        let envelope = InboundGatewayEnvelope::new(256, 257, None, None);
+
+        let read = InboundGatewayEnvelope::from_headers(&envelope.to_headers()).unwrap();
+
+        assert_eq!(read, envelope);
+    }
+
+    #[test]
+    fn missing_schema_version_is_rejected() {
+        let error = InboundGatewayEnvelope::from_headers(&headers(&[])).unwrap_err();
+
+        assert!(matches!(
+            error.kind,
+            EnvelopeErrorKind::MissingHeader(HEADER_SCHEMA_VERSION)
+        ));
+    }
+
+    #[test]
+    fn unsupported_schema_version_is_rejected() {
+        let version = (SCHEMA_VERSION + 1).to_string();
+        let error =
+            InboundGatewayEnvelope::from_headers(&headers(&[(HEADER_SCHEMA_VERSION, &version)]))
+                .unwrap_err();
+
+        assert!(matches!(
+            error.kind,
+            EnvelopeErrorKind::UnsupportedSchemaVersion(unsupported)
+                if unsupported == SCHEMA_VERSION + 1
+        ));
+    }
+
+    #[test]
+    fn missing_required_header_is_rejected() {
+        let version = SCHEMA_VERSION.to_string();
+        let error = InboundGatewayEnvelope::from_headers(&headers(&[
+            (HEADER_SCHEMA_VERSION, &version),
+            (HEADER_SHARD_ID, "300"),
+        ]))
+        .unwrap_err();
+
+        assert!(matches!(
+            error.kind,
+            EnvelopeErrorKind::MissingHeader(HEADER_SHARD_COUNT)
+        ));
+    }
+
+    #[test]
+    fn malformed_required_header_is_rejected() {
+        let version = SCHEMA_VERSION.to_string();
+        let error = InboundGatewayEnvelope::from_headers(&headers(&[
+            (HEADER_SCHEMA_VERSION, &version),
+            (HEADER_SHARD_ID, "shard"),
+        ]))
+        .unwrap_err();
+
+        assert!(matches!(
+            error.kind,
+            EnvelopeErrorKind::MalformedHeader(HEADER_SHARD_ID)
+        ));
+    }
+
+    #[test]
+    fn malformed_optional_header_is_rejected() {
+        let mut envelope = InboundGatewayEnvelope::new(300, 512, None, None).to_headers();
+        envelope = envelope.insert(Header {
+            key: HEADER_SEQUENCE,
+            value: Some("sequence"),
+        });
+
+        let error = InboundGatewayEnvelope::from_headers(&envelope).unwrap_err();
+
+        assert!(matches!(
+            error.kind,
+            EnvelopeErrorKind::MalformedHeader(HEADER_SEQUENCE)
+        ));
+    }
+}
@@ -0,0 +1,90 @@
+/*
+ * SPDX-License-Identifier: AGPL-3.0-only
+ *
+ * This file is part of HarTex.
+ *
+ * HarTex
+ * Copyright (c) 2021-2024 HarTex Project Developers
+ *
+ * HarTex is free software; you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation; either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * HarTex is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License along
+ * with HarTex. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! # Kafka Record Keys
+//!
+//! Records exchanged between the leader and other components are keyed by the shard they
+//! originate from or are destined to, such that records of the same shard are kept in order. As
+//! two shard sets with different shard counts may coexist briefly while resharding, a shard is
+//! identified by both its number and the total number of shards in its set.
+//!
+//! Consumers should not parse shards from keys; inbound gateway payloads carry their shard in
+//! their [envelope](crate::envelope).
+
+/// Prefix of the keys of inbound gateway payloads.
+pub const INBOUND_GATEWAY_PAYLOAD: &str = "INBOUND_GATEWAY_PAYLOAD_SHARD_";
+
+/// Prefix of the keys of inbound shard lifecycle events.
+pub const INBOUND_GATEWAY_LIFECYCLE: &str = "INBOUND_GATEWAY_LIFECYCLE_SHARD_";
+
+/// Key of outbound gateway commands, which carry the shards they target in their envelope.
+pub const OUTBOUND_GATEWAY_COMMAND: &str = "OUTBOUND_GATEWAY_COMMAND";
+
+/// Key of outbound presence updates, which target every shard.
+pub const OUTBOUND_UPDATE_PRESENCE: &str = "OUTBOUND_UPDATE_PRESENCE";
+
+/// Construct a key from a prefix and a shard.
+#[must_use]
+pub fn shard_key(prefix: &str, shard_id: u32, shard_count: u32) -> String {
+    format!("{prefix}{shard_id}_{shard_count}")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::shard_key;
+    use super::INBOUND_GATEWAY_PAYLOAD;
+
+    #[test]
+    fn shard_key_contains_shard_number_and_count() {
+        assert_eq!(
+            shard_key(INBOUND_GATEWAY_PAYLOAD, 300, 512),
+            "INBOUND_GATEWAY_PAYLOAD_SHARD_300_512"
+        );
+    }
+
+    #[test]
+    fn shard_keys_are_distinct_beyond_255_shards() {
+        let keys = (0..1024)
+            .map(|shard_id| shard_key(INBOUND_GATEWAY_PAYLOAD, shard_id, 1024))
+            .collect::<HashSet<_>>();
+
+        assert_eq!(keys.len(), 1024);
+        assert_ne!(
+            shard_key(INBOUND_GATEWAY_PAYLOAD, 256, 512),
+            shard_key(INBOUND_GATEWAY_PAYLOAD, 0, 512)
+        );
+    }
+
+    #[test]
+    fn shard_keys_distinguish_shard_counts() {
+        assert_ne!(
+            shard_key(INBOUND_GATEWAY_PAYLOAD, 1, 12),
+            shard_key(INBOUND_GATEWAY_PAYLOAD, 11, 2)
+        );
+        assert_ne!(
+            shard_key(INBOUND_GATEWAY_PAYLOAD, 300, 512),
+            shard_key(INBOUND_GATEWAY_PAYLOAD, 300, 600)
+        );
+    }
+}
@@ -27,5 +27,11 @@
 #![deny(warnings)]
 #![feature(iter_intersperse)]
 
+pub mod deadletter;
+pub mod envelope;
+pub mod keys;
+pub mod lifecycle;
+pub mod offsets;
+pub mod outbound;
 pub mod traits;
 pub mod types;
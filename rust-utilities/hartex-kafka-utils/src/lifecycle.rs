@@ -0,0 +1,76 @@
+/*
+ * SPDX-License-Identifier: AGPL-3.0-only
+ *
+ * This file is part of HarTex.
+ *
+ * HarTex
+ * Copyright (c) 2021-2024 HarTex Project Developers
+ *
+ * HarTex is free software; you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation; either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * HarTex is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License along
+ * with HarTex. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! # Shard Lifecycle Events
+//!
+//! Events published by the leader when the state of one of its shards changes, such that other
+//! components know when a shard went down, and when the gateway acknowledges a heartbeat of one of
+//! its shards, such that other components know the latency of shards they do not hold.
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// A lifecycle event of a shard.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ShardLifecycleEvent {
+    /// The number of the shard.
+    pub shard_id: u32,
+    /// The total number of shards.
+    pub shard_count: u32,
+    /// The kind of lifecycle event.
+    pub kind: ShardLifecycleEventKind,
+}
+
+/// The kind of lifecycle event of a shard.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case", tag = "type")]
+pub enum ShardLifecycleEventKind {
+    /// The gateway connection of the shard was closed.
+    Disconnected {
+        /// The close code sent by the gateway, if any.
+        close_code: Option<u16>,
+        /// The reason for the close sent by the gateway, if any.
+        reason: Option<String>,
+        /// How the shard is recovering from the close.
+        disconnect_kind: ShardDisconnectKind,
+    },
+    /// The gateway acknowledged a heartbeat of the shard.
+    HeartbeatAcknowledged {
+        /// The average heartbeat latency of the shard in milliseconds, if any was recorded.
+        average_latency: Option<u64>,
+        /// The latency of the most recent heartbeat of the shard in milliseconds, if any was
+        /// recorded.
+        latest_latency: Option<u64>,
+    },
+}
+
+/// How a shard recovers from its gateway connection being closed.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ShardDisconnectKind {
+    /// The shard reconnects and resumes its session.
+    Resumable,
+    /// The shard reconnects, but its session was invalidated and it has to identify anew.
+    Reconnect,
+    /// The shard cannot reconnect without intervention.
+    Fatal,
+}
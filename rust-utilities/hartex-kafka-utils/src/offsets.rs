@@ -0,0 +1,190 @@
+/*
+ * SPDX-License-Identifier: AGPL-3.0-only
+ *
+ * This file is part of HarTex.
+ *
+ * HarTex
+ * Copyright (c) 2021-2024 HarTex Project Developers
+ *
+ * HarTex is free software; you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation; either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * HarTex is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License along
+ * with HarTex. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! # Consumer Offset Commits
+//!
+//! Consumers commit the offsets of records only after they are processed, such that records being
+//! processed when a consumer stops are consumed again when it restarts. As records may be
+//! processed concurrently and finish out of order, the offset committed for a partition never
+//! goes past the earliest record of the partition that is still being processed.
+//!
+//! The records tracked for a partition are forgotten when the partition is revoked from the
+//! consumer, as they are consumed again by the consumer the partition is assigned to.
+
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use rdkafka::consumer::CommitMode;
+use rdkafka::consumer::Consumer;
+use rdkafka::consumer::ConsumerContext;
+use rdkafka::consumer::Rebalance;
+use rdkafka::error::KafkaError;
+use rdkafka::ClientContext;
+use rdkafka::Message;
+use rdkafka::Offset;
+use rdkafka::TopicPartitionList;
+
+/// The offsets of the records of a partition.
+#[derive(Default)]
+struct PartitionOffsets {
+    /// The offsets of the records being processed.
+    pending: BTreeSet<i64>,
+    /// The offset after the latest record received.
+    next: i64,
+    /// The offset last committed.
+    committed: i64,
+}
+
+/// The context of a consumer whose offsets are committed by an offset committer, holding the
+/// offsets of the records of each partition assigned to the consumer.
+///
+/// Otherwise, a record of a revoked partition that is never processed by the consumer would hold
+/// back the commits of the partition for good once the partition is assigned to it again.
+#[allow(clippy::module_name_repetitions)]
+#[derive(Default)]
+pub struct OffsetCommitterContext {
+    partitions: Mutex<HashMap<(String, i32), PartitionOffsets>>,
+}
+
+impl ClientContext for OffsetCommitterContext {}
+
+impl ConsumerContext for OffsetCommitterContext {
+    fn pre_rebalance(&self, rebalance: &Rebalance<'_>) {
+        let Rebalance::Revoke(revoked) = rebalance else {
+            return;
+        };
+
+        let mut partitions = self.partitions.lock().unwrap();
+        for element in revoked.elements() {
+            partitions.remove(&(element.topic().to_string(), element.partition()));
+        }
+    }
+}
+
+/// Tracks the records being processed and commits the offsets of those that are processed.
+#[allow(clippy::module_name_repetitions)]
+pub struct OffsetCommitter<C> {
+    consumer: Arc<C>,
+}
+
+impl<C> OffsetCommitter<C>
+where
+    C: Consumer<OffsetCommitterContext>,
+{
+    /// Construct a committer for a consumer created with an [`OffsetCommitterContext`].
+    ///
+    /// The consumer should be configured not to commit offsets automatically.
+    #[must_use]
+    pub fn new(consumer: Arc<C>) -> Self {
+        Self { consumer }
+    }
+
+    /// Track a record that was just received, before it is processed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a thread panicked while tracking offsets.
+    pub fn track<M>(&self, message: &M)
+    where
+        M: Message,
+    {
+        let mut partitions = self.consumer.context().partitions.lock().unwrap();
+        let offsets = partitions
+            .entry((message.topic().to_string(), message.partition()))
+            .or_default();
+
+        offsets.pending.insert(message.offset());
+        offsets.next = offsets.next.max(message.offset() + 1);
+    }
+
+    /// Mark a tracked record as processed, committing the offsets of the partition of the record
+    /// up to the earliest record that is still being processed.
+    ///
+    /// Records that failed to be processed are only to be marked as processed once they are
+    /// published to the dead-letter topic.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the offset cannot be committed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a thread panicked while tracking offsets.
+    pub fn complete<M>(&self, message: &M) -> Result<(), KafkaError>
+    where
+        M: Message,
+    {
+        let offset = {
+            let mut partitions = self.consumer.context().partitions.lock().unwrap();
+            let Some(offsets) =
+                partitions.get_mut(&(message.topic().to_string(), message.partition()))
+            else {
+                return Ok(());
+            };
+
+            offsets.pending.remove(&message.offset());
+            let committable = offsets.pending.first().copied().unwrap_or(offsets.next);
+            if committable <= offsets.committed {
+                return Ok(());
+            }
+
+            offsets.committed = committable;
+            committable
+        };
+
+        let mut list = TopicPartitionList::new();
+        list.add_partition_offset(message.topic(), message.partition(), Offset::Offset(offset))?;
+
+        self.consumer.commit(&list, CommitMode::Async)
+    }
+
+    /// Commit the offsets of every partition synchronously, such that they are committed by the
+    /// time the consumer is closed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the offsets cannot be committed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a thread panicked while tracking offsets.
+    pub fn commit_all(&self) -> Result<(), KafkaError> {
+        let mut list = TopicPartitionList::new();
+        {
+            let partitions = self.consumer.context().partitions.lock().unwrap();
+            for ((topic, partition), offsets) in partitions.iter() {
+                if offsets.committed > 0 {
+                    let offset = Offset::Offset(offsets.committed);
+                    list.add_partition_offset(topic, *partition, offset)?;
+                }
+            }
+        }
+
+        if list.count() == 0 {
+            return Ok(());
+        }
+
+        self.consumer.commit(&list, CommitMode::Sync)
+    }
+}
@@ -0,0 +1,95 @@
+/*
+ * SPDX-License-Identifier: AGPL-3.0-only
+ *
+ * This file is part of HarTex.
+ *
+ * HarTex
+ * Copyright (c) 2021-2024 HarTex Project Developers
+ *
+ * HarTex is free software; you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation; either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * HarTex is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License along
+ * with HarTex. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! # Outbound Gateway Commands
+//!
+//! Envelopes of gateway commands sent to the leader, to be sent to the gateway by the shards they
+//! target.
+
+use hartex_discord_core::discord::model::gateway::payload::outgoing::RequestGuildMembers;
+use hartex_discord_core::discord::model::gateway::payload::outgoing::UpdatePresence;
+use hartex_discord_core::discord::model::gateway::payload::outgoing::UpdateVoiceState;
+use hartex_discord_core::discord::model::id::marker::GuildMarker;
+use hartex_discord_core::discord::model::id::Id;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// An envelope of a gateway command, along with the shards it targets.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct OutboundEnvelope {
+    /// The shards the command targets.
+    pub target: ShardTarget,
+    /// The gateway command.
+    pub command: OutboundCommand,
+}
+
+/// The shards targeted by a gateway command.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case", tag = "type")]
+pub enum ShardTarget {
+    /// A specific shard.
+    Shard {
+        /// The number of the shard.
+        shard_id: u32,
+        /// The total number of shards.
+        shard_count: u32,
+    },
+    /// The shard receiving the events of a guild.
+    Guild {
+        /// The identifier of the guild.
+        guild_id: Id<GuildMarker>,
+    },
+    /// Every shard.
+    All,
+}
+
+impl ShardTarget {
+    /// Whether a shard is targeted.
+    #[must_use]
+    pub fn matches(&self, shard_id: u32, shard_count: u32) -> bool {
+        match *self {
+            Self::Shard {
+                shard_id: target_id,
+                shard_count: target_count,
+            } => target_id == shard_id && target_count == shard_count,
+            // https://discord.com/developers/docs/topics/gateway#sharding-sharding-formula
+            Self::Guild { guild_id } => {
+                (guild_id.get() >> 22) % u64::from(shard_count) == u64::from(shard_id)
+            }
+            Self::All => true,
+        }
+    }
+}
+
+/// A gateway command.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(content = "data", rename_all = "snake_case", tag = "type")]
+pub enum OutboundCommand {
+    /// Request the members of a guild.
+    RequestGuildMembers(RequestGuildMembers),
+    /// Update the presence of the bot.
+    UpdatePresence(UpdatePresence),
+    /// Update the voice state of the bot in a guild.
+    UpdateVoiceState(UpdateVoiceState),
+    /// A raw JSON gateway payload.
+    Raw(String),
+}
@@ -37,6 +37,13 @@ pub trait ClientConfigUtils {
     /// Configure the duration for delivery timeout.
     fn delivery_timeout_ms(&mut self, timeout: u32) -> &mut Self;
 
+    /// Configure whether consumer offsets are committed automatically in the background.
+    fn enable_auto_commit(&mut self, enable: bool) -> &mut Self;
+
+    /// Configure whether the offset of every record returned by the consumer is stored
+    /// automatically, to be committed by automatic commits.
+    fn enable_auto_offset_store(&mut self, enable: bool) -> &mut Self;
+
     /// Configure group id.
     fn group_id(&mut self, group_id: &str) -> &mut Self;
 }
@@ -57,6 +64,14 @@ impl ClientConfigUtils for ClientConfig {
         self.set("delivery.timeout.ms", timeout.to_string())
     }
 
+    fn enable_auto_commit(&mut self, enable: bool) -> &mut Self {
+        self.set("enable.auto.commit", enable.to_string())
+    }
+
+    fn enable_auto_offset_store(&mut self, enable: bool) -> &mut Self {
+        self.set("enable.auto.offset.store", enable.to_string())
+    }
+
     fn group_id(&mut self, group_id: &str) -> &mut Self {
         self.set("group.id", group_id)
     }